@@ -14,7 +14,8 @@ export interface MakerInfo {
   id: string;
 }
 
-export type MakerState = "running" | "stopped";
+/** "locked" makers wait for their wallet password via makers.unlock */
+export type MakerState = "running" | "stopped" | "locked";
 
 export interface MakerInfoDetailed {
   id: string;
@@ -22,6 +23,8 @@ export interface MakerInfoDetailed {
   rpc: string;
   zmq: string;
   wallet_name?: string;
+  /** whether the wallet password survives a dashboard restart */
+  persist_password: boolean;
   data_directory?: string;
   network_port: number;
   rpc_port: number;
@@ -49,20 +52,173 @@ export interface BalanceInfo {
   contract: number;
   fidelity: number;
   spendable: number;
+  /** frozen coins held back from the other balances; null when unknown */
+  frozen: number | null;
 }
 
+export type UtxoPool = "regular" | "swept" | "swap" | "contract" | "fidelity";
+
 export interface UtxoInfo {
-  addr: string;
+  /** txid:vout */
+  outpoint: string;
+  txid: string;
+  vout: number;
+  /** null when the output script has no address form */
+  addr: string | null;
   /** satoshis */
   amount: number;
   confirmations: number;
-  utxo_type: string;
+  /** p2wpkh, p2wsh, p2tr, p2sh, p2pkh or unknown */
+  script_type: string;
+  /** BIP-32 path from the descriptor's key origin */
+  derivation_path?: string | null;
+  /** absent while unconfirmed */
+  block_height?: number | null;
+  /** unix seconds */
+  block_time?: number | null;
+  utxo_type: UtxoPool;
+  fidelity_index?: number | null;
+  /** relative timelock in blocks, contract outputs only */
+  contract_timelock?: number | null;
+  /** swap whose funding, contract or recovery tx created this UTXO */
+  swap_id?: string | null;
+  /** label of the UTXO, falling back to its address label */
+  label?: string | null;
+  /** frozen coins are never picked by coin selection */
+  frozen: boolean;
+}
+
+export interface FrozenUtxoInfo {
+  outpoint: string;
+  frozen: boolean;
+  /** also locked in Bitcoin Core, keeping it out of swap funding */
+  core_locked: boolean;
+}
+
+export type RescanKind = "rescan" | "reimport";
+
+export interface RescanStatus {
+  kind: RescanKind;
+  from_height: number;
+  started_at: number;
+  finished_at?: number | null;
+  running: boolean;
+  /** Bitcoin Core's scan progress from 0 to 1, while it is scanning */
+  progress?: number | null;
+  error?: string | null;
+}
+
+export interface FidelityBondDto {
+  index: number;
+  /** txid:vout */
+  outpoint: string;
+  amount: number;
+  /** absolute block height the bond unlocks at */
+  locktime: number;
+  conf_height?: number | null;
+  cert_expiry?: number | null;
+  /** sats; absent while it cannot be computed */
+  bond_value?: number | null;
+  /** the bond the maker currently advertises */
+  advertised: boolean;
+  spent: boolean;
+  expired?: boolean | null;
+  blocks_to_expiry?: number | null;
+  secs_to_expiry?: number | null;
+}
+
+export interface AdvertisedBondInfo {
+  maker_id: string;
+  index: number;
+  amount: number;
+  locktime: number;
+  bond_value: number;
+}
+
+export interface BondValueEstimate {
+  amount: number;
+  timelock: number;
+  lockup_days: number;
+  /** sats, as a taker scores a freshly confirmed bond */
+  bond_value: number;
+  /** accepted as a maker's fidelity_timelock */
+  timelock_allowed: boolean;
+  /** bonds advertised by the dashboard's other makers, highest first */
+  advertised: AdvertisedBondInfo[];
+  rank?: number | null;
+}
+
+export interface RenewalPolicy {
+  enabled: boolean;
+  /** renew once all bonds are within this many blocks of expiry */
+  blocks_before_expiry: number;
+  /** redeem expired bonds so their funds go into the renewal */
+  reuse_redeemed_funds: boolean;
+  /** only record what would be done */
+  dry_run: boolean;
+  /** sat/vB; estimated when unset */
+  feerate?: number | null;
+}
+
+export interface RenewalRecord {
+  at: number;
+  height: number;
+  action: "redeem" | "create";
+  dry_run: boolean;
+  bond_index?: number | null;
+  amount?: number | null;
+  locktime?: number | null;
+  txid?: string | null;
+  error?: string | null;
+}
+
+export interface CreateFidelityRequest {
+  /** sats; defaults to the maker's fidelity_amount */
+  amount?: number;
+  /** blocks from the tip; defaults to the maker's fidelity_timelock */
+  timelock?: number;
+  /** sat/vB — provide either this or fee_target; defaults to "normal" */
+  feerate?: number;
+  fee_target?: FeePreset | number;
+  /** only report the funding requirements */
+  dry_run?: boolean;
+}
+
+export interface RedeemFidelityRequest {
+  /** sat/vB — provide either this or fee_target; defaults to "normal" */
+  feerate?: number;
+  fee_target?: FeePreset | number;
+}
+
+export interface FidelityFundingInfo {
+  index?: number | null;
+  txid?: string | null;
+  amount: number;
+  timelock: number;
+  /** absolute block height the bond unlocks at */
+  locktime: number;
+  feerate: number;
+  /** estimated fee, in sats */
+  fee: number;
+  /** amount plus fee */
+  required: number;
+  spendable: number;
+  sufficient: boolean;
+}
+
+export interface RescanRequest {
+  /** first block to scan from; defaults to genesis */
+  from_height?: number;
+  /** last block to scan (rescan only); defaults to the tip */
+  stop_height?: number;
 }
 
 export interface MakerStatus {
   id: string;
   alive: boolean;
   is_server_running: boolean;
+  /** latest rescan or descriptor re-import, if one was started */
+  rescan?: RescanStatus | null;
 }
 
 export interface HealthResponse {
@@ -149,6 +305,153 @@ export interface SwapReportDto {
   output_swap_utxos: [number, string][];
 }
 
+export interface SwapReportPage {
+  reports: SwapReportDto[];
+  /** pass as `cursor` for the next page; null on the last page */
+  next_cursor: string | null;
+}
+
+export interface SwapReportQuery {
+  status?: string;
+  role?: string;
+  /** unix timestamps bounding the swap's end */
+  from?: number;
+  to?: number;
+  /** incoming amount in sats */
+  min_amount?: number;
+  max_amount?: number;
+  /** text to find in the swap id or a txid */
+  q?: string;
+  cursor?: string;
+  limit?: number;
+}
+
+export type AnalyticsWindow = "day" | "week" | "month" | "custom";
+
+export interface AnalyticsQuery {
+  window?: AnalyticsWindow;
+  /** unix timestamps; `from` is required for the custom window */
+  from?: number;
+  to?: number;
+  /** chart interval in seconds */
+  bucket?: number;
+}
+
+export interface SwapStats {
+  swaps: number;
+  successful: number;
+  failed: number;
+  success_rate?: number | null;
+  failure_rate?: number | null;
+  earned: number;
+  mining_fees: number;
+  net_profit: number;
+  volume: number;
+  avg_duration_secs?: number | null;
+}
+
+export interface AnalyticsBucket {
+  start: number;
+  end: number;
+  stats: SwapStats;
+}
+
+export interface SwapAnalytics {
+  from: number;
+  to: number;
+  bucket_secs: number;
+  totals: SwapStats;
+  buckets: AnalyticsBucket[];
+}
+
+export interface FleetAnalytics extends SwapAnalytics {
+  makers: { maker_id: string; totals: SwapStats }[];
+}
+
+export interface BalanceHistoryPoint {
+  at: number;
+  samples: number;
+  /** share of samples alive / running, 0 to 1 */
+  alive: number;
+  running: number;
+  height?: number | null;
+  balance?: BalanceInfo | null;
+  utxos?: {
+    regular: number;
+    swept: number;
+    swap: number;
+    contract: number;
+    fidelity: number;
+  } | null;
+}
+
+export interface ReturnOnCapital {
+  from: number;
+  to: number;
+  swaps: number;
+  net_profit: number;
+  avg_regular: number;
+  avg_swap: number;
+  avg_fidelity: number;
+  avg_committed: number;
+  samples: number;
+  /** no samples covered the period; current balances were used */
+  estimated: boolean;
+  /** annualised fractions, e.g. 0.05 = 5% a year */
+  annualised_yield?: number | null;
+  liquid_yield?: number | null;
+  opportunity_rate?: number | null;
+  fidelity_opportunity_cost: number;
+  profit_after_fidelity_cost: number;
+}
+
+export type SwapErrorCategory =
+  | "timeout"
+  | "network"
+  | "insufficient-funds"
+  | "protocol"
+  | "broadcast"
+  | "other"
+  | "unknown";
+
+export interface ErrorCategoryStats {
+  category: SwapErrorCategory;
+  failed: number;
+  recovered: number;
+  last_seen: number;
+  last_error: string | null;
+}
+
+export interface RecoveryInfo {
+  swap_id: string;
+  role: string;
+  status: string;
+  end_timestamp: number;
+  category: SwapErrorCategory;
+  error_message: string | null;
+  recovery_duration_seconds: number;
+  contract_txids: string[];
+  recovery_txids: string[];
+}
+
+export interface PendingRecovery {
+  utxo: UtxoInfo;
+  timelock: number | null;
+  /** 0 once the timelock path can be spent */
+  blocks_remaining: number | null;
+  spendable_at_height: number | null;
+}
+
+export interface RecoveryInsights {
+  failed: number;
+  recovered: number;
+  categories: ErrorCategoryStats[];
+  recoveries: RecoveryInfo[];
+  pending: PendingRecovery[];
+  tip_height: number | null;
+  pending_error: string | null;
+}
+
 // ─── Request bodies ───────────────────────────────────────────────────────────
 
 export interface CreateMakerRequest {
@@ -160,6 +463,8 @@ export interface CreateMakerRequest {
   tor_auth?: string;
   wallet_name?: string;
   password?: string;
+  /** default true; when false the maker starts locked after a restart */
+  persist_password?: boolean;
   data_directory?: string;
   network_port?: number;
   rpc_port?: number;
@@ -173,6 +478,29 @@ export interface CreateMakerRequest {
   amount_relative_fee_pct?: number;
   time_relative_fee_pct?: number;
   nostr_relays?: string[];
+  /** restore the wallet from a backup instead of generating a new one */
+  restore?: RestoreWalletRequest;
+}
+
+export interface RestoreWalletRequest {
+  /** contents of a downloaded wallet backup file */
+  backup: string;
+  passphrase: string;
+}
+
+export interface WalletBackupRequest {
+  /** must repeat the maker ID */
+  confirm: string;
+  /** current wallet password, if the wallet is encrypted */
+  password?: string;
+  /** passphrase the backup is encrypted with */
+  passphrase: string;
+}
+
+export interface ChangePasswordRequest {
+  current_password?: string;
+  new_password: string;
+  persist_password?: boolean;
 }
 
 export interface UpdateMakerConfigRequest {
@@ -197,11 +525,148 @@ export interface UpdateMakerConfigRequest {
   time_relative_fee_pct?: number;
   nostr_relays?: string[];
 }
+export type FeePreset = "fast" | "normal" | "economy";
+
 export interface SendToAddressRequest {
   address: string;
   /** satoshis */
   amount: number;
+  /** sat/vB — provide either this or fee_target */
+  feerate?: number;
+  /** preset or confirmation target in blocks */
+  fee_target?: FeePreset | number;
+}
+
+export interface FeeEstimateInfo {
+  target_blocks: number;
+  /** sat/vB */
+  feerate?: number;
+}
+
+export interface FeeEstimatesInfo {
+  estimates: FeeEstimateInfo[];
+  fast?: number;
+  normal?: number;
+  economy?: number;
+  max_feerate: number;
+}
+
+export type TxCategory =
+  | "deposit"
+  | "withdrawal"
+  | "swap-in"
+  | "swap-out"
+  | "fidelity-creation"
+  | "fidelity-redemption"
+  | "sweep"
+  | "contract-recovery";
+
+export interface TransactionInfo {
+  txid: string;
+  category: TxCategory;
+  /** net wallet change in satoshis, fees included */
+  amount: number;
+  /** satoshis */
+  fee?: number;
+  confirmations: number;
+  block_height?: number;
+  /** unix seconds */
+  block_time?: number;
+  /** unix seconds */
+  time: number;
+  label?: string;
+}
+
+export interface TransactionPage {
+  total: number;
+  offset: number;
+  limit: number;
+  transactions: TransactionInfo[];
+}
+
+export type BumpMethod = "rbf" | "cpfp";
+
+export interface BumpFeeRequest {
+  method?: BumpMethod;
+  /** sat/vB — provide either this or fee_target */
+  feerate?: number;
+  fee_target?: FeePreset | number;
+}
+
+export interface BumpFeeResponse {
+  method: BumpMethod;
+  txid: string;
+  bumped_txid: string;
+  /** sat/vB */
   feerate: number;
+  /** satoshis */
+  fee: number;
+}
+
+export interface OutgoingTxInfo {
+  txid: string;
+  address: string;
+  /** satoshis */
+  amount: number;
+  /** satoshis */
+  fee: number;
+  /** sat/vB */
+  feerate: number;
+  vsize: number;
+  /** unix seconds */
+  created_at: number;
+  change?: string;
+  replaces?: string;
+  replaced_by?: string;
+  cpfp_parent?: string;
+  /** transaction label, falling back to the recipient address label */
+  label?: string;
+}
+
+export type LabelType = "tx" | "addr" | "pubkey" | "input" | "output" | "xpub";
+
+/** BIP-329 label record */
+export interface LabelDto {
+  type: LabelType;
+  /** txid, address, pubkey, txid:vout outpoint or xpub */
+  ref: string;
+  label?: string;
+  origin?: string;
+  spendable?: boolean;
+}
+
+export interface LabelImportResponse {
+  imported: number;
+  total: number;
+}
+
+export type AddressType = "p2wpkh" | "p2tr";
+
+export interface NewAddressInfo {
+  address: string;
+  address_type: AddressType;
+  /** true if a previously issued, still unused address was returned */
+  reused: boolean;
+  label?: string;
+}
+
+export interface AddressInfo {
+  address: string;
+  address_type: AddressType;
+  /** unix seconds */
+  issued_at: number;
+  used: boolean;
+  /** satoshis, unconfirmed included */
+  received: number;
+  tx_count: number;
+  label?: string;
+}
+
+export interface NewAddressOptions {
+  type?: AddressType;
+  /** derive a new address even if an issued one is unused */
+  fresh?: boolean;
+  label?: string;
 }
 
 // ─── Error class ──────────────────────────────────────────────────────────────
@@ -318,6 +783,10 @@ export const makers = {
   start: (id: string): Promise<string> => post(`/makers/${id}/start`),
   stop: (id: string): Promise<string> => post(`/makers/${id}/stop`),
   restart: (id: string): Promise<string> => post(`/makers/${id}/restart`),
+  unlock: (id: string, password: string): Promise<string> =>
+    post(`/makers/${id}/unlock`, { password }),
+  changePassword: (id: string, body: ChangePasswordRequest): Promise<string> =>
+    post(`/makers/${id}/password`, body),
 };
 
 // ─── Wallet ───────────────────────────────────────────────────────────────────
@@ -331,21 +800,163 @@ export const wallet = {
     get(`/makers/${id}/utxos/contract`),
   fidelityUtxos: (id: string): Promise<UtxoInfo[]> =>
     get(`/makers/${id}/utxos/fidelity`),
-  newAddress: (id: string): Promise<string> => get(`/makers/${id}/address`),
+  newAddress: (
+    id: string,
+    options: NewAddressOptions = {},
+  ): Promise<NewAddressInfo> => {
+    const params = new URLSearchParams();
+    if (options.type) params.set("type", options.type);
+    if (options.fresh) params.set("fresh", "true");
+    if (options.label) params.set("label", options.label);
+    const query = params.toString();
+    return get(`/makers/${id}/address${query ? `?${query}` : ""}`);
+  },
+  addresses: (id: string): Promise<AddressInfo[]> =>
+    get(`/makers/${id}/addresses`),
   send: (id: string, body: SendToAddressRequest): Promise<string> =>
     post(`/makers/${id}/send`, body),
   sync: (id: string): Promise<string> => post(`/makers/${id}/sync`),
+  rescan: (id: string, body: RescanRequest): Promise<RescanStatus> =>
+    post(`/makers/${id}/rescan`, body),
+  reimportDescriptors: (
+    id: string,
+    body: RescanRequest,
+  ): Promise<RescanStatus> => post(`/makers/${id}/descriptors/reimport`, body),
+  frozenUtxos: (id: string): Promise<string[]> =>
+    get(`/makers/${id}/utxos/frozen`),
+  freezeUtxo: (id: string, outpoint: string): Promise<FrozenUtxoInfo> =>
+    post(`/makers/${id}/utxos/${outpoint}/freeze`),
+  unfreezeUtxo: (id: string, outpoint: string): Promise<FrozenUtxoInfo> =>
+    post(`/makers/${id}/utxos/${outpoint}/unfreeze`),
+  fees: (makerId?: string): Promise<FeeEstimatesInfo> =>
+    get(`/fees${makerId !== undefined ? `?maker=${makerId}` : ""}`),
+  transactions: (
+    id: string,
+    offset = 0,
+    limit = 50,
+  ): Promise<TransactionPage> =>
+    get(`/makers/${id}/transactions?offset=${offset}&limit=${limit}`),
+  outgoing: (id: string): Promise<OutgoingTxInfo[]> =>
+    get(`/makers/${id}/transactions/outgoing`),
+  bumpFee: (
+    id: string,
+    txid: string,
+    body: BumpFeeRequest,
+  ): Promise<BumpFeeResponse> =>
+    post(`/makers/${id}/transactions/${txid}/bump`, body),
+  /** Downloads the encrypted wallet backup as a file */
+  backup: async (id: string, body: WalletBackupRequest): Promise<Blob> => {
+    const res = await fetch(`/api/makers/${id}/wallet/backup`, {
+      method: "POST",
+      headers: { "Content-Type": "application/json" },
+      body: JSON.stringify(body),
+    });
+    if (!res.ok) {
+      const raw = await res.text();
+      let message = res.statusText || "Unknown error";
+      try {
+        message = (JSON.parse(raw) as ApiResponse<string>).error ?? message;
+      } catch {
+        if (raw) message = raw;
+      }
+      throw new ApiError(res.status, message);
+    }
+    return res.blob();
+  },
+};
+
+// ─── Labels ───────────────────────────────────────────────────────────────────
+
+export const labels = {
+  list: (id: string, type?: LabelType): Promise<LabelDto[]> =>
+    get(`/makers/${id}/labels${type ? `?type=${type}` : ""}`),
+  set: (id: string, label: LabelDto): Promise<LabelDto> =>
+    put(`/makers/${id}/labels`, label),
+  remove: (id: string, type: LabelType, ref: string): Promise<string> =>
+    del(`/makers/${id}/labels/${type}/${encodeURIComponent(ref)}`),
+  /** Opens the BIP-329 export as a file download */
+  export: (id: string): void => {
+    window.open(`/api/makers/${id}/labels/export`, "_blank");
+  },
+  import: (id: string, jsonl: string): Promise<LabelImportResponse> =>
+    request(`/makers/${id}/labels/import`, {
+      method: "POST",
+      headers: { "Content-Type": "application/jsonl" },
+      body: jsonl,
+    }),
+};
+
+// ─── Exports ──────────────────────────────────────────────────────────────────
+
+export type ExportFormat = "csv" | "koinly" | "cointracking";
+
+export interface ExportQuery {
+  format?: ExportFormat;
+  /** a single maker; all makers when omitted */
+  maker?: string;
+  /** unix timestamps */
+  from?: number;
+  to?: number;
+}
+
+export const exportCsv = {
+  /** Opens the swap report CSV as a file download */
+  swapReports: (query: ExportQuery = {}): void => {
+    window.open(`/api/export/swap-reports${queryString(query)}`, "_blank");
+  },
+  /** Opens the wallet transaction CSV as a file download */
+  transactions: (query: ExportQuery = {}): void => {
+    window.open(`/api/export/transactions${queryString(query)}`, "_blank");
+  },
 };
 
 // ─── Fidelity ─────────────────────────────────────────────────────────────────
 
 export const fidelity = {
-  /** Returns a formatted display string of all fidelity bonds for a maker */
-  list: (id: string): Promise<string> => get(`/makers/${id}/fidelity`),
+  list: (id: string): Promise<FidelityBondDto[]> =>
+    get(`/makers/${id}/fidelity`),
+  create: (
+    id: string,
+    body: CreateFidelityRequest,
+  ): Promise<FidelityFundingInfo> => post(`/makers/${id}/fidelity`, body),
+  /** Redeems an expired bond; returns the txid */
+  redeem: (
+    id: string,
+    index: number,
+    body: RedeemFidelityRequest = {},
+  ): Promise<string> => post(`/makers/${id}/fidelity/${index}/redeem`, body),
+  /** What-if valuation of amount (sats) locked for timelock blocks */
+  estimateValue: (
+    amount: number,
+    timelock: number,
+    excludeMaker?: string,
+  ): Promise<BondValueEstimate> =>
+    get(
+      `/fidelity/value?amount=${amount}&timelock=${timelock}${
+        excludeMaker !== undefined ? `&maker=${excludeMaker}` : ""
+      }`,
+    ),
+  renewal: (id: string): Promise<RenewalPolicy> =>
+    get(`/makers/${id}/fidelity/renewal`),
+  setRenewal: (id: string, policy: RenewalPolicy): Promise<RenewalPolicy> =>
+    put(`/makers/${id}/fidelity/renewal`, policy),
+  renewalLog: (id: string): Promise<RenewalRecord[]> =>
+    get(`/makers/${id}/fidelity/renewal/log`),
+  evaluateRenewal: (id: string): Promise<RenewalRecord[]> =>
+    post(`/makers/${id}/fidelity/renewal/evaluate`),
 };
 
 // ─── Monitoring ───────────────────────────────────────────────────────────────
 
+function queryString(query: object): string {
+  const params = new URLSearchParams();
+  for (const [key, value] of Object.entries(query)) {
+    if (value !== undefined) params.set(key, String(value));
+  }
+  const qs = params.toString();
+  return qs ? `?${qs}` : "";
+}
+
 export const monitoring = {
   status: (id: string): Promise<MakerStatus> => get(`/makers/${id}/status`),
   torAddress: (id: string): Promise<string> => get(`/makers/${id}/tor-address`),
@@ -353,6 +964,32 @@ export const monitoring = {
   swaps: (id: string): Promise<SwapHistoryDto> => get(`/makers/${id}/swaps`),
   swapReports: (id: string): Promise<SwapReportDto[]> =>
     get(`/makers/${id}/swap-reports`),
+  swapReportPage: (
+    id: string,
+    query: SwapReportQuery = {},
+  ): Promise<SwapReportPage> =>
+    get(`/makers/${id}/swap-reports/page${queryString(query)}`),
+  swapReport: (id: string, swapId: string): Promise<SwapReportDto> =>
+    get(`/makers/${id}/swap-reports/by-id/${encodeURIComponent(swapId)}`),
+  analytics: (id: string, query: AnalyticsQuery = {}): Promise<SwapAnalytics> =>
+    get(`/makers/${id}/analytics${queryString(query)}`),
+  fleetAnalytics: (query: AnalyticsQuery = {}): Promise<FleetAnalytics> =>
+    get(`/analytics${queryString(query)}`),
+  balanceHistory: (
+    id: string,
+    query: { from?: number; to?: number; step?: number } = {},
+  ): Promise<BalanceHistoryPoint[]> =>
+    get(`/makers/${id}/history/balances${queryString(query)}`),
+  returns: (
+    id: string,
+    query: Omit<AnalyticsQuery, "bucket"> & { opportunity_rate?: number } = {},
+  ): Promise<ReturnOnCapital> =>
+    get(`/makers/${id}/returns${queryString(query)}`),
+  recovery: (
+    id: string,
+    query: { from?: number; to?: number } = {},
+  ): Promise<RecoveryInsights> =>
+    get(`/makers/${id}/recovery${queryString(query)}`),
   /** Fetches the last N log lines for a maker (default: 100) */
   logs: (id: string, lines?: number): Promise<string[]> =>
     get(`/makers/${id}/logs${lines !== undefined ? `?lines=${lines}` : ""}`),
@@ -363,6 +1000,177 @@ export const monitoring = {
     get(`/logs/combined${lines !== undefined ? `?lines=${lines}` : ""}`),
 };
 
+// ─── Events ───────────────────────────────────────────────────────────────────
+
+export type AppEventType =
+  | "maker_created"
+  | "maker_deleted"
+  | "maker_started"
+  | "maker_stopped"
+  | "maker_crashed"
+  | "config_updated"
+  | "balance_changed"
+  | "new_utxo"
+  | "swap_coin_received"
+  | "swap_completed"
+  | "swap_failed"
+  | "low_balance"
+  | "fidelity_expiring"
+  | "new_block"
+  | "bitcoind_up"
+  | "bitcoind_down"
+  | "alert_firing"
+  | "alert_resolved";
+
+/** A dashboard event; fields beyond these depend on `type` */
+export interface AppEvent {
+  id: number;
+  /** unix timestamp */
+  at: number;
+  /** null for node events */
+  maker: string | null;
+  type: AppEventType;
+  [field: string]: unknown;
+}
+
+export const events = {
+  /**
+   * Opens the event stream, optionally for one maker (node events are always
+   * included). Returns a cleanup function that closes the stream.
+   */
+  subscribe: (
+    onEvent: (event: AppEvent) => void,
+    query: { maker?: string; since?: number } = {},
+    onError?: (err: Event) => void,
+  ): (() => void) => {
+    const es = new EventSource(`/api/events${queryString(query)}`);
+    const handler = (e: MessageEvent) => onEvent(JSON.parse(e.data) as AppEvent);
+    const types: AppEventType[] = [
+      "maker_created",
+      "maker_deleted",
+      "maker_started",
+      "maker_stopped",
+      "maker_crashed",
+      "config_updated",
+      "balance_changed",
+      "new_utxo",
+      "swap_coin_received",
+      "swap_completed",
+      "swap_failed",
+      "low_balance",
+      "fidelity_expiring",
+      "new_block",
+      "bitcoind_up",
+      "bitcoind_down",
+      "alert_firing",
+      "alert_resolved",
+    ];
+    for (const type of types) es.addEventListener(type, handler);
+    if (onError) es.onerror = onError;
+    return () => es.close();
+  },
+};
+
+// ─── Webhooks ─────────────────────────────────────────────────────────────────
+
+export interface WebhookRequest {
+  url: string;
+  /** omit on update to keep the current secret; "" removes it */
+  secret?: string;
+  /** event types to deliver; empty for the default set */
+  events?: AppEventType[];
+  enabled?: boolean;
+}
+
+export interface WebhookInfo {
+  id: number;
+  url: string;
+  has_secret: boolean;
+  events: AppEventType[];
+  enabled: boolean;
+}
+
+export interface WebhookDeliveryInfo {
+  id: number;
+  webhook_id: number;
+  /** null for test deliveries */
+  event_id: number | null;
+  event_type: string;
+  attempt: number;
+  /** unix timestamp */
+  at: number;
+  status: number | null;
+  success: boolean;
+  error: string | null;
+}
+
+export const webhooks = {
+  list: (): Promise<WebhookInfo[]> => get("/webhooks"),
+  create: (body: WebhookRequest): Promise<WebhookInfo> =>
+    post("/webhooks", body),
+  update: (id: number, body: WebhookRequest): Promise<WebhookInfo> =>
+    put(`/webhooks/${id}`, body),
+  delete: (id: number): Promise<string> => del(`/webhooks/${id}`),
+  deliveries: (id: number): Promise<WebhookDeliveryInfo[]> =>
+    get(`/webhooks/${id}/deliveries`),
+  /** Sends a ping event once and returns the attempt */
+  test: (id: number): Promise<WebhookDeliveryInfo> =>
+    post(`/webhooks/${id}/test`),
+};
+
+// ─── Alerts ───────────────────────────────────────────────────────────────────
+
+export type AlertCondition =
+  | { kind: "low_balance"; sats: number }
+  | { kind: "no_successful_swap"; hours: number }
+  | { kind: "maker_down"; minutes: number }
+  /** progress from 0 to 1, e.g. 0.999 */
+  | { kind: "node_sync_below"; progress: number }
+  | { kind: "disk_free_below"; bytes: number }
+  | { kind: "fidelity_expiring"; blocks: number };
+
+export interface AlertRuleRequest {
+  name: string;
+  condition: AlertCondition;
+  /** only this maker; all makers when omitted */
+  maker?: string;
+  enabled?: boolean;
+}
+
+export interface AlertRuleInfo {
+  id: number;
+  name: string;
+  condition: AlertCondition;
+  maker: string | null;
+  enabled: boolean;
+}
+
+export type AlertState = "firing" | "resolved";
+
+export interface AlertInfo {
+  id: number;
+  rule_id: number;
+  rule_name: string;
+  maker: string;
+  state: AlertState;
+  message: string;
+  /** unix timestamps */
+  fired_at: number;
+  resolved_at: number | null;
+  updated_at: number;
+}
+
+export const alerts = {
+  list: (query: { state?: AlertState; maker?: string } = {}): Promise<AlertInfo[]> =>
+    get(`/alerts${queryString(query)}`),
+  rules: (): Promise<AlertRuleInfo[]> => get("/alerts/rules"),
+  createRule: (body: AlertRuleRequest): Promise<AlertRuleInfo> =>
+    post("/alerts/rules", body),
+  updateRule: (id: number, body: AlertRuleRequest): Promise<AlertRuleInfo> =>
+    put(`/alerts/rules/${id}`, body),
+  deleteRule: (id: number): Promise<string> => del(`/alerts/rules/${id}`),
+};
+
 // ─── Bitcoind ─────────────────────────────────────────────────────────────────
 
 export interface BitcoindStatusInfo {
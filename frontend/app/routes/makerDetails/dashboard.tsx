@@ -18,7 +18,8 @@ export default function Dashboard({ core }: Props) {
         balances.regular +
           balances.swap +
           balances.contract +
-          balances.fidelity,
+          balances.fidelity +
+          (balances.frozen ?? 0),
       )
     : null;
 
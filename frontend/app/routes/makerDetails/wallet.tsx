@@ -11,6 +11,8 @@ function utxoPurpose(type: string): string {
   switch (type) {
     case "regular":
       return "Single-sig wallet funds";
+    case "swept":
+      return "Swept swap proceeds";
     case "swap":
       return "Swap liquidity";
     case "contract":
@@ -90,8 +92,8 @@ export default function Wallet({ id, onBalanceRefresh, refreshToken }: Props) {
   async function handleGenerateAddress() {
     setAddrLoading(true);
     try {
-      const addr = await wallet.newAddress(id);
-      setNewAddress(addr);
+      const { address } = await wallet.newAddress(id);
+      setNewAddress(address);
     } catch (e) {
       alert(e instanceof Error ? e.message : "Failed to generate address");
     } finally {
@@ -255,7 +257,7 @@ export default function Wallet({ id, onBalanceRefresh, refreshToken }: Props) {
                     className="transition-colors duration-150 hover:bg-gray-800/50"
                   >
                     <td className="py-2 pr-4 font-mono text-xs truncate max-w-[180px]">
-                      {u.addr}
+                      {u.addr ?? u.outpoint}
                     </td>
                     <td className="py-2 pr-4 text-orange-400">
                       {satsToBtc(u.amount)} BTC
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::get,
+    Json, Router,
+};
+use coinswap::bitcoind::bitcoincore_rpc::RpcApi;
+
+use super::{
+    dto::{AddressInfo, ApiResponse},
+    AppState,
+};
+use crate::maker_manager::{labels::LabelType, MakerConfig};
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/makers/{id}/addresses", get(list_addresses))
+}
+
+/// What a wallet address has received so far.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct AddressUsage {
+    /// Total received in sats, unconfirmed included
+    pub received: u64,
+    pub tx_count: usize,
+}
+
+/// List receive addresses issued through the dashboard with their usage, newest first
+#[utoipa::path(
+    get,
+    path = "/api/makers/{id}/addresses",
+    tag = "wallet",
+    params(("id" = String, Path, description = "Maker ID")),
+    responses(
+        (status = 200, description = "Issued receive addresses", body = ApiResponse<Vec<AddressInfo>>),
+        (status = 404, description = "Maker not found", body = ApiResponse<Vec<AddressInfo>>),
+        (status = 503, description = "Bitcoin Core RPC unreachable", body = ApiResponse<Vec<AddressInfo>>)
+    )
+)]
+async fn list_addresses(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<Vec<AddressInfo>>>) {
+    let (config, issued, labels) = {
+        let mgr = state.lock().await;
+        let (Some(config), Some(labels)) = (mgr.get_config(&id), mgr.labels(&id)) else {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+            );
+        };
+        (config, mgr.issued_addresses(&id), labels)
+    };
+
+    let usage = match address_usage(config, id).await {
+        Ok(usage) => usage,
+        Err(e) => return (StatusCode::SERVICE_UNAVAILABLE, Json(ApiResponse::err(e))),
+    };
+    let addresses = issued
+        .into_iter()
+        .map(|issued| {
+            let used = usage.get(&issued.address).copied().unwrap_or_default();
+            AddressInfo {
+                label: labels.label_of(LabelType::Addr, &issued.address),
+                address: issued.address,
+                address_type: issued.address_type.into(),
+                issued_at: issued.issued_at,
+                used: used.tx_count > 0,
+                received: used.received,
+                tx_count: used.tx_count,
+            }
+        })
+        .collect();
+    (StatusCode::OK, Json(ApiResponse::ok(addresses)))
+}
+
+/// Queries `listreceivedbyaddress` on the maker's Core wallet, keyed by address string.
+pub(crate) async fn address_usage(
+    config: MakerConfig,
+    id: String,
+) -> Result<HashMap<String, AddressUsage>, String> {
+    tokio::task::spawn_blocking(move || {
+        let client = config.wallet_rpc_client(&id).map_err(|e| e.to_string())?;
+        let received = client
+            .list_received_by_address(None, Some(0), Some(false), Some(true))
+            .map_err(|e| format!("listreceivedbyaddress failed: {e}"))?;
+        Ok(received
+            .into_iter()
+            .map(|entry| {
+                (
+                    entry.address.assume_checked_ref().to_string(),
+                    AddressUsage {
+                        received: entry.amount.to_sat(),
+                        tx_count: entry.txids.len(),
+                    },
+                )
+            })
+            .collect())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,406 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{get, put},
+    Json, Router,
+};
+use serde::Deserialize;
+use tokio::task::JoinHandle;
+
+use super::{
+    analytics::is_successful,
+    dto::{AlertInfo, AlertRuleInfo, AlertRuleRequest, AlertStateDto, ApiResponse},
+    swap_index, AppState,
+};
+use crate::maker_manager::{
+    alerts::{Alert, AlertCondition, AlertRule, Notifier, Observation},
+    history::{HistoryPoint, NodeSample},
+    unix_now, MakerConfig,
+};
+
+/// How often the alert rules are evaluated.
+const ALERT_INTERVAL: Duration = Duration::from_secs(60);
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/alerts", get(list_alerts))
+        .route("/alerts/rules", get(list_rules).post(create_rule))
+        .route("/alerts/rules/{id}", put(update_rule).delete(delete_rule))
+}
+
+#[derive(Deserialize)]
+struct AlertsQuery {
+    state: Option<AlertStateDto>,
+    maker: Option<String>,
+}
+
+/// List alerts
+///
+/// Firing alerts, newest first, followed by the latest 200 resolved ones. Each rule raises
+/// at most one firing alert per maker. Alerts are kept in memory, so conditions that still
+/// hold are raised again after a dashboard restart.
+#[utoipa::path(
+    get,
+    path = "/api/alerts",
+    tag = "alerts",
+    params(
+        ("state" = Option<AlertStateDto>, Query, description = "Only firing or only resolved alerts"),
+        ("maker" = Option<String>, Query, description = "Only this maker's alerts")
+    ),
+    responses(
+        (status = 200, description = "Alerts", body = ApiResponse<Vec<AlertInfo>>)
+    )
+)]
+async fn list_alerts(
+    State(state): State<AppState>,
+    Query(query): Query<AlertsQuery>,
+) -> (StatusCode, Json<ApiResponse<Vec<AlertInfo>>>) {
+    let alerts = state
+        .lock()
+        .await
+        .alerts()
+        .into_iter()
+        .map(AlertInfo::from)
+        .filter(|a| query.state.is_none_or(|s| a.state == s))
+        .filter(|a| query.maker.as_ref().is_none_or(|m| &a.maker == m))
+        .collect();
+    (StatusCode::OK, Json(ApiResponse::ok(alerts)))
+}
+
+/// List alert rules
+#[utoipa::path(
+    get,
+    path = "/api/alerts/rules",
+    tag = "alerts",
+    responses(
+        (status = 200, description = "Alert rules", body = ApiResponse<Vec<AlertRuleInfo>>)
+    )
+)]
+async fn list_rules(
+    State(state): State<AppState>,
+) -> (StatusCode, Json<ApiResponse<Vec<AlertRuleInfo>>>) {
+    let rules = state.lock().await.alert_rules();
+    (
+        StatusCode::OK,
+        Json(ApiResponse::ok(rules.into_iter().map(Into::into).collect())),
+    )
+}
+
+/// Checks a rule request, returning the rule it describes.
+fn validate(id: u64, body: AlertRuleRequest) -> Result<AlertRule, String> {
+    if body.name.trim().is_empty() {
+        return Err("Rule name must not be empty".to_string());
+    }
+    let condition = AlertCondition::from(body.condition);
+    match condition {
+        AlertCondition::NoSuccessfulSwap { hours: 0 } => {
+            return Err("hours must be at least 1".to_string())
+        }
+        AlertCondition::MakerDown { minutes: 0 } => {
+            return Err("minutes must be at least 1".to_string())
+        }
+        AlertCondition::NodeSyncBelow { progress } if !(progress > 0.0 && progress <= 1.0) => {
+            return Err("progress must be above 0 and at most 1".to_string())
+        }
+        _ => {}
+    }
+    Ok(AlertRule {
+        id,
+        name: body.name.trim().to_string(),
+        condition,
+        maker: body.maker,
+        enabled: body.enabled.unwrap_or(true),
+    })
+}
+
+/// Add an alert rule
+#[utoipa::path(
+    post,
+    path = "/api/alerts/rules",
+    tag = "alerts",
+    request_body = AlertRuleRequest,
+    responses(
+        (status = 201, description = "Rule added", body = ApiResponse<AlertRuleInfo>),
+        (status = 400, description = "Invalid rule", body = ApiResponse<AlertRuleInfo>),
+        (status = 404, description = "Maker not found", body = ApiResponse<AlertRuleInfo>),
+        (status = 500, description = "Failed to save the rule", body = ApiResponse<AlertRuleInfo>)
+    )
+)]
+async fn create_rule(
+    State(state): State<AppState>,
+    Json(body): Json<AlertRuleRequest>,
+) -> (StatusCode, Json<ApiResponse<AlertRuleInfo>>) {
+    let rule = match validate(0, body) {
+        Ok(rule) => rule,
+        Err(e) => return (StatusCode::BAD_REQUEST, Json(ApiResponse::err(e))),
+    };
+    let mut mgr = state.lock().await;
+    if let Some(id) = rule.maker.as_ref().filter(|id| !mgr.has_maker(id)) {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+        );
+    }
+    match mgr.add_alert_rule(rule) {
+        Ok(rule) => (StatusCode::CREATED, Json(ApiResponse::ok(rule.into()))),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::err(e.to_string())),
+        ),
+    }
+}
+
+/// Replace an alert rule
+///
+/// Firing alerts of the rule are resolved at the next evaluation if the condition no longer
+/// holds, or if the rule was disabled or no longer applies to their maker.
+#[utoipa::path(
+    put,
+    path = "/api/alerts/rules/{id}",
+    tag = "alerts",
+    params(("id" = u64, Path, description = "Rule ID")),
+    request_body = AlertRuleRequest,
+    responses(
+        (status = 200, description = "Rule updated", body = ApiResponse<AlertRuleInfo>),
+        (status = 400, description = "Invalid rule", body = ApiResponse<AlertRuleInfo>),
+        (status = 404, description = "Rule or maker not found", body = ApiResponse<AlertRuleInfo>),
+        (status = 500, description = "Failed to save the rule", body = ApiResponse<AlertRuleInfo>)
+    )
+)]
+async fn update_rule(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+    Json(body): Json<AlertRuleRequest>,
+) -> (StatusCode, Json<ApiResponse<AlertRuleInfo>>) {
+    let rule = match validate(id, body) {
+        Ok(rule) => rule,
+        Err(e) => return (StatusCode::BAD_REQUEST, Json(ApiResponse::err(e))),
+    };
+    let mut mgr = state.lock().await;
+    if let Some(maker) = rule.maker.as_ref().filter(|m| !mgr.has_maker(m)) {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::err(format!("Maker '{maker}' not found"))),
+        );
+    }
+    match mgr.update_alert_rule(rule.clone()) {
+        Ok(true) => (StatusCode::OK, Json(ApiResponse::ok(rule.into()))),
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::err(format!("Alert rule {id} not found"))),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::err(e.to_string())),
+        ),
+    }
+}
+
+/// Delete an alert rule
+///
+/// Its firing alerts are resolved at the next evaluation.
+#[utoipa::path(
+    delete,
+    path = "/api/alerts/rules/{id}",
+    tag = "alerts",
+    params(("id" = u64, Path, description = "Rule ID")),
+    responses(
+        (status = 200, description = "Rule removed", body = ApiResponse<String>),
+        (status = 404, description = "Rule not found", body = ApiResponse<String>),
+        (status = 500, description = "Failed to save the rules", body = ApiResponse<String>)
+    )
+)]
+async fn delete_rule(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    match state.lock().await.remove_alert_rule(id) {
+        Ok(true) => (
+            StatusCode::OK,
+            Json(ApiResponse::ok(format!("Alert rule {id} removed"))),
+        ),
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::err(format!("Alert rule {id} not found"))),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::err(e.to_string())),
+        ),
+    }
+}
+
+/// What the evaluator remembers about a maker between evaluations.
+struct Watched {
+    /// When the evaluator first saw the maker
+    first_seen: u64,
+    /// Start of the current outage
+    down_since: Option<u64>,
+}
+
+/// A maker as the history sampler last saw it.
+struct Sampled {
+    id: String,
+    config: MakerConfig,
+    should_run: bool,
+    latest: Option<HistoryPoint>,
+    node: Option<NodeSample>,
+    bond_locktimes: Option<Vec<u32>>,
+}
+
+fn notify(notifiers: &[Box<dyn Notifier>], alerts: &[Alert]) {
+    for alert in alerts {
+        for notifier in notifiers {
+            notifier.notify(alert);
+        }
+    }
+}
+
+/// Spawns the task that evaluates the alert rules every minute and passes raised and
+/// resolved alerts to `notifiers`. Makers and nodes are judged by the snapshot the history
+/// sampler keeps rather than polled again.
+pub fn spawn_evaluator(state: AppState, notifiers: Vec<Box<dyn Notifier>>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut watched: HashMap<String, Watched> = HashMap::new();
+        let mut interval = tokio::time::interval(ALERT_INTERVAL);
+        loop {
+            interval.tick().await;
+            let now = unix_now();
+            let stale = state.lock().await.resolve_stale_alerts(now);
+            notify(&notifiers, &stale);
+
+            let (rules, makers) = {
+                let mgr = state.lock().await;
+                let snapshot = mgr.snapshot();
+                let makers: Vec<Sampled> = mgr
+                    .list_makers()
+                    .into_iter()
+                    .filter_map(|id| {
+                        let config = mgr.get_config(id)?;
+                        Some(Sampled {
+                            id: id.clone(),
+                            should_run: mgr.should_be_running(id),
+                            latest: snapshot.makers.get(id).copied(),
+                            node: snapshot.nodes.get(&config.rpc).copied(),
+                            bond_locktimes: snapshot.bond_locktimes.get(id).cloned(),
+                            config,
+                        })
+                    })
+                    .collect();
+                (mgr.alert_rules(), makers)
+            };
+            watched.retain(|id, _| makers.iter().any(|m| &m.id == id));
+            for maker in makers {
+                let id = maker.id.clone();
+                let rules: Vec<&AlertRule> = rules.iter().filter(|r| r.applies_to(&id)).collect();
+                if rules.is_empty() {
+                    continue;
+                }
+                let track = watched.entry(id.clone()).or_insert(Watched {
+                    first_seen: now,
+                    down_since: None,
+                });
+                let obs = observe(maker, &rules, track).await;
+                let mut changed = Vec::new();
+                for rule in rules {
+                    let check = rule.condition.check(&obs, now);
+                    changed.extend(state.lock().await.apply_alert(rule, &id, check, now));
+                }
+                notify(&notifiers, &changed);
+            }
+        }
+    })
+}
+
+/// Builds what `rules` need to know about a maker from its latest sample, its swap reports
+/// and its data directory's disk.
+async fn observe(maker: Sampled, rules: &[&AlertRule], track: &mut Watched) -> Observation {
+    let needs = |f: fn(&AlertCondition) -> bool| rules.iter().any(|r| f(&r.condition));
+    let alive = maker.latest.is_some_and(|p| p.alive > 0.0);
+    track.down_since = match maker.latest {
+        _ if !maker.should_run => None,
+        Some(point) if point.alive > 0.0 => None,
+        Some(point) => track.down_since.or(Some(point.at)),
+        None => track.down_since,
+    };
+    let mut obs = Observation {
+        should_run: maker.should_run,
+        alive,
+        down_since: track.down_since,
+        spendable: maker
+            .latest
+            .and_then(|p| p.wallet)
+            .map(|wallet| wallet.spendable),
+        sync_progress: maker.node.and_then(|n| n.progress),
+        tip: maker
+            .node
+            .and_then(|n| n.height)
+            .or(maker.latest.and_then(|p| p.height))
+            .map(|h| h as u32),
+        bond_locktimes: maker.bond_locktimes,
+        ..Observation::default()
+    };
+
+    if let Some(data_dir) = maker.config.data_directory {
+        if needs(|c| matches!(c, AlertCondition::NoSuccessfulSwap { .. })) {
+            let (reports_dir, reports_id) = (data_dir.join("swap_reports"), maker.id.clone());
+            let reports =
+                tokio::task::spawn_blocking(move || swap_index::refresh(&reports_dir, &reports_id))
+                    .await;
+            if let Ok(Ok(reports)) = reports {
+                let latest = reports
+                    .iter()
+                    .filter(|r| is_successful(&r.status))
+                    .map(|r| r.end_timestamp)
+                    .max();
+                obs.last_success = Some(latest.unwrap_or(track.first_seen));
+            }
+        }
+        if needs(|c| matches!(c, AlertCondition::DiskFreeBelow { .. })) {
+            let free = tokio::task::spawn_blocking(move || fs4::available_space(&data_dir)).await;
+            obs.disk_free = free.ok().and_then(Result::ok);
+        }
+    }
+    obs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate;
+    use crate::api::dto::{AlertConditionDto, AlertRuleRequest};
+
+    fn request(name: &str, condition: AlertConditionDto) -> AlertRuleRequest {
+        AlertRuleRequest {
+            name: name.into(),
+            condition,
+            maker: None,
+            enabled: None,
+        }
+    }
+
+    #[test]
+    fn rules_are_validated() {
+        let rule = validate(
+            3,
+            request(" Low ", AlertConditionDto::LowBalance { sats: 1 }),
+        )
+        .unwrap();
+        assert_eq!(
+            (rule.id, rule.name.as_str(), rule.enabled),
+            (3, "Low", true)
+        );
+        assert!(validate(0, request("", AlertConditionDto::LowBalance { sats: 1 })).is_err());
+        assert!(validate(
+            0,
+            request("Idle", AlertConditionDto::NoSuccessfulSwap { hours: 0 })
+        )
+        .is_err());
+        let sync = |progress| request("Sync", AlertConditionDto::NodeSyncBelow { progress });
+        assert!(validate(0, sync(0.999)).is_ok());
+        assert!(validate(0, sync(99.9)).is_err());
+        assert!(validate(0, sync(f64::NAN)).is_err());
+    }
+}
@@ -0,0 +1,440 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::get,
+    Json, Router,
+};
+use serde::Deserialize;
+
+use super::{
+    dto::{
+        AnalyticsBucket, AnalyticsWindow, ApiResponse, FleetAnalytics, MakerSwapStats,
+        SwapAnalytics, SwapReportDto, SwapStats,
+    },
+    monitoring::load_swap_reports,
+    AppState,
+};
+use crate::maker_manager::unix_now;
+
+const HOUR: u64 = 3_600;
+pub(crate) const DAY: u64 = 24 * HOUR;
+/// Upper bound on the chart intervals of one response.
+const MAX_BUCKETS: u64 = 1_000;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/analytics", get(get_fleet_analytics))
+        .route("/makers/{id}/analytics", get(get_analytics))
+}
+
+#[derive(Deserialize)]
+struct AnalyticsQuery {
+    window: Option<AnalyticsWindow>,
+    from: Option<u64>,
+    to: Option<u64>,
+    /// Chart interval in seconds
+    bucket: Option<u64>,
+}
+
+/// Resolves a reporting window ending at `to` (default `now`) to its `(from, to)` bounds.
+pub(crate) fn window_bounds(
+    window: AnalyticsWindow,
+    from: Option<u64>,
+    to: Option<u64>,
+    now: u64,
+) -> Result<(u64, u64), String> {
+    let to = to.unwrap_or(now);
+    let from = match window {
+        AnalyticsWindow::Day => to.saturating_sub(DAY),
+        AnalyticsWindow::Week => to.saturating_sub(7 * DAY),
+        AnalyticsWindow::Month => to.saturating_sub(30 * DAY),
+        AnalyticsWindow::Custom => from.ok_or("from is required for the custom window")?,
+    };
+    if from >= to {
+        return Err("from must be before to".to_string());
+    }
+    Ok((from, to))
+}
+
+/// Reporting period resolved from an [`AnalyticsQuery`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Period {
+    pub(crate) from: u64,
+    pub(crate) to: u64,
+    pub(crate) bucket_secs: u64,
+}
+
+impl Period {
+    fn resolve(query: &AnalyticsQuery, now: u64) -> Result<Self, String> {
+        let window = query.window.unwrap_or(AnalyticsWindow::Week);
+        let (from, to) = window_bounds(window, query.from, query.to, now)?;
+        let default_bucket = match window {
+            AnalyticsWindow::Day => HOUR,
+            _ => DAY,
+        };
+        let bucket_secs = query.bucket.unwrap_or(default_bucket);
+        if bucket_secs == 0 {
+            return Err("bucket must be greater than 0".to_string());
+        }
+        if (to - from).div_ceil(bucket_secs) > MAX_BUCKETS {
+            return Err(format!(
+                "bucket too small: the period would span more than {MAX_BUCKETS} buckets"
+            ));
+        }
+        Ok(Self {
+            from,
+            to,
+            bucket_secs,
+        })
+    }
+}
+
+/// Running sums behind a [`SwapStats`].
+#[derive(Default)]
+struct Tally {
+    swaps: u64,
+    successful: u64,
+    earned: i64,
+    mining_fees: u64,
+    volume: u64,
+    duration_secs: f64,
+}
+
+impl Tally {
+    fn add(&mut self, report: &SwapReportDto) {
+        self.swaps += 1;
+        if is_successful(&report.status) {
+            self.successful += 1;
+        }
+        self.earned += report.fee_paid_or_earned;
+        self.mining_fees += report.mining_fee;
+        self.volume += report.incoming_amount;
+        self.duration_secs += report.swap_duration_seconds;
+    }
+
+    fn stats(&self) -> SwapStats {
+        let rate = |n: u64| (self.swaps > 0).then(|| n as f64 / self.swaps as f64);
+        let failed = self.swaps - self.successful;
+        SwapStats {
+            swaps: self.swaps,
+            successful: self.successful,
+            failed,
+            success_rate: rate(self.successful),
+            failure_rate: rate(failed),
+            earned: self.earned,
+            mining_fees: self.mining_fees,
+            net_profit: self.earned - self.mining_fees as i64,
+            volume: self.volume,
+            avg_duration_secs: (self.swaps > 0).then(|| self.duration_secs / self.swaps as f64),
+        }
+    }
+}
+
+/// Status coinswap writes into the report of a completed swap.
+const SUCCESS_STATUS: &str = "Success";
+
+/// Whether a swap report status denotes a completed swap; anything else (failed, recovered
+/// through the contract path, ...) counts as a failure. Reports carry coinswap's status as
+/// a plain string, so it is compared exactly rather than parsed.
+pub(crate) fn is_successful(status: &str) -> bool {
+    status == SUCCESS_STATUS
+}
+
+/// Time a swap is attributed to: when it ended, or started if it never finished.
+pub(crate) fn report_time(report: &SwapReportDto) -> u64 {
+    match report.end_timestamp {
+        0 => report.start_timestamp,
+        end => end,
+    }
+}
+
+/// Sums the reports falling inside `period`, overall and per bucket.
+pub(crate) fn aggregate<'a>(
+    reports: impl IntoIterator<Item = &'a SwapReportDto>,
+    period: Period,
+) -> (SwapStats, Vec<AnalyticsBucket>) {
+    let count = (period.to - period.from).div_ceil(period.bucket_secs);
+    let mut totals = Tally::default();
+    let mut buckets: Vec<Tally> = (0..count).map(|_| Tally::default()).collect();
+    for report in reports {
+        let at = report_time(report);
+        if at < period.from || at >= period.to {
+            continue;
+        }
+        totals.add(report);
+        buckets[((at - period.from) / period.bucket_secs) as usize].add(report);
+    }
+    let buckets = buckets
+        .iter()
+        .enumerate()
+        .map(|(i, tally)| {
+            let start = period.from + i as u64 * period.bucket_secs;
+            AnalyticsBucket {
+                start,
+                end: (start + period.bucket_secs).min(period.to),
+                stats: tally.stats(),
+            }
+        })
+        .collect();
+    (totals.stats(), buckets)
+}
+
+/// Loads a maker's swap reports, or `None` if the maker is unknown.
+pub(crate) async fn maker_swap_reports(
+    state: &AppState,
+    id: &str,
+) -> Option<Result<Vec<SwapReportDto>, (StatusCode, String)>> {
+    let config = state.lock().await.get_config(&id.to_string())?;
+    let Some(data_dir) = config.data_directory else {
+        return Some(Ok(Vec::new()));
+    };
+    let id = id.to_string();
+    let reports_dir = data_dir.join("swap_reports");
+    Some(
+        tokio::task::spawn_blocking(move || load_swap_reports(reports_dir, id))
+            .await
+            .unwrap_or_else(|e| Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))),
+    )
+}
+
+/// Earnings and swap analytics of a maker
+///
+/// Aggregates the maker's swap reports over a `day`, `week` (default), `month` or `custom`
+/// window ending at `to` (default now), in `bucket`-second intervals for charting. Swaps are
+/// attributed to the time they ended.
+#[utoipa::path(
+    get,
+    path = "/api/makers/{id}/analytics",
+    tag = "monitoring",
+    params(
+        ("id" = String, Path, description = "Maker ID"),
+        ("window" = Option<AnalyticsWindow>, Query, description = "Reporting period (default week)"),
+        ("from" = Option<u64>, Query, description = "Period start as a unix timestamp, required for the custom window"),
+        ("to" = Option<u64>, Query, description = "Period end as a unix timestamp (default now)"),
+        ("bucket" = Option<u64>, Query, description = "Chart interval in seconds (default an hour for day, a day otherwise)")
+    ),
+    responses(
+        (status = 200, description = "Swap analytics", body = ApiResponse<SwapAnalytics>),
+        (status = 400, description = "Invalid period", body = ApiResponse<SwapAnalytics>),
+        (status = 404, description = "Maker not found", body = ApiResponse<SwapAnalytics>),
+        (status = 500, description = "Failed to read swap reports", body = ApiResponse<SwapAnalytics>)
+    )
+)]
+async fn get_analytics(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<AnalyticsQuery>,
+) -> (StatusCode, Json<ApiResponse<SwapAnalytics>>) {
+    let period = match Period::resolve(&query, unix_now()) {
+        Ok(period) => period,
+        Err(e) => return (StatusCode::BAD_REQUEST, Json(ApiResponse::err(e))),
+    };
+    let reports = match maker_swap_reports(&state, &id).await {
+        Some(Ok(reports)) => reports,
+        Some(Err((status, msg))) => return (status, Json(ApiResponse::err(msg))),
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+            )
+        }
+    };
+    let (totals, buckets) = aggregate(&reports, period);
+    (
+        StatusCode::OK,
+        Json(ApiResponse::ok(SwapAnalytics {
+            from: period.from,
+            to: period.to,
+            bucket_secs: period.bucket_secs,
+            totals,
+            buckets,
+        })),
+    )
+}
+
+/// Earnings and swap analytics across all makers
+///
+/// Same periods as the per-maker analytics, with each maker's totals alongside the
+/// fleet-wide figures.
+#[utoipa::path(
+    get,
+    path = "/api/analytics",
+    tag = "monitoring",
+    params(
+        ("window" = Option<AnalyticsWindow>, Query, description = "Reporting period (default week)"),
+        ("from" = Option<u64>, Query, description = "Period start as a unix timestamp, required for the custom window"),
+        ("to" = Option<u64>, Query, description = "Period end as a unix timestamp (default now)"),
+        ("bucket" = Option<u64>, Query, description = "Chart interval in seconds (default an hour for day, a day otherwise)")
+    ),
+    responses(
+        (status = 200, description = "Fleet analytics", body = ApiResponse<FleetAnalytics>),
+        (status = 400, description = "Invalid period", body = ApiResponse<FleetAnalytics>),
+        (status = 500, description = "Failed to read swap reports", body = ApiResponse<FleetAnalytics>)
+    )
+)]
+async fn get_fleet_analytics(
+    State(state): State<AppState>,
+    Query(query): Query<AnalyticsQuery>,
+) -> (StatusCode, Json<ApiResponse<FleetAnalytics>>) {
+    let period = match Period::resolve(&query, unix_now()) {
+        Ok(period) => period,
+        Err(e) => return (StatusCode::BAD_REQUEST, Json(ApiResponse::err(e))),
+    };
+    let ids: Vec<String> = state
+        .lock()
+        .await
+        .list_makers()
+        .into_iter()
+        .cloned()
+        .collect();
+
+    let mut fleet = Vec::new();
+    let mut makers = Vec::with_capacity(ids.len());
+    for id in ids {
+        let reports = match maker_swap_reports(&state, &id).await {
+            Some(Ok(reports)) => reports,
+            Some(Err((status, msg))) => {
+                return (
+                    status,
+                    Json(ApiResponse::err(format!("Maker '{id}': {msg}"))),
+                )
+            }
+            // Removed while we were collecting
+            None => continue,
+        };
+        let (totals, _) = aggregate(&reports, period);
+        makers.push(MakerSwapStats {
+            maker_id: id,
+            totals,
+        });
+        fleet.extend(reports);
+    }
+    let (totals, buckets) = aggregate(&fleet, period);
+    (
+        StatusCode::OK,
+        Json(ApiResponse::ok(FleetAnalytics {
+            from: period.from,
+            to: period.to,
+            bucket_secs: period.bucket_secs,
+            totals,
+            buckets,
+            makers,
+        })),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{aggregate, is_successful, AnalyticsQuery, Period, DAY, HOUR};
+    use crate::api::dto::{AnalyticsWindow, SwapReportDto};
+
+    fn report(end: u64, status: &str, earned: i64, mining_fee: u64) -> SwapReportDto {
+        serde_json::from_value(serde_json::json!({
+            "swap_id": format!("swap-{end}"),
+            "role": "Maker",
+            "status": status,
+            "swap_duration_seconds": 60.0,
+            "start_timestamp": end.saturating_sub(60),
+            "end_timestamp": end,
+            "network": "regtest",
+            "error_message": null,
+            "incoming_amount": 100_000,
+            "outgoing_amount": 100_000 - earned,
+            "fee_paid_or_earned": earned,
+            "incoming_contract_txid": null,
+            "outgoing_contract_txid": null,
+            "timelock": 20,
+            "makers_count": null,
+            "total_maker_fees": 0,
+            "mining_fee": mining_fee,
+            "fee_percentage": 0.0,
+            "input_utxos": []
+        }))
+        .unwrap()
+    }
+
+    fn query(window: AnalyticsWindow, from: Option<u64>, bucket: Option<u64>) -> AnalyticsQuery {
+        AnalyticsQuery {
+            window: Some(window),
+            from,
+            to: Some(10 * DAY),
+            bucket,
+        }
+    }
+
+    #[test]
+    fn windows_resolve_to_periods() {
+        let now = 20 * DAY;
+        assert_eq!(
+            Period::resolve(&query(AnalyticsWindow::Day, None, None), now),
+            Ok(Period {
+                from: 9 * DAY,
+                to: 10 * DAY,
+                bucket_secs: HOUR
+            })
+        );
+        assert_eq!(
+            Period::resolve(
+                &query(AnalyticsWindow::Custom, Some(DAY), Some(6 * HOUR)),
+                now
+            )
+            .map(|p| (p.from, p.bucket_secs)),
+            Ok((DAY, 6 * HOUR))
+        );
+        assert!(Period::resolve(&query(AnalyticsWindow::Custom, None, None), now).is_err());
+        assert!(Period::resolve(&query(AnalyticsWindow::Month, None, Some(60)), now).is_err());
+        assert!(Period::resolve(&query(AnalyticsWindow::Week, None, Some(0)), now).is_err());
+    }
+
+    #[test]
+    fn only_the_success_status_counts_as_successful() {
+        assert!(is_successful("Success"));
+        for status in [
+            "Unsuccessful",
+            "Incomplete",
+            "Failed",
+            "Recovered",
+            "success",
+            "",
+        ] {
+            assert!(!is_successful(status), "{status}");
+        }
+    }
+
+    #[test]
+    fn reports_are_summed_per_bucket() {
+        let period = Period {
+            from: 0,
+            to: 2 * DAY + HOUR,
+            bucket_secs: DAY,
+        };
+        let reports = [
+            report(HOUR, "Success", 1_000, 300),
+            report(2 * HOUR, "Failed", -200, 500),
+            report(DAY + HOUR, "Success", 2_000, 400),
+            // Outside the period
+            report(3 * DAY, "Success", 5_000, 100),
+        ];
+        let (totals, buckets) = aggregate(&reports, period);
+
+        assert_eq!(totals.swaps, 3);
+        assert_eq!(totals.successful, 2);
+        assert_eq!(totals.failed, 1);
+        assert_eq!(totals.earned, 2_800);
+        assert_eq!(totals.mining_fees, 1_200);
+        assert_eq!(totals.net_profit, 1_600);
+        assert_eq!(totals.volume, 300_000);
+        assert_eq!(totals.avg_duration_secs, Some(60.0));
+
+        assert_eq!(buckets.len(), 3);
+        assert_eq!(buckets[0].stats.swaps, 2);
+        assert_eq!(buckets[0].stats.success_rate, Some(0.5));
+        assert_eq!(buckets[1].stats.earned, 2_000);
+        assert_eq!(
+            (buckets[2].start, buckets[2].end),
+            (2 * DAY, 2 * DAY + HOUR)
+        );
+        assert_eq!(buckets[2].stats.swaps, 0);
+        assert_eq!(buckets[2].stats.success_rate, None);
+    }
+}
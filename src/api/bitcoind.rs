@@ -17,14 +17,9 @@ pub fn routes() -> Router<AppState> {
 /// Try to connect to a bitcoind RPC endpoint using a maker config.
 /// Returns the chain name (e.g. "regtest") on success.
 fn probe_rpc(config: &MakerConfig) -> Option<String> {
-    use coinswap::bitcoind::bitcoincore_rpc::{Auth, Client, RpcApi};
+    use coinswap::bitcoind::bitcoincore_rpc::RpcApi;
 
-    let auth = match &config.auth {
-        Some((u, p)) => Auth::UserPass(u.clone(), p.clone()),
-        None => Auth::None,
-    };
-    let url = format!("http://{}", config.rpc);
-    let client = Client::new(&url, auth).ok()?;
+    let client = config.rpc_client().ok()?;
     let info = client.get_blockchain_info().ok()?;
     Some(info.chain.to_string())
 }
@@ -2,7 +2,15 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use utoipa::ToSchema;
 
-use crate::maker_manager::{MakerConfig, MakerInfo as ManagerMakerInfo, MakerState};
+use crate::maker_manager::{
+    alerts::{Alert, AlertCondition, AlertRule, AlertState},
+    history::{HistoryPoint, UtxoCounts},
+    labels::{Label, LabelType},
+    message::{BumpMethod, FidelityFunding, OutgoingTx, UtxoPool, WalletAddressType},
+    renewal::{RenewalPolicy, RenewalRecord, RenewalStep},
+    webhooks::{Delivery, Webhook},
+    MakerConfig, MakerInfo as ManagerMakerInfo, MakerState, RescanJob, RescanKind,
+};
 
 /// Request body for `POST /api/makers`
 #[derive(Deserialize, ToSchema)]
@@ -21,6 +29,9 @@ pub struct CreateMakerRequest {
     #[schema(example = "maker1")]
     pub wallet_name: Option<String>,
     pub password: Option<String>,
+    /// Keep the wallet password in `makers.json` (default true). When false the maker
+    /// stays locked after a dashboard restart until unlocked via the API.
+    pub persist_password: Option<bool>,
     pub data_directory: Option<String>,
     #[schema(example = 6102)]
     pub network_port: Option<u16>,
@@ -45,6 +56,17 @@ pub struct CreateMakerRequest {
     #[schema(example = 0.001)]
     pub time_relative_fee_pct: Option<f64>,
     pub nostr_relays: Option<Vec<String>>,
+    /// Restore the wallet from a backup instead of generating a fresh one
+    pub restore: Option<RestoreWalletRequest>,
+}
+
+/// Wallet backup to restore when creating a maker
+#[derive(Deserialize, ToSchema)]
+pub struct RestoreWalletRequest {
+    /// Contents of a file from `POST /api/makers/{id}/wallet/backup`
+    pub backup: String,
+    /// Passphrase the backup was encrypted with
+    pub passphrase: String,
 }
 
 /// Request body for `PUT /api/makers/{id}/config`
@@ -105,6 +127,7 @@ impl UpdateMakerConfigRequest {
             tor_auth: self.tor_auth.or(base.tor_auth),
             wallet_name: self.wallet_name.or(base.wallet_name),
             password: self.password.or(base.password),
+            persist_password: base.persist_password,
             network_port: self.network_port.unwrap_or(base.network_port),
             rpc_port: self.rpc_port.unwrap_or(base.rpc_port),
             socks_port: self.socks_port.unwrap_or(base.socks_port),
@@ -126,6 +149,8 @@ impl UpdateMakerConfigRequest {
 }
 
 /// Request body for `POST /api/makers/{id}/send`
+///
+/// Exactly one of `feerate` (sat/vB) or `fee_target` must be provided.
 #[derive(Deserialize, ToSchema)]
 pub struct SendToAddressRequest {
     #[schema(example = "bcrt1qxyzw0k8a3gp6n9lqz7th0gkc4e5mvetlkgkay")]
@@ -133,7 +158,50 @@ pub struct SendToAddressRequest {
     #[schema(example = 50000)]
     pub amount: u64,
     #[schema(example = 1.0)]
-    pub feerate: f64,
+    pub feerate: Option<f64>,
+    /// Confirmation target used to estimate the feerate when `feerate` is omitted.
+    pub fee_target: Option<FeeTarget>,
+}
+
+/// Named confirmation targets for fee estimation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum FeePreset {
+    /// Next couple of blocks
+    Fast,
+    /// Within about an hour
+    Normal,
+    /// Within about a day
+    Economy,
+}
+
+impl FeePreset {
+    /// Confirmation target in blocks passed to `estimatesmartfee`.
+    pub fn target_blocks(self) -> u16 {
+        match self {
+            Self::Fast => 2,
+            Self::Normal => 6,
+            Self::Economy => 144,
+        }
+    }
+}
+
+/// Either a named preset (`"fast"`, `"normal"`, `"economy"`) or an explicit block target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum FeeTarget {
+    Preset(FeePreset),
+    #[schema(example = 6)]
+    Blocks(u16),
+}
+
+impl FeeTarget {
+    pub fn target_blocks(self) -> u16 {
+        match self {
+            Self::Preset(preset) => preset.target_blocks(),
+            Self::Blocks(blocks) => blocks,
+        }
+    }
 }
 
 /// Generic success / error JSON envelope
@@ -180,6 +248,8 @@ pub struct SuggestedMakerPorts {
 pub enum MakerStateDto {
     Running,
     Stopped,
+    /// Waiting for the wallet password via `POST /api/makers/{id}/unlock`
+    Locked,
 }
 
 impl From<MakerState> for MakerStateDto {
@@ -187,6 +257,7 @@ impl From<MakerState> for MakerStateDto {
         match s {
             MakerState::Running => Self::Running,
             MakerState::Stopped => Self::Stopped,
+            MakerState::Locked => Self::Locked,
         }
     }
 }
@@ -199,6 +270,8 @@ pub struct MakerInfoDetailed {
     pub rpc: String,
     pub zmq: String,
     pub wallet_name: Option<String>,
+    /// Whether the wallet password survives a dashboard restart
+    pub persist_password: bool,
     pub data_directory: Option<String>,
     pub network_port: u16,
     pub rpc_port: u16,
@@ -222,6 +295,7 @@ impl From<ManagerMakerInfo> for MakerInfoDetailed {
             rpc: info.config.rpc,
             zmq: info.config.zmq,
             wallet_name: info.config.wallet_name,
+            persist_password: info.config.persist_password,
             data_directory: info.config.data_directory.and_then(|d| {
                 if let Ok(path) = d.canonicalize() {
                     return path.to_str().map(str::to_string);
@@ -251,14 +325,63 @@ pub struct BalanceInfo {
     pub contract: u64,
     pub fidelity: u64,
     pub spendable: u64,
+    /// Frozen coins Core holds back from the balances above; absent when it couldn't be
+    /// looked up
+    pub frozen: Option<u64>,
 }
 
-#[derive(Debug, Serialize, Deserialize, ToSchema)]
+/// Wallet pool a UTXO belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum UtxoPoolDto {
+    Regular,
+    Swept,
+    Swap,
+    Contract,
+    Fidelity,
+}
+
+impl From<UtxoPool> for UtxoPoolDto {
+    fn from(pool: UtxoPool) -> Self {
+        match pool {
+            UtxoPool::Regular => Self::Regular,
+            UtxoPool::Swept => Self::Swept,
+            UtxoPool::Swap => Self::Swap,
+            UtxoPool::Contract => Self::Contract,
+            UtxoPool::Fidelity => Self::Fidelity,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
 pub struct UtxoInfo {
-    pub addr: String,
+    /// `txid:vout`
+    pub outpoint: String,
+    pub txid: String,
+    pub vout: u32,
+    /// `None` when the output script has no address form
+    pub addr: Option<String>,
     pub amount: u64,
     pub confirmations: u32,
-    pub utxo_type: String,
+    /// Output script type: `p2wpkh`, `p2wsh`, `p2tr`, `p2sh`, `p2pkh` or `unknown`
+    pub script_type: String,
+    /// BIP-32 path of the key, from the descriptor's key origin
+    pub derivation_path: Option<String>,
+    /// Height of the confirming block; absent while unconfirmed
+    pub block_height: Option<u32>,
+    /// Unix timestamp of the confirming block
+    pub block_time: Option<u64>,
+    pub utxo_type: UtxoPoolDto,
+    /// Index of the fidelity bond locked in this UTXO
+    pub fidelity_index: Option<u32>,
+    /// Relative timelock of the contract locking this UTXO, in blocks, for contract outputs
+    pub contract_timelock: Option<u16>,
+    /// Swap whose funding, contract or recovery transaction created this UTXO
+    pub swap_id: Option<String>,
+    /// Label of the UTXO, falling back to the label of its address
+    pub label: Option<String>,
+    /// Frozen UTXOs are never picked by coin selection
+    pub frozen: bool,
 }
 
 /// Swap history for a maker: active (in-flight) and completed (swept) swaps
@@ -270,7 +393,7 @@ pub struct SwapHistoryDto {
     pub completed: Vec<UtxoInfo>,
 }
 
-#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct MakerFeeInfoDto {
     pub maker_index: usize,
     pub maker_address: String,
@@ -280,7 +403,7 @@ pub struct MakerFeeInfoDto {
     pub total_fee: f64,
 }
 
-#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct SwapReportDto {
     pub swap_id: String,
     pub role: String,
@@ -322,11 +445,21 @@ pub struct SwapReportDto {
     pub output_swap_utxos: Vec<(u64, String)>,
 }
 
+/// One page of a maker's swap reports, newest first
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SwapReportPage {
+    pub reports: Vec<SwapReportDto>,
+    /// Pass as `cursor` to fetch the next page; absent on the last page
+    pub next_cursor: Option<String>,
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct MakerStatus {
     pub id: String,
     pub alive: bool,
     pub is_server_running: bool,
+    /// Latest rescan or descriptor re-import, if one was started
+    pub rescan: Option<RescanStatus>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -403,3 +536,1058 @@ pub struct BitcoindStatusInfo {
     /// True only when bitcoind was started by the dashboard (and can be stopped via /stop)
     pub managed: bool,
 }
+
+/// Feerate estimate for a single confirmation target
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FeeEstimateInfo {
+    pub target_blocks: u16,
+    /// Estimated feerate in sat/vB, or `None` if bitcoind has no estimate for this target
+    pub feerate: Option<f64>,
+}
+
+/// Response for `GET /api/fees`
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FeeEstimatesInfo {
+    pub estimates: Vec<FeeEstimateInfo>,
+    pub fast: Option<f64>,
+    pub normal: Option<f64>,
+    pub economy: Option<f64>,
+    /// Highest feerate (sat/vB) the dashboard will accept for a transaction
+    pub max_feerate: f64,
+}
+
+/// Fee-bumping strategy for `POST /api/makers/{id}/transactions/{txid}/bump`
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BumpMethodDto {
+    /// Replace the transaction, re-spending the same inputs at a higher feerate
+    #[default]
+    Rbf,
+    /// Spend our change output with a fee that lifts the parent+child package
+    Cpfp,
+}
+
+impl From<BumpMethodDto> for BumpMethod {
+    fn from(m: BumpMethodDto) -> Self {
+        match m {
+            BumpMethodDto::Rbf => Self::Rbf,
+            BumpMethodDto::Cpfp => Self::Cpfp,
+        }
+    }
+}
+
+/// Request body for `POST /api/makers/{id}/transactions/{txid}/bump`
+///
+/// Exactly one of `feerate` (sat/vB) or `fee_target` must be provided.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BumpFeeRequest {
+    #[serde(default)]
+    pub method: BumpMethodDto,
+    #[schema(example = 5.0)]
+    pub feerate: Option<f64>,
+    pub fee_target: Option<FeeTarget>,
+}
+
+/// Result of a fee bump
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BumpFeeResponse {
+    pub method: BumpMethodDto,
+    /// Txid of the replacement (RBF) or child (CPFP) transaction
+    pub txid: String,
+    /// Txid of the transaction that was bumped
+    pub bumped_txid: String,
+    /// Feerate of the new transaction in sat/vB
+    pub feerate: f64,
+    /// Absolute fee of the new transaction in sats
+    pub fee: u64,
+}
+
+/// A transaction broadcast through the dashboard
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OutgoingTxInfo {
+    pub txid: String,
+    pub address: String,
+    pub amount: u64,
+    pub fee: u64,
+    pub feerate: f64,
+    pub vsize: u64,
+    pub created_at: u64,
+    /// Change outpoint (`txid:vout`) usable for CPFP
+    pub change: Option<String>,
+    pub replaces: Option<String>,
+    pub replaced_by: Option<String>,
+    pub cpfp_parent: Option<String>,
+    /// Label of the transaction, or of the recipient address if the transaction has none
+    pub label: Option<String>,
+}
+
+impl From<OutgoingTx> for OutgoingTxInfo {
+    fn from(tx: OutgoingTx) -> Self {
+        Self {
+            txid: tx.txid.to_string(),
+            address: tx.address,
+            amount: tx.amount,
+            fee: tx.fee,
+            feerate: tx.feerate,
+            vsize: tx.vsize,
+            created_at: tx.created_at,
+            change: tx.change.map(|c| c.to_string()),
+            replaces: tx.replaces.map(|t| t.to_string()),
+            replaced_by: tx.replaced_by.map(|t| t.to_string()),
+            cpfp_parent: tx.cpfp_parent.map(|t| t.to_string()),
+            label: None,
+        }
+    }
+}
+
+/// What a wallet transaction did, from the maker's point of view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum TxCategory {
+    Deposit,
+    Withdrawal,
+    SwapIn,
+    SwapOut,
+    FidelityCreation,
+    FidelityRedemption,
+    Sweep,
+    ContractRecovery,
+}
+
+/// A single entry in a maker's wallet transaction history
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TransactionInfo {
+    pub txid: String,
+    pub category: TxCategory,
+    /// Net change to the wallet balance in sats, fees included
+    pub amount: i64,
+    /// Fee paid in sats, when the wallet funded the transaction
+    pub fee: Option<u64>,
+    pub confirmations: i32,
+    pub block_height: Option<u32>,
+    /// Unix timestamp of the confirming block
+    pub block_time: Option<u64>,
+    /// Unix timestamp the wallet first saw the transaction
+    pub time: u64,
+    /// Label of the transaction, if any
+    pub label: Option<String>,
+}
+
+/// One page of wallet transaction history, newest first
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TransactionPage {
+    /// Total number of transactions across all pages
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+    pub transactions: Vec<TransactionInfo>,
+}
+
+/// BIP-329 label type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelTypeDto {
+    Tx,
+    Addr,
+    Pubkey,
+    Input,
+    Output,
+    Xpub,
+}
+
+impl From<LabelType> for LabelTypeDto {
+    fn from(t: LabelType) -> Self {
+        match t {
+            LabelType::Tx => Self::Tx,
+            LabelType::Addr => Self::Addr,
+            LabelType::Pubkey => Self::Pubkey,
+            LabelType::Input => Self::Input,
+            LabelType::Output => Self::Output,
+            LabelType::Xpub => Self::Xpub,
+        }
+    }
+}
+
+impl From<LabelTypeDto> for LabelType {
+    fn from(t: LabelTypeDto) -> Self {
+        match t {
+            LabelTypeDto::Tx => Self::Tx,
+            LabelTypeDto::Addr => Self::Addr,
+            LabelTypeDto::Pubkey => Self::Pubkey,
+            LabelTypeDto::Input => Self::Input,
+            LabelTypeDto::Output => Self::Output,
+            LabelTypeDto::Xpub => Self::Xpub,
+        }
+    }
+}
+
+/// A BIP-329 label record
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LabelDto {
+    #[serde(rename = "type")]
+    pub label_type: LabelTypeDto,
+    /// Txid, address, pubkey, `txid:vout` outpoint or xpub, depending on `type`
+    #[serde(rename = "ref")]
+    #[schema(example = "bcrt1qxyz...")]
+    pub reference: String,
+    #[schema(example = "cold storage")]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spendable: Option<bool>,
+}
+
+impl From<Label> for LabelDto {
+    fn from(l: Label) -> Self {
+        Self {
+            label_type: l.label_type.into(),
+            reference: l.reference,
+            label: l.label,
+            origin: l.origin,
+            spendable: l.spendable,
+        }
+    }
+}
+
+impl From<LabelDto> for Label {
+    fn from(l: LabelDto) -> Self {
+        Self {
+            label_type: l.label_type.into(),
+            reference: l.reference,
+            label: l.label,
+            origin: l.origin,
+            spendable: l.spendable,
+        }
+    }
+}
+
+/// Result of a BIP-329 import
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LabelImportResponse {
+    /// Records read from the import
+    pub imported: usize,
+    /// Labels stored for the maker after the import
+    pub total: usize,
+}
+
+/// Receive address script type
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AddressTypeDto {
+    P2wpkh,
+    #[default]
+    P2tr,
+}
+
+impl From<AddressTypeDto> for WalletAddressType {
+    fn from(t: AddressTypeDto) -> Self {
+        match t {
+            AddressTypeDto::P2wpkh => Self::P2wpkh,
+            AddressTypeDto::P2tr => Self::P2tr,
+        }
+    }
+}
+
+impl From<WalletAddressType> for AddressTypeDto {
+    fn from(t: WalletAddressType) -> Self {
+        match t {
+            WalletAddressType::P2wpkh => Self::P2wpkh,
+            WalletAddressType::P2tr => Self::P2tr,
+        }
+    }
+}
+
+/// A receive address handed out by `GET /api/makers/{id}/address`
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NewAddressInfo {
+    pub address: String,
+    pub address_type: AddressTypeDto,
+    /// True if this is a previously issued address that has not received funds yet
+    pub reused: bool,
+    pub label: Option<String>,
+}
+
+/// A previously issued receive address and what it has received
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AddressInfo {
+    pub address: String,
+    pub address_type: AddressTypeDto,
+    /// Unix timestamp the address was issued
+    pub issued_at: u64,
+    /// Whether any transaction has paid to the address
+    pub used: bool,
+    /// Total received in sats, unconfirmed included
+    pub received: u64,
+    /// Number of transactions paying to the address
+    pub tx_count: usize,
+    pub label: Option<String>,
+}
+
+/// Freeze state of a UTXO after a freeze/unfreeze request
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FrozenUtxoInfo {
+    pub outpoint: String,
+    pub frozen: bool,
+    /// Whether the lock was also applied to the Bitcoin Core wallet, which keeps the
+    /// maker's own swap funding from picking the coin
+    pub core_locked: bool,
+}
+
+/// Request body for `POST /api/makers/{id}/wallet/backup`
+#[derive(Deserialize, ToSchema)]
+pub struct WalletBackupRequest {
+    /// Must repeat the maker ID to confirm the export
+    #[schema(example = "maker1")]
+    pub confirm: String,
+    /// Current wallet encryption password; required when the wallet is encrypted
+    pub password: Option<String>,
+    /// Passphrase to encrypt the backup with
+    pub passphrase: String,
+}
+
+/// Request body for `POST /api/makers/{id}/unlock`
+#[derive(Deserialize, ToSchema)]
+pub struct UnlockMakerRequest {
+    /// Wallet password; kept in memory only
+    pub password: String,
+}
+
+/// Request body for `POST /api/makers/{id}/password`
+#[derive(Deserialize, ToSchema)]
+pub struct ChangePasswordRequest {
+    /// Current wallet password; required when the wallet is encrypted
+    pub current_password: Option<String>,
+    pub new_password: String,
+    /// Keep the new password in `makers.json`; defaults to the maker's current setting
+    pub persist_password: Option<bool>,
+}
+
+/// Request body for `POST /api/makers/{id}/rescan` and `POST /api/makers/{id}/descriptors/reimport`
+#[derive(Deserialize, ToSchema)]
+pub struct RescanRequest {
+    /// First block to scan from; defaults to the genesis block
+    #[schema(example = 200000)]
+    pub from_height: Option<u64>,
+    /// Last block to scan (rescan only); defaults to the chain tip
+    pub stop_height: Option<u64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RescanKindDto {
+    Rescan,
+    Reimport,
+}
+
+impl From<RescanKind> for RescanKindDto {
+    fn from(kind: RescanKind) -> Self {
+        match kind {
+            RescanKind::Rescan => Self::Rescan,
+            RescanKind::Reimport => Self::Reimport,
+        }
+    }
+}
+
+/// Progress of a rescan or descriptor re-import
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RescanStatus {
+    pub kind: RescanKindDto,
+    pub from_height: u64,
+    pub started_at: u64,
+    pub finished_at: Option<u64>,
+    pub running: bool,
+    /// Bitcoin Core's scan progress from 0 to 1, while it is scanning
+    pub progress: Option<f32>,
+    pub error: Option<String>,
+}
+
+impl From<RescanJob> for RescanStatus {
+    fn from(job: RescanJob) -> Self {
+        Self {
+            kind: job.kind.into(),
+            from_height: job.from_height,
+            started_at: job.started_at,
+            running: job.finished_at.is_none(),
+            finished_at: job.finished_at,
+            progress: None,
+            error: job.error,
+        }
+    }
+}
+
+/// Request body for `POST /api/makers/{id}/fidelity`
+///
+/// `amount` and `timelock` default to the maker's `fidelity_amount` and `fidelity_timelock`;
+/// the feerate defaults to the `normal` fee target.
+#[derive(Deserialize, ToSchema)]
+pub struct CreateFidelityRequest {
+    /// Amount to lock, in sats
+    #[schema(example = 50000)]
+    pub amount: Option<u64>,
+    /// Lock duration in blocks from the current tip
+    #[schema(example = 15000)]
+    pub timelock: Option<u32>,
+    pub feerate: Option<f64>,
+    pub fee_target: Option<FeeTarget>,
+    /// Only report the funding requirements, without creating the bond
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Funding requirements of a fidelity bond, and the bond once it was created
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FidelityFundingInfo {
+    /// Index of the created bond; absent for a dry run
+    pub index: Option<u32>,
+    /// Funding transaction of the created bond
+    pub txid: Option<String>,
+    pub amount: u64,
+    /// Lock duration in blocks from the tip at creation
+    pub timelock: u32,
+    /// Absolute block height the bond unlocks at
+    pub locktime: u32,
+    pub feerate: f64,
+    /// Estimated fee of the funding transaction, in sats
+    pub fee: u64,
+    /// Amount plus fee
+    pub required: u64,
+    /// Spendable balance before funding, in sats
+    pub spendable: u64,
+    pub sufficient: bool,
+}
+
+impl FidelityFundingInfo {
+    pub fn new(funding: FidelityFunding, timelock: u32) -> Self {
+        Self {
+            index: funding.index,
+            txid: funding.txid.map(|txid| txid.to_string()),
+            amount: funding.amount,
+            timelock,
+            locktime: funding.locktime,
+            feerate: funding.feerate,
+            fee: funding.fee,
+            required: funding.amount + funding.fee,
+            spendable: funding.spendable,
+            sufficient: funding.sufficient,
+        }
+    }
+}
+
+/// Request body for `POST /api/makers/{id}/fidelity/{index}/redeem`
+///
+/// The feerate defaults to the `normal` fee target.
+#[derive(Deserialize, ToSchema)]
+pub struct RedeemFidelityRequest {
+    #[schema(example = 2.0)]
+    pub feerate: Option<f64>,
+    pub fee_target: Option<FeeTarget>,
+}
+
+/// A fidelity bond of a maker
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FidelityBondDto {
+    pub index: u32,
+    /// Bond outpoint as `txid:vout`
+    pub outpoint: String,
+    /// Locked amount in sats
+    pub amount: u64,
+    /// Absolute block height the bond unlocks at
+    pub locktime: u32,
+    /// Height of the block that confirmed the bond
+    pub conf_height: Option<u32>,
+    /// Block height the bond's certificate expires at
+    pub cert_expiry: Option<u32>,
+    /// Bond value in sats as advertised to takers; absent while it cannot be computed
+    pub bond_value: Option<u64>,
+    /// Whether this is the bond the maker currently advertises
+    pub advertised: bool,
+    /// Already redeemed
+    pub spent: bool,
+    /// Timelock has passed and the bond can be redeemed
+    pub expired: Option<bool>,
+    /// Blocks until the timelock expires, 0 once expired; absent if the tip is unknown
+    pub blocks_to_expiry: Option<u32>,
+    /// Estimated seconds until expiry at 10 minutes per block
+    pub secs_to_expiry: Option<u64>,
+}
+
+/// Fidelity bond renewal policy of a maker
+///
+/// Evaluated on every new block: once all unspent bonds are within `blocks_before_expiry`
+/// of expiry, a new bond with the maker's `fidelity_amount` and `fidelity_timelock` is created.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RenewalPolicyDto {
+    pub enabled: bool,
+    #[schema(example = 144)]
+    pub blocks_before_expiry: u32,
+    /// Redeem expired bonds so their funds go into the renewal
+    pub reuse_redeemed_funds: bool,
+    /// Only record the actions that would be taken
+    pub dry_run: bool,
+    /// Feerate in sat/vB; estimated with the `normal` target when unset
+    pub feerate: Option<f64>,
+}
+
+impl From<RenewalPolicy> for RenewalPolicyDto {
+    fn from(p: RenewalPolicy) -> Self {
+        Self {
+            enabled: p.enabled,
+            blocks_before_expiry: p.blocks_before_expiry,
+            reuse_redeemed_funds: p.reuse_redeemed_funds,
+            dry_run: p.dry_run,
+            feerate: p.feerate,
+        }
+    }
+}
+
+impl From<RenewalPolicyDto> for RenewalPolicy {
+    fn from(p: RenewalPolicyDto) -> Self {
+        Self {
+            enabled: p.enabled,
+            blocks_before_expiry: p.blocks_before_expiry,
+            reuse_redeemed_funds: p.reuse_redeemed_funds,
+            dry_run: p.dry_run,
+            feerate: p.feerate,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RenewalActionDto {
+    Redeem,
+    Create,
+}
+
+/// Audit record of an automatic renewal action
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RenewalRecordDto {
+    /// Unix timestamp of the evaluation
+    pub at: u64,
+    /// Chain tip the action was planned at
+    pub height: u32,
+    pub action: RenewalActionDto,
+    pub dry_run: bool,
+    /// Feerate in sat/vB the action was, or would have been, broadcast at
+    pub feerate: Option<f64>,
+    /// Index of the redeemed or created bond
+    pub bond_index: Option<u32>,
+    /// Amount of the new bond, for `create`
+    pub amount: Option<u64>,
+    /// Unlock height of the new bond, for `create`
+    pub locktime: Option<u32>,
+    pub txid: Option<String>,
+    pub error: Option<String>,
+}
+
+impl From<RenewalRecord> for RenewalRecordDto {
+    fn from(r: RenewalRecord) -> Self {
+        let (action, amount, locktime) = match r.step {
+            RenewalStep::Redeem { .. } => (RenewalActionDto::Redeem, None, None),
+            RenewalStep::Create { amount, locktime } => {
+                (RenewalActionDto::Create, Some(amount), Some(locktime))
+            }
+        };
+        Self {
+            at: r.at,
+            height: r.height,
+            action,
+            dry_run: r.dry_run,
+            feerate: r.feerate,
+            bond_index: r.bond_index,
+            amount,
+            locktime,
+            txid: r.txid.map(|txid| txid.to_string()),
+            error: r.error,
+        }
+    }
+}
+
+/// A bond advertised by one of the dashboard's makers, for comparison
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdvertisedBondInfo {
+    pub maker_id: String,
+    pub index: u32,
+    pub amount: u64,
+    pub locktime: u32,
+    /// Bond value in sats as computed by the maker's wallet
+    pub bond_value: u64,
+}
+
+/// What-if valuation of a fidelity bond
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BondValueEstimate {
+    pub amount: u64,
+    /// Lock duration in blocks
+    pub timelock: u32,
+    /// Capital lock-up period in days at 10 minutes per block
+    pub lockup_days: f64,
+    /// Bond value in sats as a taker scores a freshly confirmed bond
+    pub bond_value: u64,
+    /// Whether `timelock` is accepted as a maker's `fidelity_timelock`
+    pub timelock_allowed: bool,
+    /// Bonds currently advertised by the dashboard's other makers, highest value first
+    pub advertised: Vec<AdvertisedBondInfo>,
+    /// 1-based position of this bond among the advertised ones by value
+    pub rank: Option<usize>,
+}
+
+/// Layout of an exported CSV file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// Every field of the record, amounts in sats and BTC
+    Csv,
+    /// Koinly universal import format
+    Koinly,
+    /// CoinTracking CSV import format
+    Cointracking,
+}
+
+/// Preset period for swap analytics, ending at `to` (default now)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AnalyticsWindow {
+    Day,
+    Week,
+    /// 30 days
+    Month,
+    /// `from` to `to`
+    Custom,
+}
+
+/// Swap report figures over a period
+#[derive(Debug, Default, Clone, PartialEq, Serialize, ToSchema)]
+pub struct SwapStats {
+    pub swaps: u64,
+    pub successful: u64,
+    pub failed: u64,
+    /// Share of successful swaps from 0 to 1; absent without swaps
+    pub success_rate: Option<f64>,
+    pub failure_rate: Option<f64>,
+    /// Sum of `fee_paid_or_earned`, in sats
+    pub earned: i64,
+    /// Mining fees paid, in sats
+    pub mining_fees: u64,
+    /// `earned` minus `mining_fees`, in sats
+    pub net_profit: i64,
+    /// Incoming swap amount, in sats
+    pub volume: u64,
+    pub avg_duration_secs: Option<f64>,
+}
+
+/// Swap figures of one chart interval, `start` inclusive and `end` exclusive
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct AnalyticsBucket {
+    pub start: u64,
+    pub end: u64,
+    pub stats: SwapStats,
+}
+
+/// Earnings and swap analytics of a maker
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SwapAnalytics {
+    /// Unix timestamp the period starts at
+    pub from: u64,
+    /// Unix timestamp the period ends at
+    pub to: u64,
+    pub bucket_secs: u64,
+    pub totals: SwapStats,
+    pub buckets: Vec<AnalyticsBucket>,
+}
+
+/// Period totals of one maker in the fleet analytics
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MakerSwapStats {
+    pub maker_id: String,
+    pub totals: SwapStats,
+}
+
+/// Earnings and swap analytics across all makers
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FleetAnalytics {
+    pub from: u64,
+    pub to: u64,
+    pub bucket_secs: u64,
+    pub totals: SwapStats,
+    pub buckets: Vec<AnalyticsBucket>,
+    pub makers: Vec<MakerSwapStats>,
+}
+
+/// Return on the capital a maker commits, over a period
+///
+/// Amounts are in sats; yields and rates are annualised fractions (0.05 = 5% a year).
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReturnOnCapital {
+    pub from: u64,
+    pub to: u64,
+    pub swaps: u64,
+    /// Swap fees earned minus mining fees
+    pub net_profit: i64,
+    /// Time-weighted average balances over the period
+    pub avg_regular: u64,
+    pub avg_swap: u64,
+    pub avg_fidelity: u64,
+    pub avg_committed: u64,
+    /// Number of balance samples inside the period
+    pub samples: usize,
+    /// Whether no samples covered the period and current balances were used instead
+    pub estimated: bool,
+    /// Net profit over average committed capital (regular + swap + fidelity)
+    pub annualised_yield: Option<f64>,
+    /// Net profit over average liquidity available to swaps (regular + swap)
+    pub liquid_yield: Option<f64>,
+    /// Rate the fidelity capital is charged at: the requested `opportunity_rate`, or the
+    /// liquid yield, i.e. what the bonded coins would have earned as swap liquidity
+    pub opportunity_rate: Option<f64>,
+    /// What the average fidelity capital would have earned at `opportunity_rate`
+    pub fidelity_opportunity_cost: u64,
+    /// `net_profit` minus `fidelity_opportunity_cost`
+    pub profit_after_fidelity_cost: i64,
+}
+
+/// UTXO counts per wallet pool
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UtxoCountsInfo {
+    pub regular: u32,
+    pub swept: u32,
+    pub swap: u32,
+    pub contract: u32,
+    pub fidelity: u32,
+}
+
+impl From<UtxoCounts> for UtxoCountsInfo {
+    fn from(c: UtxoCounts) -> Self {
+        Self {
+            regular: c.regular,
+            swept: c.swept,
+            swap: c.swap,
+            contract: c.contract,
+            fidelity: c.fidelity,
+        }
+    }
+}
+
+/// Balances and status of a maker over one history interval
+///
+/// Balances and UTXO counts are averaged over the samples the wallet answered in; they are
+/// absent when it answered in none.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BalanceHistoryPoint {
+    /// Unix timestamp the interval starts at
+    pub at: u64,
+    /// Samples recorded in the interval
+    pub samples: u32,
+    /// Share of samples the maker was alive in, from 0 to 1
+    pub alive: f64,
+    /// Share of samples the coinswap server was running in, from 0 to 1
+    pub running: f64,
+    /// Highest block height of the maker's node
+    pub height: Option<u64>,
+    pub balance: Option<BalanceInfo>,
+    pub utxos: Option<UtxoCountsInfo>,
+}
+
+impl From<HistoryPoint> for BalanceHistoryPoint {
+    fn from(p: HistoryPoint) -> Self {
+        Self {
+            at: p.at,
+            samples: p.samples,
+            alive: p.alive,
+            running: p.running,
+            height: p.height,
+            balance: p.wallet.map(|w| BalanceInfo {
+                regular: w.regular,
+                swap: w.swap,
+                contract: w.contract,
+                fidelity: w.fidelity,
+                spendable: w.spendable,
+                frozen: None,
+            }),
+            utxos: p.wallet.map(|w| w.utxos.into()),
+        }
+    }
+}
+
+/// Broad cause of a failed swap, inferred from its error message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum SwapErrorCategory {
+    /// The counterparty stopped responding in time
+    Timeout,
+    /// Connection, Tor or peer failures
+    Network,
+    /// Not enough funds or liquidity for the swap or its fees
+    InsufficientFunds,
+    /// Invalid signatures, contracts or unexpected protocol messages
+    Protocol,
+    /// Failed broadcasts or Bitcoin Core RPC errors
+    Broadcast,
+    /// An error message matching none of the above
+    Other,
+    /// No error message was recorded
+    Unknown,
+}
+
+/// Failed and recovered swaps sharing an error category
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorCategoryStats {
+    pub category: SwapErrorCategory,
+    /// Swaps that failed without a recovery transaction
+    pub failed: u64,
+    /// Swaps whose contracts were recovered on chain
+    pub recovered: u64,
+    /// End time of the latest swap in the category
+    pub last_seen: u64,
+    /// Error message of the latest swap in the category
+    pub last_error: Option<String>,
+}
+
+/// A swap whose contract coins were recovered, with the transactions involved
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RecoveryInfo {
+    pub swap_id: String,
+    pub role: String,
+    pub status: String,
+    pub end_timestamp: u64,
+    pub category: SwapErrorCategory,
+    pub error_message: Option<String>,
+    pub recovery_duration_seconds: f64,
+    /// Contract transactions whose outputs were recovered
+    pub contract_txids: Vec<String>,
+    /// Transactions that spent the contracts back into the wallet
+    pub recovery_txids: Vec<String>,
+}
+
+/// A contract output still waiting for its timelock before it can be recovered
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PendingRecovery {
+    pub utxo: UtxoInfo,
+    /// Relative timelock of the contract in blocks, read from its script
+    pub timelock: Option<u16>,
+    /// Blocks until the timelock path can be spent; 0 once it can
+    pub blocks_remaining: Option<u32>,
+    /// Height at which the timelock path becomes spendable
+    pub spendable_at_height: Option<u64>,
+}
+
+/// Failed swaps, recoveries and contracts awaiting recovery for a maker
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RecoveryInsights {
+    /// Swaps that failed without a recovery transaction
+    pub failed: u64,
+    pub recovered: u64,
+    /// By error category, most frequent first
+    pub categories: Vec<ErrorCategoryStats>,
+    /// Recovered swaps, newest first
+    pub recoveries: Vec<RecoveryInfo>,
+    /// Contract outputs still in the wallet, soonest recoverable first
+    pub pending: Vec<PendingRecovery>,
+    /// Current block height, if the node answered
+    pub tip_height: Option<u64>,
+    /// Why contract outputs could not be listed, if they couldn't
+    pub pending_error: Option<String>,
+}
+
+/// Request body for `POST /api/webhooks` and `PUT /api/webhooks/{id}`
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct WebhookRequest {
+    /// http(s) URL the events are posted to
+    #[schema(example = "https://example.com/hooks/maker")]
+    pub url: String,
+    /// Key for the `X-Webhook-Signature` HMAC. On update, omit to keep the current secret
+    /// or pass an empty string to remove it
+    pub secret: Option<String>,
+    /// Event types to deliver, e.g. `maker_crashed`; defaults to maker crashes, completed
+    /// and failed swaps, expiring fidelity bonds, low balances and bitcoind going down
+    #[serde(default)]
+    pub events: Vec<String>,
+    /// Defaults to true
+    pub enabled: Option<bool>,
+}
+
+/// A configured webhook; the secret itself is never returned
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookInfo {
+    pub id: u64,
+    pub url: String,
+    pub has_secret: bool,
+    /// Event types delivered; empty for the default set
+    pub events: Vec<String>,
+    pub enabled: bool,
+}
+
+impl From<Webhook> for WebhookInfo {
+    fn from(w: Webhook) -> Self {
+        Self {
+            id: w.id,
+            url: w.url,
+            has_secret: w.secret.is_some(),
+            events: w.events,
+            enabled: w.enabled,
+        }
+    }
+}
+
+/// A webhook delivery attempt
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookDeliveryInfo {
+    pub id: u64,
+    pub webhook_id: u64,
+    /// Event delivered; absent for test deliveries
+    pub event_id: Option<u64>,
+    #[schema(example = "maker_crashed")]
+    pub event_type: String,
+    /// 1 for the first attempt at an event
+    pub attempt: u32,
+    /// Unix timestamp of the attempt
+    pub at: u64,
+    /// HTTP status of the response, if one was received
+    pub status: Option<u16>,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+impl From<Delivery> for WebhookDeliveryInfo {
+    fn from(d: Delivery) -> Self {
+        Self {
+            id: d.id,
+            webhook_id: d.webhook_id,
+            event_id: d.event_id,
+            event_type: d.event_type,
+            attempt: d.attempt,
+            at: d.at,
+            status: d.status,
+            success: d.error.is_none(),
+            error: d.error,
+        }
+    }
+}
+
+/// What an alert rule checks for, per maker
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AlertConditionDto {
+    /// Spendable balance below `sats`
+    LowBalance { sats: u64 },
+    /// No successful swap for `hours`, counted from dashboard start until the maker has one
+    NoSuccessfulSwap { hours: u64 },
+    /// The maker has not answered for `minutes` while its server should be running
+    MakerDown { minutes: u64 },
+    /// The node's verification progress is below `progress`, e.g. 0.999
+    NodeSyncBelow { progress: f64 },
+    /// Less than `bytes` free on the disk holding the maker's data directory
+    DiskFreeBelow { bytes: u64 },
+    /// A fidelity bond unlocks within `blocks`
+    FidelityExpiring { blocks: u32 },
+}
+
+impl From<AlertCondition> for AlertConditionDto {
+    fn from(c: AlertCondition) -> Self {
+        match c {
+            AlertCondition::LowBalance { sats } => Self::LowBalance { sats },
+            AlertCondition::NoSuccessfulSwap { hours } => Self::NoSuccessfulSwap { hours },
+            AlertCondition::MakerDown { minutes } => Self::MakerDown { minutes },
+            AlertCondition::NodeSyncBelow { progress } => Self::NodeSyncBelow { progress },
+            AlertCondition::DiskFreeBelow { bytes } => Self::DiskFreeBelow { bytes },
+            AlertCondition::FidelityExpiring { blocks } => Self::FidelityExpiring { blocks },
+        }
+    }
+}
+
+impl From<AlertConditionDto> for AlertCondition {
+    fn from(c: AlertConditionDto) -> Self {
+        match c {
+            AlertConditionDto::LowBalance { sats } => Self::LowBalance { sats },
+            AlertConditionDto::NoSuccessfulSwap { hours } => Self::NoSuccessfulSwap { hours },
+            AlertConditionDto::MakerDown { minutes } => Self::MakerDown { minutes },
+            AlertConditionDto::NodeSyncBelow { progress } => Self::NodeSyncBelow { progress },
+            AlertConditionDto::DiskFreeBelow { bytes } => Self::DiskFreeBelow { bytes },
+            AlertConditionDto::FidelityExpiring { blocks } => Self::FidelityExpiring { blocks },
+        }
+    }
+}
+
+/// Request body for `POST /api/alerts/rules` and `PUT /api/alerts/rules/{id}`
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AlertRuleRequest {
+    #[schema(example = "Low balance")]
+    pub name: String,
+    pub condition: AlertConditionDto,
+    /// Only evaluate for this maker; all makers when omitted
+    pub maker: Option<String>,
+    /// Defaults to true
+    pub enabled: Option<bool>,
+}
+
+/// A configured alert rule
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AlertRuleInfo {
+    pub id: u64,
+    pub name: String,
+    pub condition: AlertConditionDto,
+    pub maker: Option<String>,
+    pub enabled: bool,
+}
+
+impl From<AlertRule> for AlertRuleInfo {
+    fn from(r: AlertRule) -> Self {
+        Self {
+            id: r.id,
+            name: r.name,
+            condition: r.condition.into(),
+            maker: r.maker,
+            enabled: r.enabled,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertStateDto {
+    Firing,
+    Resolved,
+}
+
+impl From<AlertState> for AlertStateDto {
+    fn from(s: AlertState) -> Self {
+        match s {
+            AlertState::Firing => Self::Firing,
+            AlertState::Resolved => Self::Resolved,
+        }
+    }
+}
+
+/// An alert raised by a rule for a maker
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AlertInfo {
+    pub id: u64,
+    pub rule_id: u64,
+    pub rule_name: String,
+    pub maker: String,
+    pub state: AlertStateDto,
+    /// Latest description of the condition
+    pub message: String,
+    /// Unix timestamp the alert was raised at
+    pub fired_at: u64,
+    pub resolved_at: Option<u64>,
+    /// Unix timestamp the condition was last found holding
+    pub updated_at: u64,
+}
+
+impl From<Alert> for AlertInfo {
+    fn from(a: Alert) -> Self {
+        Self {
+            id: a.id,
+            rule_id: a.rule_id,
+            rule_name: a.rule_name,
+            maker: a.maker,
+            state: a.state.into(),
+            message: a.message,
+            fired_at: a.fired_at,
+            resolved_at: a.resolved_at,
+            updated_at: a.updated_at,
+        }
+    }
+}
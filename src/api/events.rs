@@ -0,0 +1,587 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        FromRequestParts, Query, Request, State,
+    },
+    http::{header, HeaderMap, StatusCode},
+    response::{
+        sse::{Event as SseEvent, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    routing::get,
+    Json, Router,
+};
+use futures::{future, stream, Stream, StreamExt};
+use serde::Deserialize;
+use tokio::{sync::broadcast::error::RecvError, task::JoinHandle};
+use tracing::debug;
+
+use super::{
+    analytics::is_successful, dto::ApiResponse, metrics::sample_node, swap_index,
+    wallet::reapply_frozen, AppState,
+};
+use crate::maker_manager::{
+    events::{Event, EventBus, EventKind},
+    message::{FidelityBondSummary, MessageResponse, UtxoPool},
+    MakerConfig,
+};
+
+/// How often the watcher looks for changes in makers and nodes.
+const WATCH_INTERVAL: Duration = Duration::from_secs(10);
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/events", get(get_events))
+}
+
+#[derive(Deserialize)]
+struct EventsQuery {
+    /// Only this maker's events, plus node events
+    maker: Option<String>,
+    /// Replay retained events after this ID first
+    since: Option<u64>,
+}
+
+/// Stream dashboard events
+///
+/// Served as Server-Sent Events, or over a WebSocket when the request asks for an upgrade;
+/// WebSocket messages carry the same JSON as SSE `data`. Events cover maker lifecycle and
+/// configuration, balance changes, new UTXOs, swaps starting, completing and failing, new
+/// blocks and the node going up or down. With `maker`, only that maker's events (and node
+/// events) are sent. A client resuming after a disconnect passes the last ID it saw as
+/// `since` (or, for SSE, the `Last-Event-ID` header) to first receive the retained events it
+/// missed; the latest 1000 events are retained.
+#[utoipa::path(
+    get,
+    path = "/api/events",
+    tag = "monitoring",
+    params(
+        ("maker" = Option<String>, Query, description = "Only this maker's events, plus node events"),
+        ("since" = Option<u64>, Query, description = "Replay retained events after this event ID first")
+    ),
+    responses(
+        (status = 200, description = "Event stream; each event's JSON carries its id, time, maker and type", content_type = "text/event-stream"),
+        (status = 101, description = "Switched to a WebSocket delivering one JSON event per text message"),
+        (status = 404, description = "Maker not found", body = ApiResponse<String>)
+    )
+)]
+async fn get_events(
+    State(state): State<AppState>,
+    Query(query): Query<EventsQuery>,
+    headers: HeaderMap,
+    request: Request,
+) -> Response {
+    let bus = {
+        let mgr = state.lock().await;
+        if let Some(id) = query.maker.as_ref().filter(|id| !mgr.has_maker(id)) {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::<()>::err(format!("Maker '{id}' not found"))),
+            )
+                .into_response();
+        }
+        mgr.events()
+    };
+    let since = query.since.or_else(|| {
+        headers
+            .get("last-event-id")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok())
+    });
+    let events = subscribe(bus, since, query.maker);
+
+    let upgrade = headers
+        .get(header::UPGRADE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.eq_ignore_ascii_case("websocket"));
+    if upgrade {
+        let (mut parts, _) = request.into_parts();
+        return match WebSocketUpgrade::from_request_parts(&mut parts, &state).await {
+            Ok(ws) => ws.on_upgrade(move |socket| forward(socket, events)),
+            Err(rejection) => rejection.into_response(),
+        };
+    }
+
+    let events = events.map(|event| {
+        let data = serde_json::to_string(&event).unwrap_or_default();
+        Ok::<_, Infallible>(
+            SseEvent::default()
+                .id(event.id.to_string())
+                .event(event.kind.name())
+                .data(data),
+        )
+    });
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+/// Events after `since` followed by live ones, restricted to `maker` and node events if set.
+fn subscribe(
+    bus: Arc<EventBus>,
+    since: Option<u64>,
+    maker: Option<String>,
+) -> impl Stream<Item = Event> + Send + 'static {
+    let sub = bus.subscribe(since);
+    let last = sub.replay.last().map_or(sub.last_id, |event| event.id);
+    let pending = VecDeque::from(sub.replay);
+    stream::unfold(
+        (pending, sub.receiver, bus, last),
+        |(mut pending, mut receiver, bus, mut last)| async move {
+            loop {
+                if let Some(event) = pending.pop_front() {
+                    last = event.id;
+                    return Some((event, (pending, receiver, bus, last)));
+                }
+                match receiver.recv().await {
+                    Ok(event) if event.id > last => pending.push_back(event),
+                    Ok(_) => {}
+                    // Fell behind the channel; catch up from the backlog
+                    Err(RecvError::Lagged(_)) => pending.extend(bus.since(last)),
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        },
+    )
+    .filter(move |event| {
+        future::ready(match (&maker, &event.maker) {
+            (Some(maker), Some(of)) => maker == of,
+            _ => true,
+        })
+    })
+}
+
+/// Sends `events` over the socket until either side goes away.
+async fn forward(mut socket: WebSocket, events: impl Stream<Item = Event> + Send) {
+    let mut events = std::pin::pin!(events);
+    loop {
+        tokio::select! {
+            event = events.next() => {
+                let Some(event) = event else { break };
+                let text = serde_json::to_string(&event).unwrap_or_default();
+                if socket.send(Message::Text(text.into())).await.is_err() {
+                    break;
+                }
+            }
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+/// Node state last seen by the watcher.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct NodeState {
+    up: bool,
+    height: Option<u64>,
+}
+
+/// Events for a node moving from `previous` (unknown on the first poll) to `now`.
+fn node_events(rpc: &str, previous: Option<NodeState>, now: NodeState) -> Vec<EventKind> {
+    let rpc = rpc.to_string();
+    let mut events = Vec::new();
+    match (previous.map(|p| p.up), now.up) {
+        (Some(false), true) => events.push(EventKind::BitcoindUp { rpc: rpc.clone() }),
+        (Some(true) | None, false) => events.push(EventKind::BitcoindDown { rpc: rpc.clone() }),
+        _ => {}
+    }
+    if let (Some(previous), Some(height)) = (previous.and_then(|p| p.height), now.height) {
+        if height > previous {
+            events.push(EventKind::NewBlock { rpc, height });
+        }
+    }
+    events
+}
+
+/// What the watcher last saw of one maker's wallet and swap reports.
+#[derive(Debug, Default)]
+struct MakerState {
+    balance: Option<[u64; 5]>,
+    low_balance: bool,
+    utxos: Option<HashSet<String>>,
+    swaps: Option<HashSet<String>>,
+    /// Tip the bonds were last checked at
+    bonds_checked_at: Option<u64>,
+    /// Bonds already reported as expiring
+    expiring: HashSet<u32>,
+    /// Whether the frozen UTXO locks are in place in Core since the node was last seen up
+    frozen_locked: bool,
+}
+
+/// A [`EventKind::LowBalance`] event when `spendable` drops below `threshold`; `was_low`
+/// keeps it from repeating until the balance recovers.
+fn low_balance_event(was_low: &mut bool, spendable: u64, threshold: u64) -> Option<EventKind> {
+    let low = spendable < threshold;
+    let event = (low && !*was_low).then_some(EventKind::LowBalance {
+        spendable,
+        threshold,
+    });
+    *was_low = low;
+    event
+}
+
+/// Events for the unspent bonds unlocking within `window` blocks of `tip` that aren't in
+/// `reported` yet. `reported` is left holding the bonds currently in the window.
+fn expiring_bond_events(
+    reported: &mut HashSet<u32>,
+    bonds: &[FidelityBondSummary],
+    tip: u32,
+    window: u32,
+) -> Vec<EventKind> {
+    let expiring: Vec<_> = bonds
+        .iter()
+        .filter(|bond| !bond.spent && bond.locktime > tip && bond.locktime - tip <= window)
+        .collect();
+    let events = expiring
+        .iter()
+        .filter(|bond| !reported.contains(&bond.index))
+        .map(|bond| EventKind::FidelityExpiring {
+            index: bond.index,
+            locktime: bond.locktime,
+            blocks_remaining: bond.locktime - tip,
+        })
+        .collect();
+    *reported = expiring.iter().map(|bond| bond.index).collect();
+    events
+}
+
+/// Events for the UTXOs in `current` (outpoint, amount, pool) not in `seen`, which is then
+/// replaced. Nothing is reported the first time a maker's UTXOs are seen.
+fn utxo_events(
+    seen: &mut Option<HashSet<String>>,
+    current: Vec<(String, u64, UtxoPool)>,
+) -> Vec<EventKind> {
+    let mut events = Vec::new();
+    if let Some(seen) = seen.as_ref() {
+        for (outpoint, amount, pool) in current.iter().filter(|(op, ..)| !seen.contains(op)) {
+            if *pool == UtxoPool::Swap {
+                events.push(EventKind::SwapCoinReceived {
+                    outpoint: outpoint.clone(),
+                    amount: *amount,
+                });
+            }
+            events.push(EventKind::NewUtxo {
+                outpoint: outpoint.clone(),
+                amount: *amount,
+                pool: format!("{pool:?}").to_lowercase(),
+            });
+        }
+    }
+    *seen = Some(current.into_iter().map(|(outpoint, ..)| outpoint).collect());
+    events
+}
+
+/// Events for swap reports (`swap_id`, status, fee, error) not in `seen`, which is then
+/// replaced. Nothing is reported the first time a maker's reports are read.
+fn swap_events(
+    seen: &mut Option<HashSet<String>>,
+    reports: Vec<(String, String, i64, Option<String>)>,
+) -> Vec<EventKind> {
+    let mut events = Vec::new();
+    if let Some(seen) = seen.as_ref() {
+        for (swap_id, status, fee, error) in reports.iter().filter(|r| !seen.contains(&r.0)) {
+            events.push(if is_successful(status) {
+                EventKind::SwapCompleted {
+                    swap_id: swap_id.clone(),
+                    fee: *fee,
+                }
+            } else {
+                EventKind::SwapFailed {
+                    swap_id: swap_id.clone(),
+                    error: error.clone(),
+                }
+            });
+        }
+    }
+    *seen = Some(reports.into_iter().map(|r| r.0).collect());
+    events
+}
+
+/// Spawns the task that publishes the events the dashboard doesn't cause itself: crashes,
+/// node and wallet changes, swaps and expiring bonds. It also locks frozen coins in Core
+/// again for makers it hasn't seen before and after their node restarts.
+pub fn spawn_watcher(state: AppState) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let bus = state.lock().await.events();
+        let mut nodes: HashMap<String, NodeState> = HashMap::new();
+        let mut makers: HashMap<String, MakerState> = HashMap::new();
+        let mut interval = tokio::time::interval(WATCH_INTERVAL);
+        loop {
+            interval.tick().await;
+            state.lock().await.reap_crashed();
+
+            let ids: Vec<String> = state
+                .lock()
+                .await
+                .list_makers()
+                .into_iter()
+                .cloned()
+                .collect();
+            makers.retain(|id, _| ids.contains(id));
+            let mut polled = HashSet::new();
+            // Nodes that came back this round; Core lost its coin locks
+            let mut restarted = HashSet::new();
+            for id in ids {
+                let Some(config) = state.lock().await.get_config(&id) else {
+                    continue;
+                };
+                if polled.insert(config.rpc.clone()) {
+                    let rpc = config.rpc.clone();
+                    let sample = sample_node(config.clone()).await;
+                    let now = NodeState {
+                        up: sample.up,
+                        height: sample.height,
+                    };
+                    if now.up && nodes.get(&rpc).is_some_and(|previous| !previous.up) {
+                        restarted.insert(rpc.clone());
+                    }
+                    for kind in node_events(&rpc, nodes.get(&rpc).copied(), now) {
+                        bus.publish(None, kind);
+                    }
+                    nodes.insert(rpc, now);
+                }
+
+                let tip = nodes.get(&config.rpc).and_then(|node| node.height);
+                let seen = makers.entry(id.clone()).or_default();
+                if restarted.contains(&config.rpc) {
+                    seen.frozen_locked = false;
+                }
+                for kind in watch_maker(&state, &id, config, tip, seen).await {
+                    bus.publish(Some(&id), kind);
+                }
+            }
+        }
+    })
+}
+
+/// Polls one maker's wallet, bonds and swap reports for changes since `seen`. Bonds are
+/// checked once per new `tip`.
+async fn watch_maker(
+    state: &AppState,
+    id: &String,
+    config: MakerConfig,
+    tip: Option<u64>,
+    seen: &mut MakerState,
+) -> Vec<EventKind> {
+    let mut events = Vec::new();
+
+    if let Some(data_dir) = config.data_directory {
+        let reports_id = id.clone();
+        let reports = tokio::task::spawn_blocking(move || {
+            swap_index::refresh(&data_dir.join("swap_reports"), &reports_id)
+        })
+        .await;
+        match reports {
+            Ok(Ok(reports)) => {
+                let reports = reports
+                    .iter()
+                    .map(|r| {
+                        (
+                            r.swap_id.clone(),
+                            r.status.clone(),
+                            r.fee_paid_or_earned,
+                            r.error_message.clone(),
+                        )
+                    })
+                    .collect();
+                events.extend(swap_events(&mut seen.swaps, reports));
+            }
+            Ok(Err((_, e))) => debug!("Skipping swap events for maker '{id}': {e}"),
+            Err(e) => debug!("Skipping swap events for maker '{id}': {e}"),
+        }
+    }
+
+    if state.lock().await.is_locked(id) {
+        return events;
+    }
+    if !seen.frozen_locked {
+        match reapply_frozen(state, id).await {
+            Ok(()) => seen.frozen_locked = true,
+            Err(e) => debug!("Could not re-apply UTXO locks for maker '{id}': {e}"),
+        }
+    }
+    let balances = state.lock().await.get_balances(id).await;
+    if let Ok(MessageResponse::TotalBalanceResp(b)) = balances {
+        let now = [
+            b.regular.to_sat(),
+            b.swap.to_sat(),
+            b.contract.to_sat(),
+            b.fidelity.to_sat(),
+            b.spendable.to_sat(),
+        ];
+        if seen.balance.is_some_and(|previous| previous != now) {
+            let [regular, swap, contract, fidelity, spendable] = now;
+            events.push(EventKind::BalanceChanged {
+                regular,
+                swap,
+                contract,
+                fidelity,
+                spendable,
+            });
+        }
+        seen.balance = Some(now);
+        events.extend(low_balance_event(
+            &mut seen.low_balance,
+            b.spendable.to_sat(),
+            config.min_swap_amount,
+        ));
+    }
+    let utxos = state.lock().await.get_utxos(id).await;
+    if let Ok(MessageResponse::UtxoResp { utxos }) = utxos {
+        let current = utxos
+            .into_iter()
+            .map(|u| (u.outpoint.to_string(), u.amount.to_sat(), u.pool))
+            .collect();
+        events.extend(utxo_events(&mut seen.utxos, current));
+    }
+
+    let Some(tip) = tip.filter(|tip| seen.bonds_checked_at != Some(*tip)) else {
+        return events;
+    };
+    let window = state
+        .lock()
+        .await
+        .renewal_policy(id)
+        .unwrap_or_default()
+        .blocks_before_expiry;
+    let listed = state.lock().await.list_fidelity(id).await;
+    if let Ok(MessageResponse::ListBonds(bonds)) = listed {
+        seen.bonds_checked_at = Some(tip);
+        events.extend(expiring_bond_events(
+            &mut seen.expiring,
+            &bonds,
+            tip as u32,
+            window,
+        ));
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use coinswap::bitcoin::{Amount, OutPoint};
+
+    use super::{
+        expiring_bond_events, low_balance_event, node_events, swap_events, utxo_events, NodeState,
+    };
+    use crate::maker_manager::{
+        events::EventKind,
+        message::{FidelityBondSummary, UtxoPool},
+    };
+
+    fn node(up: bool, height: Option<u64>) -> NodeState {
+        NodeState { up, height }
+    }
+
+    #[test]
+    fn node_transitions_and_new_blocks() {
+        assert!(node_events("n", None, node(true, Some(100))).is_empty());
+        assert_eq!(
+            node_events("n", None, node(false, None)),
+            [EventKind::BitcoindDown { rpc: "n".into() }]
+        );
+        assert_eq!(
+            node_events("n", Some(node(true, Some(100))), node(true, Some(101))),
+            [EventKind::NewBlock {
+                rpc: "n".into(),
+                height: 101
+            }]
+        );
+        assert_eq!(
+            node_events("n", Some(node(false, None)), node(true, Some(101))),
+            [EventKind::BitcoindUp { rpc: "n".into() }]
+        );
+        assert!(node_events("n", Some(node(false, None)), node(false, None)).is_empty());
+    }
+
+    #[test]
+    fn only_new_utxos_and_reports_are_reported() {
+        let mut seen = None;
+        let first = vec![("a:0".to_string(), 1_000, UtxoPool::Regular)];
+        assert!(utxo_events(&mut seen, first.clone()).is_empty());
+        let mut second = first;
+        second.push(("b:1".to_string(), 2_000, UtxoPool::Swap));
+        let events = utxo_events(&mut seen, second);
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[0],
+            EventKind::SwapCoinReceived {
+                outpoint: "b:1".into(),
+                amount: 2_000
+            }
+        );
+        assert!(matches!(&events[1], EventKind::NewUtxo { pool, .. } if pool == "swap"));
+
+        let mut seen = None;
+        let report = |id: &str, status: &str| (id.to_string(), status.to_string(), 10, None);
+        assert!(swap_events(&mut seen, vec![report("s1", "Success")]).is_empty());
+        let events = swap_events(
+            &mut seen,
+            vec![report("s1", "Success"), report("s2", "Failed")],
+        );
+        assert_eq!(
+            events,
+            [EventKind::SwapFailed {
+                swap_id: "s2".into(),
+                error: None
+            }]
+        );
+    }
+
+    #[test]
+    fn low_balance_is_reported_once_per_drop() {
+        let mut low = false;
+        assert_eq!(low_balance_event(&mut low, 20_000, 10_000), None);
+        assert_eq!(
+            low_balance_event(&mut low, 5_000, 10_000),
+            Some(EventKind::LowBalance {
+                spendable: 5_000,
+                threshold: 10_000
+            })
+        );
+        assert_eq!(low_balance_event(&mut low, 4_000, 10_000), None);
+        assert_eq!(low_balance_event(&mut low, 12_000, 10_000), None);
+        assert!(low_balance_event(&mut low, 9_000, 10_000).is_some());
+    }
+
+    #[test]
+    fn bonds_are_reported_once_when_entering_the_window() {
+        let bond = |index, locktime, spent| FidelityBondSummary {
+            index,
+            outpoint: OutPoint::null(),
+            amount: Amount::from_sat(50_000),
+            locktime,
+            conf_height: Some(100),
+            cert_expiry: None,
+            spent,
+            value: None,
+            advertised: index == 0,
+        };
+        let bonds = [
+            bond(0, 1_100, false),
+            bond(1, 5_000, false),
+            bond(2, 1_050, true),
+        ];
+        let mut reported = HashSet::new();
+        assert_eq!(
+            expiring_bond_events(&mut reported, &bonds, 1_000, 144),
+            [EventKind::FidelityExpiring {
+                index: 0,
+                locktime: 1_100,
+                blocks_remaining: 100
+            }]
+        );
+        assert!(expiring_bond_events(&mut reported, &bonds, 1_001, 144).is_empty());
+        // Expired bonds leave the window
+        assert!(expiring_bond_events(&mut reported, &bonds, 1_100, 144).is_empty());
+        assert!(reported.is_empty());
+    }
+}
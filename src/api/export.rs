@@ -0,0 +1,737 @@
+//! CSV exports of swap reports and wallet transactions for bookkeeping.
+//!
+//! Besides a plain CSV with every field, records can be rendered in the Koinly and
+//! CoinTracking import formats. Rows are streamed maker by maker, a few hundred at a time,
+//! so large histories never sit in memory as a whole file.
+
+use std::borrow::Cow;
+use std::io;
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    extract::{Query, State},
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use futures::{future, stream, Stream, StreamExt};
+use serde::Deserialize;
+use tracing::warn;
+
+use super::{
+    dto::{ApiResponse, ExportFormat, SwapReportDto, TransactionInfo, TxCategory},
+    swap_index::{self, ReportFilter},
+    transactions::{WalletHistory, WalletTxs},
+    AppState,
+};
+
+/// Rows rendered per chunk of the response body.
+const ROWS_PER_CHUNK: usize = 200;
+/// Wallet transactions described per blocking RPC batch.
+const TXS_PER_BATCH: usize = 50;
+const SATS_PER_BTC: u64 = 100_000_000;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/export/swap-reports", get(export_swap_reports))
+        .route("/export/transactions", get(export_transactions))
+}
+
+#[derive(Deserialize)]
+struct ExportQuery {
+    format: Option<ExportFormat>,
+    /// Only this maker; all makers if unset
+    maker: Option<String>,
+    from: Option<u64>,
+    to: Option<u64>,
+}
+
+type Chunk = Result<String, io::Error>;
+
+/// Export swap reports as CSV
+///
+/// Reports are filtered on their end time and listed newest first, maker by maker. The
+/// accounting formats book a maker's positive fee as income and a negative one (or, failing
+/// that, the mining fees paid) as a cost; reports without either are left out.
+#[utoipa::path(
+    get,
+    path = "/api/export/swap-reports",
+    tag = "monitoring",
+    params(
+        ("format" = Option<ExportFormat>, Query, description = "File layout (default csv)"),
+        ("maker" = Option<String>, Query, description = "Export only this maker (default all makers)"),
+        ("from" = Option<u64>, Query, description = "Earliest end time as a unix timestamp"),
+        ("to" = Option<u64>, Query, description = "Latest end time as a unix timestamp")
+    ),
+    responses(
+        (status = 200, description = "CSV file", content_type = "text/csv"),
+        (status = 400, description = "Invalid period", body = ApiResponse<String>),
+        (status = 404, description = "Maker not found", body = ApiResponse<String>)
+    )
+)]
+async fn export_swap_reports(
+    State(state): State<AppState>,
+    Query(query): Query<ExportQuery>,
+) -> Response {
+    let makers = match select_makers(&state, &query).await {
+        Ok(makers) => makers,
+        Err(response) => return response,
+    };
+    let format = query.format.unwrap_or(ExportFormat::Csv);
+    let filter = ReportFilter {
+        from: query.from,
+        to: query.to,
+        ..ReportFilter::default()
+    };
+
+    let rows = stream::iter(makers)
+        .then(move |id| {
+            let state = state.clone();
+            async move {
+                let config = state.lock().await.get_config(&id);
+                let Some(data_dir) = config.and_then(|config| config.data_directory) else {
+                    return (id, Ok(Vec::new()));
+                };
+                let reports_id = id.clone();
+                let reports = tokio::task::spawn_blocking(move || {
+                    swap_index::refresh(&data_dir.join("swap_reports"), &reports_id)
+                        .map_err(|(_, msg)| msg)
+                })
+                .await
+                .unwrap_or_else(|e| Err(e.to_string()));
+                (id, reports)
+            }
+        })
+        .flat_map(move |(id, reports)| match reports {
+            Ok(reports) => {
+                let reports: Vec<_> = reports.into_iter().filter(|r| filter.matches(r)).collect();
+                let chunks: Vec<Vec<Arc<SwapReportDto>>> =
+                    reports.chunks(ROWS_PER_CHUNK).map(<[_]>::to_vec).collect();
+                stream::iter(chunks.into_iter().map(move |chunk| {
+                    Ok(chunk
+                        .iter()
+                        .filter_map(|report| swap_report_row(format, &id, report))
+                        .collect::<String>())
+                }))
+                .left_stream()
+            }
+            Err(e) => stream::once(future::ready(export_failed(&id, e))).right_stream(),
+        });
+
+    csv_response("swap-reports", &query, swap_report_header(format), rows)
+}
+
+/// Export wallet transactions as CSV
+///
+/// Transactions are filtered on their block time (or, while unconfirmed, the time the
+/// wallet first saw them) and listed newest first, maker by maker. The accounting formats
+/// book deposits and withdrawals as such; swap, fidelity, sweep and recovery transactions
+/// only move coins between the maker's own outputs and contracts, so just the fee the wallet
+/// paid for them is booked as a cost.
+#[utoipa::path(
+    get,
+    path = "/api/export/transactions",
+    tag = "wallet",
+    params(
+        ("format" = Option<ExportFormat>, Query, description = "File layout (default csv)"),
+        ("maker" = Option<String>, Query, description = "Export only this maker (default all makers)"),
+        ("from" = Option<u64>, Query, description = "Earliest transaction time as a unix timestamp"),
+        ("to" = Option<u64>, Query, description = "Latest transaction time as a unix timestamp")
+    ),
+    responses(
+        (status = 200, description = "CSV file", content_type = "text/csv"),
+        (status = 400, description = "Invalid period", body = ApiResponse<String>),
+        (status = 404, description = "Maker not found", body = ApiResponse<String>),
+        (status = 500, description = "Failed to query a maker's wallet", body = ApiResponse<String>)
+    )
+)]
+async fn export_transactions(
+    State(state): State<AppState>,
+    Query(query): Query<ExportQuery>,
+) -> Response {
+    let makers = match select_makers(&state, &query).await {
+        Ok(makers) => makers,
+        Err(response) => return response,
+    };
+    // Fail before the response starts if a maker can't tell its transactions apart
+    let mut histories = Vec::with_capacity(makers.len());
+    for id in makers {
+        match WalletHistory::load(&state, &id).await {
+            Some(Ok(history)) => histories.push((id, history)),
+            Some(Err(e)) => {
+                return error_response(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("Failed to read transactions of maker '{id}': {e}"),
+                )
+            }
+            // Deleted since it was listed
+            None => continue,
+        }
+    }
+    let format = query.format.unwrap_or(ExportFormat::Csv);
+    let (from, to) = (query.from, query.to);
+
+    let rows = stream::iter(histories)
+        .then(|(id, history)| async move {
+            let wallet = tokio::task::spawn_blocking(move || history.open())
+                .await
+                .unwrap_or_else(|e| Err(e.to_string()));
+            (id, wallet)
+        })
+        .flat_map(move |(id, wallet)| match wallet {
+            Ok(mut wallet) => {
+                wallet.txs.retain(|info| {
+                    let at = info.blocktime.unwrap_or(info.time);
+                    from.is_none_or(|from| at >= from) && to.is_none_or(|to| at <= to)
+                });
+                transaction_rows(format, id, wallet).left_stream()
+            }
+            Err(e) => stream::once(future::ready(export_failed(&id, e))).right_stream(),
+        });
+
+    csv_response("transactions", &query, transaction_header(format), rows)
+}
+
+/// Describes `wallet`'s transactions in batches off the async runtime and renders them.
+fn transaction_rows(
+    format: ExportFormat,
+    id: String,
+    wallet: WalletTxs,
+) -> impl Stream<Item = Chunk> + Send {
+    let batches: Vec<std::ops::Range<usize>> = (0..wallet.txs.len())
+        .step_by(TXS_PER_BATCH)
+        .map(|start| start..(start + TXS_PER_BATCH).min(wallet.txs.len()))
+        .collect();
+    let wallet = Arc::new(wallet);
+    let id = Arc::new(id);
+    stream::iter(batches)
+        .then(move |batch| {
+            let wallet = wallet.clone();
+            let id = id.clone();
+            async move {
+                let described = tokio::task::spawn_blocking(move || {
+                    wallet.txs[batch]
+                        .iter()
+                        .map(|info| wallet.describe(info))
+                        .collect::<Result<Vec<_>, String>>()
+                })
+                .await
+                .unwrap_or_else(|e| Err(e.to_string()));
+                match described {
+                    Ok(txs) => Ok(txs
+                        .iter()
+                        .filter_map(|tx| transaction_row(format, &id, tx))
+                        .collect()),
+                    Err(e) => export_failed(&id, e),
+                }
+            }
+        })
+        // Stop at the first failure; the client sees a truncated download
+        .scan(false, |failed, chunk| {
+            let emit = !*failed;
+            *failed |= chunk.is_err();
+            future::ready(emit.then_some(chunk))
+        })
+}
+
+/// The makers to export, or an error response if the query is invalid.
+async fn select_makers(state: &AppState, query: &ExportQuery) -> Result<Vec<String>, Response> {
+    if let (Some(from), Some(to)) = (query.from, query.to) {
+        if from > to {
+            return Err(error_response(
+                StatusCode::BAD_REQUEST,
+                "from must not be after to".to_string(),
+            ));
+        }
+    }
+    let mgr = state.lock().await;
+    match &query.maker {
+        Some(id) if mgr.has_maker(id) => Ok(vec![id.clone()]),
+        Some(id) => Err(error_response(
+            StatusCode::NOT_FOUND,
+            format!("Maker '{id}' not found"),
+        )),
+        None => {
+            let mut makers: Vec<String> = mgr.list_makers().into_iter().cloned().collect();
+            makers.sort();
+            Ok(makers)
+        }
+    }
+}
+
+fn error_response(status: StatusCode, msg: String) -> Response {
+    (status, Json(ApiResponse::<()>::err(msg))).into_response()
+}
+
+/// Ends the body with an error once the response has started.
+fn export_failed(id: &str, e: String) -> Chunk {
+    warn!("Export aborted for maker '{id}': {e}");
+    Err(io::Error::other(format!("maker '{id}': {e}")))
+}
+
+fn csv_response(
+    kind: &str,
+    query: &ExportQuery,
+    header_row: String,
+    rows: impl Stream<Item = Chunk> + Send + 'static,
+) -> Response {
+    let format = match query.format.unwrap_or(ExportFormat::Csv) {
+        ExportFormat::Csv => "",
+        ExportFormat::Koinly => "-koinly",
+        ExportFormat::Cointracking => "-cointracking",
+    };
+    // Sanitize the maker ID so it can't inject characters into the header value.
+    let scope = match &query.maker {
+        Some(id) => {
+            let safe_id: String = id
+                .chars()
+                .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+                .collect();
+            format!("maker-{safe_id}")
+        }
+        None => "makers".to_string(),
+    };
+    let disposition = format!("attachment; filename=\"{scope}-{kind}{format}.csv\"");
+    let headers = [
+        (
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("text/csv; charset=utf-8"),
+        ),
+        (
+            header::CONTENT_DISPOSITION,
+            HeaderValue::from_str(&disposition)
+                .unwrap_or_else(|_| HeaderValue::from_static("attachment")),
+        ),
+    ];
+    let body = stream::once(future::ready(Ok(header_row))).chain(rows);
+    (StatusCode::OK, headers, Body::from_stream(body)).into_response()
+}
+
+/// How a record is booked by accounting tools.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EntryKind {
+    Income,
+    Cost,
+    Deposit,
+    Withdrawal,
+}
+
+/// A record reduced to what accounting tools import, amounts in sats.
+#[derive(Debug, PartialEq, Eq)]
+struct LedgerEntry<'a> {
+    time: u64,
+    kind: EntryKind,
+    sent: u64,
+    received: u64,
+    fee: u64,
+    description: String,
+    txid: Option<&'a str>,
+}
+
+fn swap_entry(report: &SwapReportDto) -> Option<LedgerEntry<'_>> {
+    let earned = report.fee_paid_or_earned;
+    let (kind, sent, received, fee) = if earned > 0 {
+        (EntryKind::Income, 0, earned as u64, report.mining_fee)
+    } else if earned < 0 {
+        (EntryKind::Cost, earned.unsigned_abs(), 0, report.mining_fee)
+    } else if report.mining_fee > 0 {
+        (EntryKind::Cost, report.mining_fee, 0, 0)
+    } else {
+        return None;
+    };
+    Some(LedgerEntry {
+        time: report.end_timestamp,
+        kind,
+        sent,
+        received,
+        fee,
+        description: format!(
+            "Coinswap {} swap {} ({})",
+            report.role, report.swap_id, report.status
+        ),
+        txid: report
+            .outgoing_contract_txid
+            .as_deref()
+            .or(report.incoming_contract_txid.as_deref()),
+    })
+}
+
+fn transaction_entry(tx: &TransactionInfo) -> Option<LedgerEntry<'_>> {
+    let fee = tx.fee.unwrap_or(0);
+    let (kind, sent, received, fee) = match tx.category {
+        TxCategory::Deposit => (EntryKind::Deposit, 0, tx.amount.max(0) as u64, 0),
+        // `amount` includes the fee
+        TxCategory::Withdrawal => (
+            EntryKind::Withdrawal,
+            tx.amount.unsigned_abs().saturating_sub(fee),
+            0,
+            fee,
+        ),
+        _ if fee > 0 => (EntryKind::Cost, fee, 0, 0),
+        _ => return None,
+    };
+    let category = category_name(tx.category);
+    let description = match &tx.label {
+        Some(label) => format!("{category}: {label}"),
+        None => category,
+    };
+    Some(LedgerEntry {
+        time: tx.block_time.unwrap_or(tx.time),
+        kind,
+        sent,
+        received,
+        fee,
+        description,
+        txid: Some(&tx.txid),
+    })
+}
+
+/// The category as the API spells it, e.g. `swap-in`.
+fn category_name(category: TxCategory) -> String {
+    serde_json::to_value(category)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn swap_report_header(format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Csv => "maker,swap_id,role,status,start_time,end_time,start_timestamp,\
+            end_timestamp,incoming_sats,incoming_btc,outgoing_sats,outgoing_btc,\
+            fee_earned_sats,fee_earned_btc,mining_fee_sats,mining_fee_btc,\
+            incoming_contract_txid,outgoing_contract_txid,error_message\n"
+            .to_string(),
+        ExportFormat::Koinly | ExportFormat::Cointracking => ledger_header(format),
+    }
+}
+
+fn swap_report_row(format: ExportFormat, maker: &str, report: &SwapReportDto) -> Option<String> {
+    match format {
+        ExportFormat::Csv => Some(csv_row(&[
+            &text(maker),
+            &text(&report.swap_id),
+            &text(&report.role),
+            &text(&report.status),
+            &iso_time(report.start_timestamp),
+            &iso_time(report.end_timestamp),
+            &report.start_timestamp.to_string(),
+            &report.end_timestamp.to_string(),
+            &report.incoming_amount.to_string(),
+            &btc(report.incoming_amount as i64),
+            &report.outgoing_amount.to_string(),
+            &btc(report.outgoing_amount as i64),
+            &report.fee_paid_or_earned.to_string(),
+            &btc(report.fee_paid_or_earned),
+            &report.mining_fee.to_string(),
+            &btc(report.mining_fee as i64),
+            report.incoming_contract_txid.as_deref().unwrap_or(""),
+            report.outgoing_contract_txid.as_deref().unwrap_or(""),
+            &text(report.error_message.as_deref().unwrap_or("")),
+        ])),
+        _ => swap_entry(report).map(|entry| ledger_row(format, maker, &entry)),
+    }
+}
+
+fn transaction_header(format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Csv => "maker,txid,category,time,timestamp,amount_sats,amount_btc,\
+            fee_sats,fee_btc,confirmations,block_height,label\n"
+            .to_string(),
+        ExportFormat::Koinly | ExportFormat::Cointracking => ledger_header(format),
+    }
+}
+
+fn transaction_row(format: ExportFormat, maker: &str, tx: &TransactionInfo) -> Option<String> {
+    match format {
+        ExportFormat::Csv => {
+            let at = tx.block_time.unwrap_or(tx.time);
+            Some(csv_row(&[
+                &text(maker),
+                &tx.txid,
+                &category_name(tx.category),
+                &iso_time(at),
+                &at.to_string(),
+                &tx.amount.to_string(),
+                &btc(tx.amount),
+                &tx.fee.map(|fee| fee.to_string()).unwrap_or_default(),
+                &tx.fee.map(|fee| btc(fee as i64)).unwrap_or_default(),
+                &tx.confirmations.to_string(),
+                &tx.block_height.map(|h| h.to_string()).unwrap_or_default(),
+                &text(tx.label.as_deref().unwrap_or("")),
+            ]))
+        }
+        _ => transaction_entry(tx).map(|entry| ledger_row(format, maker, &entry)),
+    }
+}
+
+fn ledger_header(format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Koinly => "Date,Sent Amount,Sent Currency,Received Amount,\
+            Received Currency,Fee Amount,Fee Currency,Net Worth Amount,Net Worth Currency,\
+            Label,Description,TxHash\n"
+            .to_string(),
+        _ => "Type,Buy Amount,Buy Currency,Sell Amount,Sell Currency,Fee,Fee Currency,\
+            Exchange,Trade-Group,Comment,Date,Tx-ID\n"
+            .to_string(),
+    }
+}
+
+fn ledger_row(format: ExportFormat, maker: &str, entry: &LedgerEntry) -> String {
+    let amount = |sats: u64| {
+        if sats > 0 {
+            (btc(sats as i64), "BTC")
+        } else {
+            (String::new(), "")
+        }
+    };
+    let (sent, sent_currency) = amount(entry.sent);
+    let (received, received_currency) = amount(entry.received);
+    let (fee, fee_currency) = amount(entry.fee);
+    let txid = entry.txid.unwrap_or("");
+    match format {
+        ExportFormat::Koinly => {
+            let label = match entry.kind {
+                EntryKind::Income => "income",
+                EntryKind::Cost => "cost",
+                EntryKind::Deposit | EntryKind::Withdrawal => "",
+            };
+            csv_row(&[
+                &utc_time(entry.time),
+                &sent,
+                sent_currency,
+                &received,
+                received_currency,
+                &fee,
+                fee_currency,
+                "",
+                "",
+                label,
+                &text(&entry.description),
+                txid,
+            ])
+        }
+        _ => {
+            let kind = match entry.kind {
+                EntryKind::Income => "Income",
+                EntryKind::Cost => "Other Fee",
+                EntryKind::Deposit => "Deposit",
+                EntryKind::Withdrawal => "Withdrawal",
+            };
+            csv_row(&[
+                kind,
+                &received,
+                received_currency,
+                &sent,
+                sent_currency,
+                &fee,
+                fee_currency,
+                &format!("Coinswap maker {maker}"),
+                &text(maker),
+                &text(&entry.description),
+                &utc_time(entry.time),
+                txid,
+            ])
+        }
+    }
+}
+
+/// Joins `fields` into one CSV line, quoting fields that need it (RFC 4180).
+fn csv_row(fields: &[&str]) -> String {
+    let mut line = String::new();
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            line.push(',');
+        }
+        if field.contains([',', '"', '\n', '\r']) {
+            line.push('"');
+            line.push_str(&field.replace('"', "\"\""));
+            line.push('"');
+        } else {
+            line.push_str(field);
+        }
+    }
+    line.push('\n');
+    line
+}
+
+/// Prefixes a text field that a spreadsheet would evaluate as a formula with `'`, so
+/// labels and error messages can't inject one. Amount columns don't go through this, as
+/// their minus sign must stay.
+fn text(field: &str) -> Cow<'_, str> {
+    if field.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        Cow::Owned(format!("'{field}"))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+/// `sats` as a BTC amount with eight decimals.
+fn btc(sats: i64) -> String {
+    let sign = if sats < 0 { "-" } else { "" };
+    let abs = sats.unsigned_abs();
+    format!("{sign}{}.{:08}", abs / SATS_PER_BTC, abs % SATS_PER_BTC)
+}
+
+/// Calendar date and time of a unix timestamp in UTC.
+fn civil(secs: u64) -> (i64, u64, u64, u64, u64, u64) {
+    let (days, rem) = ((secs / 86_400) as i64, secs % 86_400);
+    // Howard Hinnant's civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097) as u64;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe as i64 + era * 400 + i64::from(month <= 2);
+    (year, month, day, rem / 3_600, rem % 3_600 / 60, rem % 60)
+}
+
+/// ISO 8601, e.g. `2024-03-01T12:00:00Z`.
+fn iso_time(secs: u64) -> String {
+    let (y, mo, d, h, mi, s) = civil(secs);
+    format!("{y:04}-{mo:02}-{d:02}T{h:02}:{mi:02}:{s:02}Z")
+}
+
+/// The `YYYY-MM-DD HH:MM:SS` UTC form both import formats accept.
+fn utc_time(secs: u64) -> String {
+    let (y, mo, d, h, mi, s) = civil(secs);
+    format!("{y:04}-{mo:02}-{d:02} {h:02}:{mi:02}:{s:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        btc, csv_row, iso_time, swap_entry, swap_report_header, swap_report_row, text,
+        transaction_entry, transaction_row, utc_time, EntryKind,
+    };
+    use crate::api::dto::{ExportFormat, SwapReportDto, TransactionInfo, TxCategory};
+
+    fn report(earned: i64, mining_fee: u64) -> SwapReportDto {
+        serde_json::from_value(serde_json::json!({
+            "swap_id": "s1",
+            "role": "Maker",
+            "status": "Success",
+            "swap_duration_seconds": 60.0,
+            "start_timestamp": 1_709_294_340u64,
+            "end_timestamp": 1_709_294_400u64,
+            "network": "regtest",
+            "incoming_amount": 1_000_000,
+            "outgoing_amount": 998_000,
+            "fee_paid_or_earned": earned,
+            "incoming_contract_txid": "aa",
+            "outgoing_contract_txid": "bb",
+            "error_message": "a, \"quoted\" error",
+            "timelock": 20,
+            "total_maker_fees": 0,
+            "mining_fee": mining_fee,
+            "fee_percentage": 0.0,
+            "input_utxos": []
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn formats_amounts_and_times() {
+        assert_eq!(btc(123_456_789), "1.23456789");
+        assert_eq!(btc(-1_500), "-0.00001500");
+        assert_eq!(iso_time(0), "1970-01-01T00:00:00Z");
+        assert_eq!(utc_time(1_709_294_400), "2024-03-01 12:00:00");
+        assert_eq!(iso_time(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(csv_row(&["a", "b,c", "d\"e"]), "a,\"b,c\",\"d\"\"e\"\n");
+    }
+
+    #[test]
+    fn swap_reports_book_fees_as_income_or_cost() {
+        let income = report(2_000, 300);
+        let entry = swap_entry(&income).unwrap();
+        assert_eq!(entry.kind, EntryKind::Income);
+        assert_eq!((entry.received, entry.fee), (2_000, 300));
+        assert_eq!(entry.txid, Some("bb"));
+        assert_eq!(
+            swap_report_row(ExportFormat::Koinly, "m1", &income).unwrap(),
+            "2024-03-01 12:00:00,,,0.00002000,BTC,0.00000300,BTC,,,income,\
+             Coinswap Maker swap s1 (Success),bb\n"
+        );
+        assert_eq!(
+            swap_report_row(ExportFormat::Cointracking, "m1", &income).unwrap(),
+            "Income,0.00002000,BTC,,,0.00000300,BTC,Coinswap maker m1,m1,\
+             Coinswap Maker swap s1 (Success),2024-03-01 12:00:00,bb\n"
+        );
+
+        let failed = report(0, 500);
+        let entry = swap_entry(&failed).unwrap();
+        assert_eq!(
+            (entry.kind, entry.sent, entry.fee),
+            (EntryKind::Cost, 500, 0)
+        );
+        assert!(swap_entry(&report(0, 0)).is_none());
+
+        let row = swap_report_row(ExportFormat::Csv, "m1", &income).unwrap();
+        assert!(row.starts_with("m1,s1,Maker,Success,2024-03-01T11:59:00Z,"));
+        assert!(row.ends_with(",aa,bb,\"a, \"\"quoted\"\" error\"\n"));
+        assert_eq!(
+            row.split(',').count(),
+            swap_report_header(ExportFormat::Csv).split(',').count() + 1
+        );
+    }
+
+    #[test]
+    fn internal_transactions_only_book_their_fee() {
+        let tx = |category, amount, fee| TransactionInfo {
+            txid: "cc".into(),
+            category,
+            amount,
+            fee,
+            confirmations: 1,
+            block_height: Some(100),
+            block_time: Some(1_709_294_400),
+            time: 1_709_294_000,
+            label: None,
+        };
+        let withdrawal = tx(TxCategory::Withdrawal, -10_200, Some(200));
+        let entry = transaction_entry(&withdrawal).unwrap();
+        assert_eq!((entry.sent, entry.fee), (10_000, 200));
+        let deposit = tx(TxCategory::Deposit, 5_000, None);
+        assert_eq!(transaction_entry(&deposit).unwrap().received, 5_000);
+        let sweep = tx(TxCategory::Sweep, -300, Some(300));
+        let entry = transaction_entry(&sweep).unwrap();
+        assert_eq!((entry.kind, entry.sent), (EntryKind::Cost, 300));
+        assert!(transaction_entry(&tx(TxCategory::SwapIn, 50_000, None)).is_none());
+
+        assert_eq!(
+            transaction_row(ExportFormat::Csv, "m1", &withdrawal).unwrap(),
+            "m1,cc,withdrawal,2024-03-01T12:00:00Z,1709294400,-10200,-0.00010200,200,\
+             0.00000200,1,100,\n"
+        );
+    }
+
+    #[test]
+    fn formula_like_text_is_neutralized() {
+        for formula in ["=1+1", "+1", "-1", "@SUM(A1)", "\tx", "\rx"] {
+            assert_eq!(text(formula), format!("'{formula}"));
+        }
+        assert_eq!(text("plain"), "plain");
+
+        let tx = TransactionInfo {
+            txid: "cc".into(),
+            category: TxCategory::Withdrawal,
+            amount: -10_200,
+            fee: Some(200),
+            confirmations: 1,
+            block_height: Some(100),
+            block_time: Some(1_709_294_400),
+            time: 1_709_294_000,
+            label: Some("=HYPERLINK(\"x\")".into()),
+        };
+        // Negative amounts keep their sign; only the label is prefixed
+        assert_eq!(
+            transaction_row(ExportFormat::Csv, "m1", &tx).unwrap(),
+            "m1,cc,withdrawal,2024-03-01T12:00:00Z,1709294400,-10200,-0.00010200,200,\
+             0.00000200,1,100,\"'=HYPERLINK(\"\"x\"\")\"\n"
+        );
+    }
+}
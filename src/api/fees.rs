@@ -0,0 +1,210 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    routing::get,
+    Json, Router,
+};
+use coinswap::bitcoind::bitcoincore_rpc::{Client, RpcApi};
+use serde::Deserialize;
+
+use crate::maker_manager::MakerConfig;
+
+use super::{
+    dto::{ApiResponse, FeeEstimateInfo, FeeEstimatesInfo, FeePreset, FeeTarget},
+    AppState,
+};
+
+/// Highest feerate (sat/vB) accepted for any dashboard-built transaction.
+pub const MAX_FEERATE: f64 = 1000.0;
+/// Floor applied to bitcoind estimates (sat/vB).
+const MIN_ESTIMATED_FEERATE: f64 = 1.0;
+/// Confirmation targets reported by `GET /api/fees`.
+const ESTIMATE_TARGETS: [u16; 7] = [1, 2, 3, 6, 12, 24, 144];
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/fees", get(get_fees))
+}
+
+#[derive(Deserialize)]
+struct FeesQuery {
+    maker: Option<String>,
+}
+
+/// Get feerate estimates from bitcoind for common confirmation targets.
+/// Uses the RPC config of the given maker, or the first reachable registered maker.
+#[utoipa::path(
+    get,
+    path = "/api/fees",
+    tag = "wallet",
+    params(("maker" = Option<String>, Query, description = "Maker whose RPC config is used")),
+    responses(
+        (status = 200, description = "Feerate estimates in sat/vB", body = ApiResponse<FeeEstimatesInfo>),
+        (status = 404, description = "Maker not found", body = ApiResponse<FeeEstimatesInfo>),
+        (status = 503, description = "No reachable bitcoind", body = ApiResponse<FeeEstimatesInfo>)
+    )
+)]
+async fn get_fees(
+    State(state): State<AppState>,
+    Query(query): Query<FeesQuery>,
+) -> (StatusCode, Json<ApiResponse<FeeEstimatesInfo>>) {
+    // Collect maker configs (drop lock before blocking work)
+    let configs: Vec<MakerConfig> = {
+        let mgr = state.lock().await;
+        match query.maker {
+            Some(id) => match mgr.get_config(&id) {
+                Some(config) => vec![config],
+                None => {
+                    return (
+                        StatusCode::NOT_FOUND,
+                        Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+                    )
+                }
+            },
+            None => mgr
+                .list_makers()
+                .into_iter()
+                .filter_map(|id| mgr.get_config(id))
+                .collect(),
+        }
+    };
+
+    let result = tokio::task::spawn_blocking(move || {
+        configs.iter().find_map(|config| {
+            let client = config.rpc_client().ok()?;
+            // Skip nodes that are down instead of reporting every target as missing
+            client.get_block_count().ok()?;
+            Some(fee_estimates(&client))
+        })
+    })
+    .await;
+
+    match result {
+        Ok(Some(info)) => (StatusCode::OK, Json(ApiResponse::ok(info))),
+        Ok(None) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ApiResponse::err(
+                "No reachable Bitcoin Core RPC among registered makers",
+            )),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::err(e.to_string())),
+        ),
+    }
+}
+
+fn fee_estimates(client: &Client) -> FeeEstimatesInfo {
+    let estimate = |target| estimate_feerate(client, target).ok();
+    FeeEstimatesInfo {
+        estimates: ESTIMATE_TARGETS
+            .iter()
+            .map(|&target_blocks| FeeEstimateInfo {
+                target_blocks,
+                feerate: estimate(target_blocks),
+            })
+            .collect(),
+        fast: estimate(FeePreset::Fast.target_blocks()),
+        normal: estimate(FeePreset::Normal.target_blocks()),
+        economy: estimate(FeePreset::Economy.target_blocks()),
+        max_feerate: MAX_FEERATE,
+    }
+}
+
+/// Queries `estimatesmartfee` and converts the result from BTC/kvB to sat/vB.
+pub(crate) fn estimate_feerate(client: &Client, target_blocks: u16) -> Result<f64, String> {
+    let result = client
+        .estimate_smart_fee(target_blocks, None)
+        .map_err(|e| format!("estimatesmartfee failed: {e}"))?;
+    match result.fee_rate {
+        Some(rate) => Ok((rate.to_sat() as f64 / 1000.0).max(MIN_ESTIMATED_FEERATE)),
+        None => Err(format!(
+            "No fee estimate available for {target_blocks} blocks: {}",
+            result
+                .errors
+                .map(|errors| errors.join("; "))
+                .unwrap_or_else(|| "unknown reason".to_string())
+        )),
+    }
+}
+
+/// Rejects non-positive, non-finite and absurdly high feerates.
+pub(crate) fn validate_feerate(feerate: f64) -> Result<f64, String> {
+    if !feerate.is_finite() || feerate <= 0.0 {
+        return Err(format!("feerate must be a positive number, got {feerate}"));
+    }
+    if feerate > MAX_FEERATE {
+        return Err(format!(
+            "feerate {feerate} sat/vB exceeds the sanity cap of {MAX_FEERATE} sat/vB"
+        ));
+    }
+    Ok(feerate)
+}
+
+/// Resolves the feerate for a transaction from either an explicit value or a confirmation
+/// target, estimating the latter through the maker's RPC.
+pub(crate) async fn resolve_feerate(
+    config: MakerConfig,
+    feerate: Option<f64>,
+    fee_target: Option<FeeTarget>,
+) -> Result<f64, (StatusCode, String)> {
+    let feerate = match (feerate, fee_target) {
+        (Some(feerate), None) => feerate,
+        (None, Some(target)) => {
+            let target_blocks = target.target_blocks();
+            if target_blocks == 0 {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    "fee_target must be at least 1 block".to_string(),
+                ));
+            }
+            tokio::task::spawn_blocking(move || {
+                let client = config.rpc_client().map_err(|e| e.to_string())?;
+                estimate_feerate(&client, target_blocks)
+            })
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            .map_err(|e| (StatusCode::SERVICE_UNAVAILABLE, e))?
+        }
+        (Some(_), Some(_)) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "Specify either feerate or fee_target, not both".to_string(),
+            ))
+        }
+        (None, None) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "Either feerate or fee_target must be provided".to_string(),
+            ))
+        }
+    };
+    validate_feerate(feerate).map_err(|e| (StatusCode::BAD_REQUEST, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{validate_feerate, MAX_FEERATE};
+    use crate::api::dto::{FeePreset, FeeTarget};
+
+    #[test]
+    fn fee_target_accepts_presets_and_block_counts() {
+        let fast: FeeTarget = serde_json::from_str("\"fast\"").unwrap();
+        assert_eq!(fast, FeeTarget::Preset(FeePreset::Fast));
+        assert_eq!(fast.target_blocks(), 2);
+
+        let blocks: FeeTarget = serde_json::from_str("12").unwrap();
+        assert_eq!(blocks, FeeTarget::Blocks(12));
+        assert_eq!(blocks.target_blocks(), 12);
+
+        assert!(serde_json::from_str::<FeeTarget>("\"turbo\"").is_err());
+    }
+
+    #[test]
+    fn validate_feerate_enforces_sanity_cap() {
+        assert_eq!(validate_feerate(2.5), Ok(2.5));
+        assert_eq!(validate_feerate(MAX_FEERATE), Ok(MAX_FEERATE));
+        assert!(validate_feerate(MAX_FEERATE + 1.0).is_err());
+        assert!(validate_feerate(0.0).is_err());
+        assert!(validate_feerate(f64::NAN).is_err());
+    }
+}
@@ -1,41 +1,317 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
-    routing::get,
+    routing::{get, post},
     Json, Router,
 };
+use coinswap::bitcoind::bitcoincore_rpc::RpcApi;
+use serde::Deserialize;
+use tracing::warn;
 
-use super::{dto::ApiResponse, AppState};
-use crate::maker_manager::message::MessageResponse;
+use super::{
+    dto::{
+        AdvertisedBondInfo, ApiResponse, BondValueEstimate, CreateFidelityRequest, FeePreset,
+        FeeTarget, FidelityBondDto, FidelityFundingInfo, RedeemFidelityRequest,
+    },
+    fees,
+    makers::FIDELITY_TIMELOCK_RANGE,
+    AppState,
+};
+use crate::maker_manager::{
+    message::{FidelityBondSummary, FidelityRedeemError, MessageResponse},
+    MakerConfig,
+};
+
+/// Average block interval used to estimate time to expiry.
+const SECS_PER_BLOCK: u64 = 600;
+/// Interest rate and exponent of the bond value formula, as used by coinswap takers. coinswap
+/// only exposes the formula as `Wallet::calculate_bond_value`, which scores a bond the wallet
+/// already holds, so what-if values for an amount and timelock are computed here;
+/// `bond_value_matches_coinswap` pins these constants against known values.
+const BOND_VALUE_INTEREST_RATE: f64 = 0.01;
+const BOND_VALUE_EXPONENT: f64 = 1.3;
+const SECS_PER_YEAR: f64 = 60.0 * 60.0 * 24.0 * 365.2425;
 
 pub fn routes() -> Router<AppState> {
-    Router::new().route("/makers/{id}/fidelity", get(list_fidelity))
+    Router::new()
+        .route(
+            "/makers/{id}/fidelity",
+            get(list_fidelity).post(create_fidelity),
+        )
+        .route(
+            "/makers/{id}/fidelity/{index}/redeem",
+            post(redeem_fidelity),
+        )
+        .route("/fidelity/value", get(estimate_bond_value))
+}
+
+/// Resolves the feerate of a bond transaction, falling back to the `normal` fee target.
+pub(crate) async fn feerate_or_normal(
+    config: MakerConfig,
+    feerate: Option<f64>,
+    fee_target: Option<FeeTarget>,
+) -> Result<f64, (StatusCode, String)> {
+    let fee_target = match (feerate, fee_target) {
+        (None, None) => Some(FeeTarget::Preset(FeePreset::Normal)),
+        (_, fee_target) => fee_target,
+    };
+    fees::resolve_feerate(config, feerate, fee_target).await
+}
+
+/// Current block height of the maker's node.
+pub(crate) async fn chain_tip(config: MakerConfig) -> Result<u32, (StatusCode, String)> {
+    let tip = tokio::task::spawn_blocking(move || {
+        config
+            .rpc_client()
+            .and_then(|client| Ok(client.get_block_count()?))
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+    .map_err(|e| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            format!("Bitcoin Core unreachable: {e}"),
+        )
+    })?;
+    u32::try_from(tip).map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Block height {tip} out of range"),
+        )
+    })
 }
 
 /// List all fidelity bonds for a maker
+///
+/// Expiry fields are computed against the node's tip and left out when it is unreachable.
 #[utoipa::path(
     get,
     path = "/api/makers/{id}/fidelity",
     tag = "fidelity",
     params(("id" = String, Path, description = "Maker ID")),
     responses(
-        (status = 200, description = "List of fidelity bonds", body = ApiResponse<String>),
-        (status = 404, description = "Maker not found", body = ApiResponse<String>),
-        (status = 500, description = "Internal error", body = ApiResponse<String>)
+        (status = 200, description = "List of fidelity bonds", body = ApiResponse<Vec<FidelityBondDto>>),
+        (status = 404, description = "Maker not found", body = ApiResponse<Vec<FidelityBondDto>>),
+        (status = 500, description = "Internal error", body = ApiResponse<Vec<FidelityBondDto>>)
     )
 )]
 async fn list_fidelity(
     State(state): State<AppState>,
     Path(id): Path<String>,
-) -> (StatusCode, Json<ApiResponse<String>>) {
-    if !state.lock().await.has_maker(&id) {
+) -> (StatusCode, Json<ApiResponse<Vec<FidelityBondDto>>>) {
+    let Some(config) = state.lock().await.get_config(&id) else {
         return (
             StatusCode::NOT_FOUND,
             Json(ApiResponse::err(format!("Maker '{id}' not found"))),
         );
+    };
+    let listed = state.lock().await.list_fidelity(&id).await;
+    match listed {
+        Ok(MessageResponse::ListBonds(bonds)) => {
+            let tip = match chain_tip(config).await {
+                Ok(tip) => Some(tip),
+                Err((_, e)) => {
+                    warn!("Fidelity bond expiry for maker '{id}' unavailable: {e}");
+                    None
+                }
+            };
+            let bonds = bonds.into_iter().map(|bond| bond_dto(bond, tip)).collect();
+            (StatusCode::OK, Json(ApiResponse::ok(bonds)))
+        }
+        Ok(MessageResponse::ServerError(e)) => {
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::err(e)))
+        }
+        Ok(other) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::err(format!("Unexpected response: {other}"))),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::err(e.to_string())),
+        ),
+    }
+}
+
+/// Converts a wallet bond, computing its expiry against `tip` when known.
+fn bond_dto(bond: FidelityBondSummary, tip: Option<u32>) -> FidelityBondDto {
+    let blocks_to_expiry = tip.map(|tip| bond.locktime.saturating_sub(tip));
+    FidelityBondDto {
+        index: bond.index,
+        outpoint: bond.outpoint.to_string(),
+        amount: bond.amount.to_sat(),
+        locktime: bond.locktime,
+        conf_height: bond.conf_height,
+        cert_expiry: bond.cert_expiry,
+        bond_value: bond.value.map(|value| value.to_sat()),
+        advertised: bond.advertised,
+        spent: bond.spent,
+        expired: blocks_to_expiry.map(|blocks| blocks == 0),
+        blocks_to_expiry,
+        secs_to_expiry: blocks_to_expiry.map(|blocks| u64::from(blocks) * SECS_PER_BLOCK),
+    }
+}
+
+/// Create a fidelity bond for a maker
+///
+/// Amount and timelock default to the maker's `fidelity_amount` and `fidelity_timelock`.
+/// With `dry_run` only the required funding and estimated fee are returned. Creation is
+/// refused when the spendable balance does not cover amount and fee, and while any
+/// spendable coin is frozen, since the wallet picks the bond's inputs itself.
+#[utoipa::path(
+    post,
+    path = "/api/makers/{id}/fidelity",
+    tag = "fidelity",
+    params(("id" = String, Path, description = "Maker ID")),
+    request_body = CreateFidelityRequest,
+    responses(
+        (status = 200, description = "Funding requirements (dry run)", body = ApiResponse<FidelityFundingInfo>),
+        (status = 201, description = "Bond created", body = ApiResponse<FidelityFundingInfo>),
+        (status = 400, description = "Invalid parameters or insufficient funds", body = ApiResponse<FidelityFundingInfo>),
+        (status = 404, description = "Maker not found", body = ApiResponse<FidelityFundingInfo>),
+        (status = 500, description = "Internal error", body = ApiResponse<FidelityFundingInfo>),
+        (status = 503, description = "Bitcoin Core unreachable", body = ApiResponse<FidelityFundingInfo>)
+    )
+)]
+async fn create_fidelity(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(body): Json<CreateFidelityRequest>,
+) -> (StatusCode, Json<ApiResponse<FidelityFundingInfo>>) {
+    let Some(config) = state.lock().await.get_config(&id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+        );
+    };
+    let amount = body.amount.unwrap_or(config.fidelity_amount);
+    let timelock = body.timelock.unwrap_or(config.fidelity_timelock);
+    if amount == 0 {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::err("amount must be greater than 0")),
+        );
+    }
+    if timelock == 0 {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::err("timelock must be at least 1 block")),
+        );
+    }
+    let feerate = match feerate_or_normal(config.clone(), body.feerate, body.fee_target).await {
+        Ok(feerate) => feerate,
+        Err((status, msg)) => return (status, Json(ApiResponse::err(msg))),
+    };
+    let tip = match chain_tip(config).await {
+        Ok(tip) => tip,
+        Err((status, msg)) => return (status, Json(ApiResponse::err(msg))),
+    };
+    let Some(locktime) = tip.checked_add(timelock) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::err(format!(
+                "timelock {timelock} is out of range"
+            ))),
+        );
+    };
+
+    let created = state
+        .lock()
+        .await
+        .create_fidelity(&id, amount, locktime, feerate, body.dry_run)
+        .await;
+    match created {
+        Ok(MessageResponse::FidelityCreateResp(funding)) => {
+            let info = FidelityFundingInfo::new(funding, timelock);
+            if body.dry_run {
+                (StatusCode::OK, Json(ApiResponse::ok(info)))
+            } else if !info.sufficient {
+                (
+                    StatusCode::BAD_REQUEST,
+                    Json(ApiResponse::err(format!(
+                        "Insufficient spendable balance: bond needs {} sats plus ~{} sats fee, wallet has {} sats",
+                        info.amount, info.fee, info.spendable
+                    ))),
+                )
+            } else {
+                (StatusCode::CREATED, Json(ApiResponse::ok(info)))
+            }
+        }
+        Ok(MessageResponse::ServerError(e)) => {
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::err(e)))
+        }
+        Ok(other) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::err(format!("Unexpected response: {other}"))),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::err(e.to_string())),
+        ),
     }
-    match state.lock().await.list_fidelity(&id).await {
-        Ok(MessageResponse::ListBonds(bonds)) => (StatusCode::OK, Json(ApiResponse::ok(bonds))),
+}
+
+/// Redeem an expired fidelity bond
+///
+/// Spends the bond back into the regular wallet and returns the txid. Bonds whose
+/// timelock has not expired are rejected with the remaining block count.
+#[utoipa::path(
+    post,
+    path = "/api/makers/{id}/fidelity/{index}/redeem",
+    tag = "fidelity",
+    params(
+        ("id" = String, Path, description = "Maker ID"),
+        ("index" = u32, Path, description = "Fidelity bond index")
+    ),
+    request_body = RedeemFidelityRequest,
+    responses(
+        (status = 200, description = "Redemption txid", body = ApiResponse<String>),
+        (status = 400, description = "Invalid feerate", body = ApiResponse<String>),
+        (status = 404, description = "Maker or bond not found", body = ApiResponse<String>),
+        (status = 409, description = "Bond not expired or already redeemed", body = ApiResponse<String>),
+        (status = 500, description = "Internal error", body = ApiResponse<String>),
+        (status = 503, description = "Bitcoin Core unreachable", body = ApiResponse<String>)
+    )
+)]
+async fn redeem_fidelity(
+    State(state): State<AppState>,
+    Path((id, index)): Path<(String, u32)>,
+    Json(body): Json<RedeemFidelityRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let Some(config) = state.lock().await.get_config(&id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+        );
+    };
+    let feerate = match feerate_or_normal(config.clone(), body.feerate, body.fee_target).await {
+        Ok(feerate) => feerate,
+        Err((status, msg)) => return (status, Json(ApiResponse::err(msg))),
+    };
+    let tip = match chain_tip(config).await {
+        Ok(tip) => tip,
+        Err((status, msg)) => return (status, Json(ApiResponse::err(msg))),
+    };
+
+    let redeemed = state
+        .lock()
+        .await
+        .redeem_fidelity(&id, index, feerate, tip)
+        .await;
+    match redeemed {
+        Ok(MessageResponse::FidelitySpend(txid)) => {
+            (StatusCode::OK, Json(ApiResponse::ok(txid.to_string())))
+        }
+        Ok(rejected @ MessageResponse::FidelityRedeemRejected { .. }) => {
+            let status = match &rejected {
+                MessageResponse::FidelityRedeemRejected {
+                    reason: FidelityRedeemError::NotFound,
+                    ..
+                } => StatusCode::NOT_FOUND,
+                _ => StatusCode::CONFLICT,
+            };
+            (status, Json(ApiResponse::err(rejected.to_string())))
+        }
         Ok(MessageResponse::ServerError(e)) => {
             (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::err(e)))
         }
@@ -49,3 +325,185 @@ async fn list_fidelity(
         ),
     }
 }
+
+#[derive(Deserialize)]
+struct BondValueQuery {
+    amount: u64,
+    timelock: u32,
+    /// Maker to leave out of the comparison
+    maker: Option<String>,
+}
+
+/// Estimate the value of a fidelity bond before creating it
+///
+/// Scores `amount` locked for `timelock` blocks the way a coinswap taker scores a freshly
+/// confirmed bond, and ranks it against the bonds advertised by the dashboard's makers.
+/// Bonds of makers outside this dashboard are not known to it and are not compared.
+#[utoipa::path(
+    get,
+    path = "/api/fidelity/value",
+    tag = "fidelity",
+    params(
+        ("amount" = u64, Query, description = "Amount to lock, in sats"),
+        ("timelock" = u32, Query, description = "Lock duration in blocks"),
+        ("maker" = Option<String>, Query, description = "Maker to leave out of the comparison")
+    ),
+    responses(
+        (status = 200, description = "Bond valuation", body = ApiResponse<BondValueEstimate>),
+        (status = 400, description = "Invalid amount or timelock", body = ApiResponse<BondValueEstimate>)
+    )
+)]
+async fn estimate_bond_value(
+    State(state): State<AppState>,
+    Query(query): Query<BondValueQuery>,
+) -> (StatusCode, Json<ApiResponse<BondValueEstimate>>) {
+    if query.amount == 0 || query.timelock == 0 {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::err(
+                "amount and timelock must be greater than 0",
+            )),
+        );
+    }
+    let lock_secs = u64::from(query.timelock) * SECS_PER_BLOCK;
+    let bond_value = bond_value(query.amount, lock_secs, 0);
+
+    let ids: Vec<String> = {
+        let mgr = state.lock().await;
+        mgr.list_makers()
+            .into_iter()
+            .filter(|id| query.maker.as_ref() != Some(*id) && !mgr.is_locked(id))
+            .cloned()
+            .collect()
+    };
+    let mut advertised = Vec::new();
+    for id in ids {
+        let listed = state.lock().await.list_fidelity(&id).await;
+        let Ok(MessageResponse::ListBonds(bonds)) = listed else {
+            continue;
+        };
+        advertised.extend(
+            bonds
+                .into_iter()
+                .filter(|bond| bond.advertised && !bond.spent)
+                .filter_map(|bond| {
+                    Some(AdvertisedBondInfo {
+                        maker_id: id.clone(),
+                        index: bond.index,
+                        amount: bond.amount.to_sat(),
+                        locktime: bond.locktime,
+                        bond_value: bond.value?.to_sat(),
+                    })
+                }),
+        );
+    }
+    advertised.sort_by(|a, b| b.bond_value.cmp(&a.bond_value));
+    let rank = (!advertised.is_empty()).then(|| {
+        advertised
+            .iter()
+            .take_while(|bond| bond.bond_value > bond_value)
+            .count()
+            + 1
+    });
+
+    (
+        StatusCode::OK,
+        Json(ApiResponse::ok(BondValueEstimate {
+            amount: query.amount,
+            timelock: query.timelock,
+            lockup_days: lock_secs as f64 / 86_400.0,
+            bond_value,
+            timelock_allowed: FIDELITY_TIMELOCK_RANGE.contains(&query.timelock),
+            advertised,
+            rank,
+        })),
+    )
+}
+
+/// Value of a bond of `amount` sats locked for `lock_secs` after confirmation, scored
+/// `secs_past_expiry` after its locktime (0 while it is still locked), in sats.
+///
+/// `((amount * max(0, min(1, e^(r*T) - 1) - min(1, e^(r*t) - 1)))^1.3`, with the lock period
+/// `T` and the time since expiry `t` in years. Mirrors coinswap's private implementation
+/// behind `Wallet::calculate_bond_value`.
+fn bond_value(amount: u64, lock_secs: u64, secs_past_expiry: u64) -> u64 {
+    let lock_years = lock_secs as f64 / SECS_PER_YEAR;
+    let expired_years = secs_past_expiry as f64 / SECS_PER_YEAR;
+    let time_value = (f64::exp_m1(BOND_VALUE_INTEREST_RATE * lock_years).min(1.0)
+        - f64::exp_m1(BOND_VALUE_INTEREST_RATE * expired_years).min(1.0))
+    .max(0.0);
+    let btc = (amount as f64 / 100_000_000.0 * time_value).powf(BOND_VALUE_EXPONENT);
+    (btc * 100_000_000.0).round() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use coinswap::bitcoin::{Amount, OutPoint};
+
+    use super::{bond_dto, bond_value, SECS_PER_BLOCK};
+    use crate::maker_manager::message::FidelityBondSummary;
+
+    fn bond(locktime: u32) -> FidelityBondSummary {
+        FidelityBondSummary {
+            index: 3,
+            outpoint: OutPoint::from_str(
+                "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:1",
+            )
+            .unwrap(),
+            amount: Amount::from_sat(50_000),
+            locktime,
+            conf_height: Some(900),
+            cert_expiry: Some(2),
+            spent: false,
+            value: Some(Amount::from_sat(1_234)),
+            advertised: true,
+        }
+    }
+
+    #[test]
+    fn bond_dto_computes_expiry_against_tip() {
+        let dto = bond_dto(bond(1_000), Some(990));
+        assert_eq!(dto.blocks_to_expiry, Some(10));
+        assert_eq!(dto.secs_to_expiry, Some(6_000));
+        assert_eq!(dto.expired, Some(false));
+        assert_eq!(dto.bond_value, Some(1_234));
+        assert_eq!(
+            dto.outpoint,
+            "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:1"
+        );
+
+        let dto = bond_dto(bond(1_000), Some(1_200));
+        assert_eq!(dto.blocks_to_expiry, Some(0));
+        assert_eq!(dto.expired, Some(true));
+
+        let dto = bond_dto(bond(1_000), None);
+        assert_eq!(dto.blocks_to_expiry, None);
+        assert_eq!(dto.expired, None);
+    }
+
+    #[test]
+    fn bond_value_grows_with_amount_and_lock_period() {
+        let year = 52_560 * SECS_PER_BLOCK;
+        // 1 BTC for a year: (e^0.01 - 1)^1.3 BTC
+        let one_year = bond_value(100_000_000, year, 0);
+        assert!((252_000..=254_000).contains(&one_year), "{one_year}");
+        assert!(bond_value(200_000_000, year, 0) > 2 * one_year);
+        assert!(bond_value(100_000_000, 2 * year, 0) > one_year);
+        assert_eq!(bond_value(0, year, 0), 0);
+        assert_eq!(bond_value(100_000_000, 0, 0), 0);
+        // Worthless once it has been expired as long as it was locked
+        assert_eq!(bond_value(100_000_000, year, year), 0);
+    }
+
+    #[test]
+    fn bond_value_matches_coinswap() {
+        // Expected values are coinswap's `calculate_fidelity_value` for the same bonds
+        let year = 31_556_952;
+        assert_eq!(bond_value(100_000_000, year, 0), 252_828);
+        assert_eq!(bond_value(1_000_000_000, year, 0), 5_044_583);
+        assert_eq!(bond_value(50_000_000, 15_000 * SECS_PER_BLOCK, 0), 20_006);
+        assert_eq!(bond_value(100_000_000, 2 * year, year), 256_136);
+    }
+}
@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::get,
+    Json, Router,
+};
+use serde::Deserialize;
+use tokio::task::JoinHandle;
+
+use super::{
+    analytics::DAY,
+    dto::{ApiResponse, BalanceHistoryPoint},
+    metrics::sample_node,
+    AppState,
+};
+use crate::maker_manager::{
+    history::{HistoryPoint, NodeSample, UtxoCounts, WalletPoint},
+    message::{MessageResponse, UtxoPool},
+    unix_now,
+};
+
+/// Default number of points returned when no `step` is given.
+const DEFAULT_POINTS: u64 = 500;
+/// Upper bound on the points of one response.
+const MAX_POINTS: u64 = 5_000;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/makers/{id}/history/balances", get(get_balance_history))
+}
+
+#[derive(Deserialize)]
+struct HistoryQuery {
+    from: Option<u64>,
+    to: Option<u64>,
+    /// Interval width in seconds
+    step: Option<u64>,
+}
+
+/// Spawns the task that records every maker's balances and status once per `interval`.
+/// Each round also refreshes the manager's snapshot, which `/metrics` and the alert
+/// evaluator read instead of polling the makers themselves.
+pub fn spawn_sampler(state: AppState, interval: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let ids: Vec<String> = state
+                .lock()
+                .await
+                .list_makers()
+                .into_iter()
+                .cloned()
+                .collect();
+            // Makers usually share a node; ask each one once per round
+            let mut nodes: HashMap<String, NodeSample> = HashMap::new();
+            for id in ids {
+                let Some(config) = state.lock().await.get_config(&id) else {
+                    continue;
+                };
+                let height = match nodes.get(&config.rpc) {
+                    Some(node) => node.height,
+                    None => {
+                        let rpc = config.rpc.clone();
+                        let node = sample_node(config).await;
+                        nodes.insert(rpc, node);
+                        node.height
+                    }
+                };
+                let point = sample(&state, &id, height).await;
+                let bonds = if point.alive > 0.0 {
+                    read_bond_locktimes(&state, &id).await
+                } else {
+                    None
+                };
+                let mut mgr = state.lock().await;
+                mgr.record_history(&id, point);
+                if let Some(locktimes) = bonds {
+                    mgr.record_bond_locktimes(&id, locktimes);
+                }
+            }
+            state.lock().await.record_nodes(nodes);
+        }
+    })
+}
+
+/// Reads a maker's liveness, server state and, if it answers, its wallet.
+async fn sample(state: &AppState, id: &String, height: Option<u64>) -> HistoryPoint {
+    let (locked, running) = {
+        let mut mgr = state.lock().await;
+        (mgr.is_locked(id), mgr.is_server_running(id))
+    };
+    let alive = !locked && state.lock().await.ping(id).await.is_ok();
+    let mut point = HistoryPoint::sample(unix_now(), alive, running, height);
+    if alive {
+        point.wallet = read_wallet(state, id).await;
+    }
+    point
+}
+
+async fn read_wallet(state: &AppState, id: &String) -> Option<WalletPoint> {
+    let balances = state.lock().await.get_balances(id).await;
+    let Ok(MessageResponse::TotalBalanceResp(b)) = balances else {
+        return None;
+    };
+    let mut utxos = UtxoCounts::default();
+    let listed = state.lock().await.get_utxos(id).await;
+    if let Ok(MessageResponse::UtxoResp { utxos: listed }) = listed {
+        for utxo in listed {
+            let count = match utxo.pool {
+                UtxoPool::Regular => &mut utxos.regular,
+                UtxoPool::Swept => &mut utxos.swept,
+                UtxoPool::Swap => &mut utxos.swap,
+                UtxoPool::Contract => &mut utxos.contract,
+                UtxoPool::Fidelity => &mut utxos.fidelity,
+            };
+            *count += 1;
+        }
+    }
+    Some(WalletPoint {
+        samples: 1,
+        regular: b.regular.to_sat(),
+        swap: b.swap.to_sat(),
+        contract: b.contract.to_sat(),
+        fidelity: b.fidelity.to_sat(),
+        spendable: b.spendable.to_sat(),
+        utxos,
+    })
+}
+
+/// Unlock heights of a maker's unspent fidelity bonds, for the alert evaluator.
+async fn read_bond_locktimes(state: &AppState, id: &String) -> Option<Vec<u32>> {
+    match state.lock().await.list_fidelity(id).await {
+        Ok(MessageResponse::ListBonds(bonds)) => Some(
+            bonds
+                .iter()
+                .filter(|b| !b.spent)
+                .map(|b| b.locktime)
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+/// Balance and status history of a maker
+///
+/// Returns the samples recorded by the background sampler between `from` (default a week
+/// before `to`) and `to` (default now), merged into `step`-second intervals. Raw samples are
+/// kept for two days, hourly points for 60 days and daily points for five years; older
+/// periods are served from the coarser tiers. Intervals without samples are omitted.
+#[utoipa::path(
+    get,
+    path = "/api/makers/{id}/history/balances",
+    tag = "monitoring",
+    params(
+        ("id" = String, Path, description = "Maker ID"),
+        ("from" = Option<u64>, Query, description = "Period start as a unix timestamp (default a week before to)"),
+        ("to" = Option<u64>, Query, description = "Period end as a unix timestamp (default now)"),
+        ("step" = Option<u64>, Query, description = "Interval width in seconds (default the period split into 500 intervals)")
+    ),
+    responses(
+        (status = 200, description = "History points, oldest first", body = ApiResponse<Vec<BalanceHistoryPoint>>),
+        (status = 400, description = "Invalid period or step", body = ApiResponse<Vec<BalanceHistoryPoint>>),
+        (status = 404, description = "Maker not found", body = ApiResponse<Vec<BalanceHistoryPoint>>)
+    )
+)]
+async fn get_balance_history(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<HistoryQuery>,
+) -> (StatusCode, Json<ApiResponse<Vec<BalanceHistoryPoint>>>) {
+    let to = query.to.unwrap_or_else(unix_now);
+    let from = query.from.unwrap_or(to.saturating_sub(7 * DAY));
+    if from >= to {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::err("from must be before to")),
+        );
+    }
+    let step = query
+        .step
+        .unwrap_or_else(|| (to - from).div_ceil(DEFAULT_POINTS));
+    if step == 0 || (to - from).div_ceil(step) > MAX_POINTS {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::err(format!(
+                "step must split the period into at most {MAX_POINTS} intervals"
+            ))),
+        );
+    }
+    match state.lock().await.history(&id, from, to, step) {
+        Some(points) => (
+            StatusCode::OK,
+            Json(ApiResponse::ok(
+                points.into_iter().map(Into::into).collect(),
+            )),
+        ),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+        ),
+    }
+}
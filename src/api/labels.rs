@@ -0,0 +1,232 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{delete, get, post},
+    Json, Router,
+};
+use serde::Deserialize;
+
+use super::{
+    dto::{ApiResponse, LabelDto, LabelImportResponse, LabelTypeDto},
+    AppState,
+};
+use crate::maker_manager::{
+    labels::{Label, LabelStore, LabelType},
+    MakerManagerError,
+};
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/makers/{id}/labels", get(list_labels).put(set_label))
+        .route("/makers/{id}/labels/export", get(export_labels))
+        .route("/makers/{id}/labels/import", post(import_labels))
+        .route(
+            "/makers/{id}/labels/{label_type}/{reference}",
+            delete(delete_label),
+        )
+}
+
+#[derive(Deserialize)]
+struct LabelsQuery {
+    #[serde(rename = "type")]
+    label_type: Option<LabelTypeDto>,
+}
+
+/// List a maker's labels, optionally filtered by type
+#[utoipa::path(
+    get,
+    path = "/api/makers/{id}/labels",
+    tag = "wallet",
+    params(
+        ("id" = String, Path, description = "Maker ID"),
+        ("type" = Option<LabelTypeDto>, Query, description = "Only return labels of this type")
+    ),
+    responses(
+        (status = 200, description = "Labels", body = ApiResponse<Vec<LabelDto>>),
+        (status = 404, description = "Maker not found", body = ApiResponse<Vec<LabelDto>>)
+    )
+)]
+async fn list_labels(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<LabelsQuery>,
+) -> (StatusCode, Json<ApiResponse<Vec<LabelDto>>>) {
+    let Some(labels) = state.lock().await.labels(&id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+        );
+    };
+    let filter = query.label_type.map(LabelType::from);
+    let labels = labels
+        .iter()
+        .filter(|label| filter.is_none_or(|t| label.label_type == t))
+        .cloned()
+        .map(LabelDto::from)
+        .collect();
+    (StatusCode::OK, Json(ApiResponse::ok(labels)))
+}
+
+/// Create or replace the label for a transaction, address, output or key
+#[utoipa::path(
+    put,
+    path = "/api/makers/{id}/labels",
+    tag = "wallet",
+    params(("id" = String, Path, description = "Maker ID")),
+    request_body = LabelDto,
+    responses(
+        (status = 200, description = "Label stored", body = ApiResponse<LabelDto>),
+        (status = 400, description = "Malformed reference", body = ApiResponse<LabelDto>),
+        (status = 404, description = "Maker not found", body = ApiResponse<LabelDto>),
+        (status = 500, description = "Failed to persist labels", body = ApiResponse<LabelDto>)
+    )
+)]
+async fn set_label(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(body): Json<LabelDto>,
+) -> (StatusCode, Json<ApiResponse<LabelDto>>) {
+    let label = Label::from(body);
+    if let Err(e) = label.validate() {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::err(e)));
+    }
+    match state.lock().await.set_label(&id, label.clone()) {
+        Ok(()) => (StatusCode::OK, Json(ApiResponse::ok(label.into()))),
+        Err(e) => label_error(&id, e),
+    }
+}
+
+/// Delete a label
+#[utoipa::path(
+    delete,
+    path = "/api/makers/{id}/labels/{label_type}/{reference}",
+    tag = "wallet",
+    params(
+        ("id" = String, Path, description = "Maker ID"),
+        ("label_type" = LabelTypeDto, Path, description = "BIP-329 label type"),
+        ("reference" = String, Path, description = "Labelled txid, address, outpoint or key")
+    ),
+    responses(
+        (status = 200, description = "Label removed", body = ApiResponse<String>),
+        (status = 400, description = "Unknown label type", body = ApiResponse<String>),
+        (status = 404, description = "Maker or label not found", body = ApiResponse<String>),
+        (status = 500, description = "Failed to persist labels", body = ApiResponse<String>)
+    )
+)]
+async fn delete_label(
+    State(state): State<AppState>,
+    Path((id, label_type, reference)): Path<(String, String, String)>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let Ok(label_type) = serde_json::from_value::<LabelTypeDto>(label_type.clone().into()) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::err(format!(
+                "Unknown label type '{label_type}'"
+            ))),
+        );
+    };
+    match state
+        .lock()
+        .await
+        .remove_label(&id, label_type.into(), &reference)
+    {
+        Ok(true) => (
+            StatusCode::OK,
+            Json(ApiResponse::ok(format!("Label for '{reference}' removed"))),
+        ),
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::err(format!("No label for '{reference}'"))),
+        ),
+        Err(e) => label_error(&id, e),
+    }
+}
+
+/// Export a maker's labels as BIP-329 JSON lines
+#[utoipa::path(
+    get,
+    path = "/api/makers/{id}/labels/export",
+    tag = "wallet",
+    params(("id" = String, Path, description = "Maker ID")),
+    responses(
+        (status = 200, description = "BIP-329 JSON lines", content_type = "application/jsonl"),
+        (status = 404, description = "Maker not found", body = ApiResponse<String>)
+    )
+)]
+async fn export_labels(State(state): State<AppState>, Path(id): Path<String>) -> Response {
+    let Some(labels) = state.lock().await.labels(&id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::<()>::err(format!("Maker '{id}' not found"))),
+        )
+            .into_response();
+    };
+    // Sanitize the maker ID so it can't inject characters into the header value.
+    let safe_id: String = id
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+        .collect();
+    let disposition = format!("attachment; filename=\"maker-{safe_id}-labels.jsonl\"");
+    let headers = [
+        (
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/jsonl; charset=utf-8"),
+        ),
+        (
+            header::CONTENT_DISPOSITION,
+            HeaderValue::from_str(&disposition)
+                .unwrap_or_else(|_| HeaderValue::from_static("attachment")),
+        ),
+    ];
+    (StatusCode::OK, headers, labels.to_jsonl()).into_response()
+}
+
+/// Import BIP-329 JSON lines, replacing labels with the same type and reference
+#[utoipa::path(
+    post,
+    path = "/api/makers/{id}/labels/import",
+    tag = "wallet",
+    params(("id" = String, Path, description = "Maker ID")),
+    request_body(content = String, content_type = "application/jsonl", description = "BIP-329 JSON lines"),
+    responses(
+        (status = 200, description = "Labels imported", body = ApiResponse<LabelImportResponse>),
+        (status = 400, description = "Malformed record; nothing was imported", body = ApiResponse<LabelImportResponse>),
+        (status = 404, description = "Maker not found", body = ApiResponse<LabelImportResponse>),
+        (status = 500, description = "Failed to persist labels", body = ApiResponse<LabelImportResponse>)
+    )
+)]
+async fn import_labels(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    body: String,
+) -> (StatusCode, Json<ApiResponse<LabelImportResponse>>) {
+    let imported = match LabelStore::parse_jsonl(&body) {
+        Ok(imported) => imported,
+        Err(e) => return (StatusCode::BAD_REQUEST, Json(ApiResponse::err(e))),
+    };
+    let mut mgr = state.lock().await;
+    match mgr.import_labels(&id, imported) {
+        Ok(imported) => (
+            StatusCode::OK,
+            Json(ApiResponse::ok(LabelImportResponse {
+                imported,
+                total: mgr.labels(&id).map(|l| l.len()).unwrap_or_default(),
+            })),
+        ),
+        Err(e) => label_error(&id, e),
+    }
+}
+
+fn label_error<T>(id: &str, e: MakerManagerError) -> (StatusCode, Json<ApiResponse<T>>) {
+    match e {
+        MakerManagerError::NotFound(_) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+        ),
+        e => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::err(e.to_string())),
+        ),
+    }
+}
@@ -9,12 +9,14 @@ use axum::{
 
 use super::{
     dto::{
-        ApiResponse, CreateMakerRequest, MakerInfo, MakerInfoDetailed, SuggestedMakerPorts,
-        UpdateMakerConfigRequest,
+        ApiResponse, ChangePasswordRequest, CreateMakerRequest, MakerInfo, MakerInfoDetailed,
+        SuggestedMakerPorts, UnlockMakerRequest, UpdateMakerConfigRequest,
     },
+    swap_index,
+    wallet::relock_frozen,
     AppState,
 };
-use crate::maker_manager::{MakerConfig, MakerManagerError};
+use crate::maker_manager::{maker_pool::is_valid_maker_id, MakerConfig, MakerManagerError};
 
 pub fn routes() -> Router<AppState> {
     Router::new()
@@ -29,6 +31,8 @@ pub fn routes() -> Router<AppState> {
         .route("/makers/{id}/start", post(start_maker))
         .route("/makers/{id}/stop", post(stop_maker))
         .route("/makers/{id}/restart", post(restart_maker))
+        .route("/makers/{id}/unlock", post(unlock_maker))
+        .route("/makers/{id}/password", post(change_password))
 }
 
 /// List all makers
@@ -75,6 +79,9 @@ async fn get_suggested_ports(
     }
 }
 
+/// Fidelity bond timelocks accepted for a maker's config, in blocks.
+pub(crate) const FIDELITY_TIMELOCK_RANGE: std::ops::RangeInclusive<u32> = 12960..=25920;
+
 fn validate_maker_config(config: &MakerConfig) -> Result<(), String> {
     for (name, port) in [
         ("network_port", config.network_port),
@@ -104,9 +111,11 @@ fn validate_maker_config(config: &MakerConfig) -> Result<(), String> {
         }
     }
 
-    if !(12960..=25920).contains(&config.fidelity_timelock) {
+    if !FIDELITY_TIMELOCK_RANGE.contains(&config.fidelity_timelock) {
         return Err(format!(
-            "fidelity_timelock must be between 12960 and 25920, got {}",
+            "fidelity_timelock must be between {} and {}, got {}",
+            FIDELITY_TIMELOCK_RANGE.start(),
+            FIDELITY_TIMELOCK_RANGE.end(),
             config.fidelity_timelock
         ));
     }
@@ -117,10 +126,15 @@ fn validate_maker_config(config: &MakerConfig) -> Result<(), String> {
     if config.fidelity_amount == 0 {
         return Err("fidelity_amount must be greater than 0".to_string());
     }
+    if !config.persist_password && config.password.is_none() {
+        return Err(
+            "persist_password can only be disabled for password-protected wallets".to_string(),
+        );
+    }
 
     Ok(())
 }
-/// Create a new maker
+/// Create a new maker, optionally restoring its wallet from a backup
 #[utoipa::path(
     post, path = "/api/makers", tag = "makers",
     request_body = CreateMakerRequest,
@@ -135,6 +149,14 @@ async fn create_maker(
     State(state): State<AppState>,
     Json(body): Json<CreateMakerRequest>,
 ) -> (StatusCode, Json<ApiResponse<MakerInfo>>) {
+    if !is_valid_maker_id(&body.id) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::err(
+                "Maker ID may only contain letters, digits, '-' and '_'",
+            )),
+        );
+    }
     let mut mgr = state.lock().await;
     if mgr.has_maker(&body.id) {
         return (
@@ -168,6 +190,7 @@ async fn create_maker(
         tor_auth: body.tor_auth,
         wallet_name: body.wallet_name,
         password: body.password,
+        persist_password: body.persist_password.unwrap_or(true),
         network_port: body.network_port.unwrap_or(6102),
         rpc_port: body.rpc_port.unwrap_or(6103),
         socks_port: body.socks_port.unwrap_or(9050),
@@ -185,6 +208,16 @@ async fn create_maker(
     if let Err(e) = validate_maker_config(&config) {
         return (StatusCode::BAD_REQUEST, Json(ApiResponse::err(e)));
     }
+    if let Some(restore) = &body.restore {
+        if restore.backup.trim().is_empty() || restore.passphrase.is_empty() {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::err(
+                    "Restoring requires both the backup contents and its passphrase",
+                )),
+            );
+        }
+    }
 
     match mgr.assign_available_maker_ports(
         config.network_port,
@@ -207,11 +240,24 @@ async fn create_maker(
         }
     }
 
-    match mgr.create_maker(body.id.clone(), config) {
-        Ok(()) => (
-            StatusCode::CREATED,
-            Json(ApiResponse::ok(MakerInfo { id: body.id })),
+    let created = match body.restore {
+        Some(restore) => mgr.create_maker_from_backup(
+            body.id.clone(),
+            config,
+            &restore.backup,
+            restore.passphrase,
         ),
+        None => mgr.create_maker(body.id.clone(), config),
+    };
+    drop(mgr);
+    match created {
+        Ok(()) => {
+            relock_frozen(&state, &body.id).await;
+            (
+                StatusCode::CREATED,
+                Json(ApiResponse::ok(MakerInfo { id: body.id })),
+            )
+        }
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::err(e.to_string())),
@@ -260,7 +306,13 @@ async fn delete_maker(
     Path(id): Path<String>,
 ) -> (StatusCode, Json<ApiResponse<String>>) {
     let mut mgr = state.lock().await;
+    let data_dir = mgr
+        .get_maker_config(&id)
+        .and_then(|config| config.data_directory.clone());
     if mgr.remove_maker(&id) {
+        if let Some(data_dir) = data_dir {
+            swap_index::forget(&data_dir.join("swap_reports"));
+        }
         (
             StatusCode::OK,
             Json(ApiResponse::ok(format!("Maker '{id}' removed"))),
@@ -281,6 +333,7 @@ async fn delete_maker(
     responses(
         (status = 200, description = "Config updated",  body = ApiResponse<String>),
         (status = 404, description = "Maker not found", body = ApiResponse<String>),
+        (status = 423, description = "Maker locked",    body = ApiResponse<String>),
         (status = 500, description = "Internal error",  body = ApiResponse<String>)
     )
 )]
@@ -301,6 +354,15 @@ async fn update_config(
         }
     };
 
+    if mgr.is_locked(&id) {
+        return (
+            StatusCode::LOCKED,
+            Json(ApiResponse::err(format!(
+                "Maker '{id}' is locked; unlock it with its wallet password first"
+            ))),
+        );
+    }
+
     let config = body.apply_to(base);
 
     if let Err(e) = validate_maker_config(&config) {
@@ -378,6 +440,7 @@ async fn get_maker_count(State(state): State<AppState>) -> Json<ApiResponse<usiz
         (status = 200, description = "Maker started",          body = ApiResponse<String>),
         (status = 404, description = "Maker not found",        body = ApiResponse<String>),
         (status = 409, description = "Maker already running",  body = ApiResponse<String>),
+        (status = 423, description = "Maker locked",           body = ApiResponse<String>),
         (status = 500, description = "Internal error",         body = ApiResponse<String>)
     )
 )]
@@ -385,8 +448,10 @@ async fn start_maker(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> (StatusCode, Json<ApiResponse<String>>) {
-    let mut mgr = state.lock().await;
-    match mgr.start_maker(&id) {
+    // Before the server starts, so it can't fund anything from frozen coins
+    relock_frozen(&state, &id).await;
+    let started = state.lock().await.start_maker(&id);
+    match started {
         Ok(()) => (
             StatusCode::OK,
             Json(ApiResponse::ok(format!("Maker '{id}' started"))),
@@ -399,6 +464,9 @@ async fn start_maker(
             StatusCode::CONFLICT,
             Json(ApiResponse::err(format!("Maker '{id}' is already running"))),
         ),
+        Err(e @ MakerManagerError::Locked(_)) => {
+            (StatusCode::LOCKED, Json(ApiResponse::err(e.to_string())))
+        }
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::err(e.to_string())),
@@ -449,6 +517,7 @@ async fn stop_maker(
     responses(
         (status = 200, description = "Maker restarted",  body = ApiResponse<String>),
         (status = 404, description = "Maker not found",  body = ApiResponse<String>),
+        (status = 423, description = "Maker locked",     body = ApiResponse<String>),
         (status = 500, description = "Internal error",   body = ApiResponse<String>)
     )
 )]
@@ -456,8 +525,9 @@ async fn restart_maker(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> (StatusCode, Json<ApiResponse<String>>) {
-    let mut mgr = state.lock().await;
-    match mgr.restart_maker(&id) {
+    relock_frozen(&state, &id).await;
+    let restarted = state.lock().await.restart_maker(&id);
+    match restarted {
         Ok(()) => (
             StatusCode::OK,
             Json(ApiResponse::ok(format!("Maker '{id}' restarted"))),
@@ -466,6 +536,127 @@ async fn restart_maker(
             StatusCode::NOT_FOUND,
             Json(ApiResponse::err(format!("Maker '{id}' not found"))),
         ),
+        Err(e @ MakerManagerError::Locked(_)) => {
+            (StatusCode::LOCKED, Json(ApiResponse::err(e.to_string())))
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::err(e.to_string())),
+        ),
+    }
+}
+
+/// Unlock a maker whose wallet password is not persisted
+#[utoipa::path(
+    post, path = "/api/makers/{id}/unlock", tag = "makers",
+    params(("id" = String, Path, description = "Maker ID")),
+    request_body = UnlockMakerRequest,
+    responses(
+        (status = 200, description = "Maker unlocked (stopped)", body = ApiResponse<String>),
+        (status = 404, description = "Maker not found",          body = ApiResponse<String>),
+        (status = 409, description = "Maker is not locked",      body = ApiResponse<String>),
+        (status = 500, description = "Wallet could not be opened", body = ApiResponse<String>)
+    )
+)]
+async fn unlock_maker(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(body): Json<UnlockMakerRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let unlocked = state.lock().await.unlock_maker(&id, body.password);
+    match unlocked {
+        Ok(()) => {
+            relock_frozen(&state, &id).await;
+            (
+                StatusCode::OK,
+                Json(ApiResponse::ok(format!("Maker '{id}' unlocked"))),
+            )
+        }
+        Err(MakerManagerError::NotFound(_)) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+        ),
+        Err(e @ MakerManagerError::NotLocked(_)) => {
+            (StatusCode::CONFLICT, Json(ApiResponse::err(e.to_string())))
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::err(e.to_string())),
+        ),
+    }
+}
+
+/// Change a maker's wallet encryption password
+///
+/// The maker must be stopped. The wallet is re-encrypted under the new password; with
+/// `persist_password: false` the password is no longer written to `makers.json`.
+#[utoipa::path(
+    post, path = "/api/makers/{id}/password", tag = "makers",
+    params(("id" = String, Path, description = "Maker ID")),
+    request_body = ChangePasswordRequest,
+    responses(
+        (status = 200, description = "Password changed",         body = ApiResponse<String>),
+        (status = 400, description = "Empty new password",       body = ApiResponse<String>),
+        (status = 401, description = "Wrong current password",   body = ApiResponse<String>),
+        (status = 404, description = "Maker not found",          body = ApiResponse<String>),
+        (status = 409, description = "Maker is running",         body = ApiResponse<String>),
+        (status = 423, description = "Maker locked",             body = ApiResponse<String>),
+        (status = 500, description = "Internal error",           body = ApiResponse<String>)
+    )
+)]
+async fn change_password(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(body): Json<ChangePasswordRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    if body.new_password.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::err("new_password must not be empty")),
+        );
+    }
+    let mut mgr = state.lock().await;
+    let Some(persist_password) = mgr
+        .get_maker_config(&id)
+        .map(|c| body.persist_password.unwrap_or(c.persist_password))
+    else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+        );
+    };
+    match mgr
+        .change_wallet_password(
+            &id,
+            body.current_password.as_deref(),
+            body.new_password,
+            persist_password,
+        )
+        .await
+    {
+        Ok(()) => (
+            StatusCode::OK,
+            Json(ApiResponse::ok(format!(
+                "Wallet password of '{id}' changed"
+            ))),
+        ),
+        Err(MakerManagerError::NotFound(_)) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+        ),
+        Err(e @ MakerManagerError::WrongPassword(_)) => (
+            StatusCode::UNAUTHORIZED,
+            Json(ApiResponse::err(e.to_string())),
+        ),
+        Err(MakerManagerError::AlreadyRunning(_)) => (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::err(format!(
+                "Stop maker '{id}' before changing its wallet password"
+            ))),
+        ),
+        Err(e @ MakerManagerError::Locked(_)) => {
+            (StatusCode::LOCKED, Json(ApiResponse::err(e.to_string())))
+        }
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::err(e.to_string())),
@@ -0,0 +1,535 @@
+//! Prometheus metrics in the text exposition format.
+//!
+//! Maker and node gauges are read from the snapshot the history sampler keeps, and swap
+//! totals from the swap report index, so a scrape doesn't poll any maker or node. HTTP
+//! request counters and latencies are recorded by
+//! [`crate::middlewares::track_http_metrics`] as requests complete.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use axum::{
+    extract::State,
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use coinswap::bitcoind::bitcoincore_rpc::RpcApi;
+
+use super::{swap_index, AppState};
+use crate::maker_manager::{
+    history::{HistoryPoint, NodeSample},
+    MakerConfig,
+};
+
+/// Upper bounds of the HTTP latency histogram buckets, in seconds.
+const LATENCY_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/metrics", get(get_metrics))
+}
+
+#[derive(Default)]
+struct Histogram {
+    /// Non-cumulative counts per bucket in [`LATENCY_BUCKETS`]
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, secs: f64) {
+        if let Some(i) = LATENCY_BUCKETS.iter().position(|&bound| secs <= bound) {
+            self.buckets[i] += 1;
+        }
+        self.sum += secs;
+        self.count += 1;
+    }
+}
+
+/// Request counters and latencies, keyed by method and route template.
+#[derive(Default)]
+struct HttpMetrics {
+    requests: BTreeMap<(String, String, u16), u64>,
+    latencies: BTreeMap<(String, String), Histogram>,
+}
+
+fn http_metrics() -> &'static Mutex<HttpMetrics> {
+    static METRICS: OnceLock<Mutex<HttpMetrics>> = OnceLock::new();
+    METRICS.get_or_init(Default::default)
+}
+
+/// Records a completed HTTP request. `path` should be the route template, not the
+/// concrete URI, to keep label cardinality bounded.
+pub fn record_http_request(method: &str, path: &str, status: u16, elapsed: Duration) {
+    let Ok(mut metrics) = http_metrics().lock() else {
+        return;
+    };
+    *metrics
+        .requests
+        .entry((method.to_string(), path.to_string(), status))
+        .or_default() += 1;
+    metrics
+        .latencies
+        .entry((method.to_string(), path.to_string()))
+        .or_default()
+        .observe(elapsed.as_secs_f64());
+}
+
+/// Builds a text exposition, one metric family at a time.
+#[derive(Default)]
+struct Exposition {
+    out: String,
+}
+
+impl Exposition {
+    fn family(&mut self, name: &str, kind: &str, help: &str) {
+        let _ = writeln!(self.out, "# HELP {name} {help}");
+        let _ = writeln!(self.out, "# TYPE {name} {kind}");
+    }
+
+    fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: f64) {
+        self.out.push_str(name);
+        if !labels.is_empty() {
+            let labels: Vec<String> = labels
+                .iter()
+                .map(|(key, value)| format!("{key}=\"{}\"", escape_label(value)))
+                .collect();
+            let _ = write!(self.out, "{{{}}}", labels.join(","));
+        }
+        let _ = writeln!(self.out, " {value}");
+    }
+
+    fn http(&mut self, metrics: &HttpMetrics) {
+        self.family(
+            "http_requests_total",
+            "counter",
+            "HTTP requests handled, by method, route and status.",
+        );
+        for ((method, path, status), count) in &metrics.requests {
+            let status = status.to_string();
+            self.sample(
+                "http_requests_total",
+                &[
+                    ("method", method.as_str()),
+                    ("path", path.as_str()),
+                    ("status", status.as_str()),
+                ],
+                *count as f64,
+            );
+        }
+        self.family(
+            "http_request_duration_seconds",
+            "histogram",
+            "HTTP request latency, by method and route.",
+        );
+        for ((method, path), histogram) in &metrics.latencies {
+            let mut cumulative = 0;
+            for (bound, count) in LATENCY_BUCKETS.iter().zip(histogram.buckets) {
+                cumulative += count;
+                let le = bound.to_string();
+                self.sample(
+                    "http_request_duration_seconds_bucket",
+                    &[
+                        ("method", method.as_str()),
+                        ("path", path.as_str()),
+                        ("le", le.as_str()),
+                    ],
+                    cumulative as f64,
+                );
+            }
+            let labels = [("method", method.as_str()), ("path", path.as_str())];
+            self.sample(
+                "http_request_duration_seconds_bucket",
+                &[labels[0], labels[1], ("le", "+Inf")],
+                histogram.count as f64,
+            );
+            self.sample("http_request_duration_seconds_sum", &labels, histogram.sum);
+            self.sample(
+                "http_request_duration_seconds_count",
+                &labels,
+                histogram.count as f64,
+            );
+        }
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Per-maker figures gathered for one scrape.
+#[derive(Default)]
+struct MakerSample {
+    server_running: bool,
+    locked: bool,
+    /// Latest reading of the history sampler, once it has sampled the maker
+    latest: Option<HistoryPoint>,
+    /// Swap count by report status
+    swaps: BTreeMap<String, u64>,
+    fees_earned: u64,
+    fees_paid: u64,
+    mining_fees: u64,
+    volume: u64,
+}
+
+pub(crate) async fn sample_node(config: MakerConfig) -> NodeSample {
+    let info = tokio::task::spawn_blocking(move || {
+        config
+            .rpc_client()
+            .and_then(|client| Ok(client.get_blockchain_info()?))
+    })
+    .await
+    .ok()
+    .and_then(Result::ok);
+    NodeSample {
+        up: info.is_some(),
+        height: info.as_ref().map(|info| info.blocks),
+        progress: info.map(|info| info.verification_progress),
+    }
+}
+
+/// Prometheus metrics
+///
+/// Per-maker balances, UTXO counts, liveness and swap report totals, node height and sync
+/// progress, and HTTP request counters and latencies, in the text exposition format.
+/// Liveness, wallet and node figures come from the latest round of the history sampler,
+/// so a scrape never waits on a maker or node; `maker_sample_timestamp_seconds` tells
+/// their age.
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "monitoring",
+    responses(
+        (status = 200, description = "Prometheus text exposition", body = String, content_type = "text/plain")
+    )
+)]
+async fn get_metrics(State(state): State<AppState>) -> Response {
+    let (mut makers, nodes) = {
+        let mut mgr = state.lock().await;
+        let snapshot = mgr.snapshot().clone();
+        let ids: Vec<String> = mgr.list_makers().into_iter().cloned().collect();
+        let makers: BTreeMap<String, (MakerSample, Option<PathBuf>)> = ids
+            .into_iter()
+            .map(|id| {
+                let sample = MakerSample {
+                    server_running: mgr.is_server_running(&id),
+                    locked: mgr.is_locked(&id),
+                    latest: snapshot.makers.get(&id).copied(),
+                    ..MakerSample::default()
+                };
+                let data_dir = mgr.get_config(&id).and_then(|config| config.data_directory);
+                (id, (sample, data_dir))
+            })
+            .collect();
+        let nodes: BTreeMap<String, NodeSample> = snapshot.nodes.into_iter().collect();
+        (makers, nodes)
+    };
+    for (id, (sample, data_dir)) in makers.iter_mut() {
+        let Some(reports_dir) = data_dir.as_ref().map(|dir| dir.join("swap_reports")) else {
+            continue;
+        };
+        let id = id.clone();
+        let reports = tokio::task::spawn_blocking(move || swap_index::refresh(&reports_dir, &id))
+            .await
+            .ok()
+            .and_then(Result::ok)
+            .unwrap_or_default();
+        for report in reports {
+            *sample.swaps.entry(report.status.clone()).or_default() += 1;
+            let fee = report.fee_paid_or_earned;
+            if fee >= 0 {
+                sample.fees_earned += fee.unsigned_abs();
+            } else {
+                sample.fees_paid += fee.unsigned_abs();
+            }
+            sample.mining_fees += report.mining_fee;
+            sample.volume += report.incoming_amount;
+        }
+    }
+    let makers: Vec<(&String, &MakerSample)> = makers
+        .iter()
+        .map(|(id, (sample, _))| (id, sample))
+        .collect();
+
+    let mut out = Exposition::default();
+    out.family(
+        "maker_count",
+        "gauge",
+        "Makers registered with the dashboard.",
+    );
+    out.sample("maker_count", &[], makers.len() as f64);
+
+    out.family(
+        "maker_sample_timestamp_seconds",
+        "gauge",
+        "Unix time of the sample the maker's liveness and wallet figures come from.",
+    );
+    for (id, m) in &makers {
+        if let Some(latest) = &m.latest {
+            out.sample(
+                "maker_sample_timestamp_seconds",
+                &[("maker", id.as_str())],
+                latest.at as f64,
+            );
+        }
+    }
+    out.family(
+        "maker_up",
+        "gauge",
+        "Whether the maker's wallet thread answered when last sampled.",
+    );
+    for (id, m) in &makers {
+        if let Some(latest) = &m.latest {
+            out.sample(
+                "maker_up",
+                &[("maker", id.as_str())],
+                f64::from(u8::from(latest.alive > 0.0)),
+            );
+        }
+    }
+    out.family(
+        "maker_server_running",
+        "gauge",
+        "Whether the maker's coinswap server is running.",
+    );
+    for (id, m) in &makers {
+        out.sample(
+            "maker_server_running",
+            &[("maker", id.as_str())],
+            f64::from(u8::from(m.server_running)),
+        );
+    }
+    out.family(
+        "maker_locked",
+        "gauge",
+        "Whether the maker waits for its wallet password.",
+    );
+    for (id, m) in &makers {
+        out.sample(
+            "maker_locked",
+            &[("maker", id.as_str())],
+            f64::from(u8::from(m.locked)),
+        );
+    }
+    out.family(
+        "maker_balance_sats",
+        "gauge",
+        "Wallet balance by category, in sats.",
+    );
+    for (id, m) in &makers {
+        let Some(wallet) = m.latest.and_then(|latest| latest.wallet) else {
+            continue;
+        };
+        for (category, sats) in [
+            ("regular", wallet.regular),
+            ("swap", wallet.swap),
+            ("contract", wallet.contract),
+            ("fidelity", wallet.fidelity),
+            ("spendable", wallet.spendable),
+        ] {
+            out.sample(
+                "maker_balance_sats",
+                &[("maker", id.as_str()), ("category", category)],
+                sats as f64,
+            );
+        }
+    }
+    out.family("maker_utxos", "gauge", "Wallet UTXOs by pool.");
+    for (id, m) in &makers {
+        let Some(wallet) = m.latest.and_then(|latest| latest.wallet) else {
+            continue;
+        };
+        let utxos = wallet.utxos;
+        for (pool, count) in [
+            ("contract", utxos.contract),
+            ("fidelity", utxos.fidelity),
+            ("regular", utxos.regular),
+            ("swap", utxos.swap),
+            ("swept", utxos.swept),
+        ] {
+            out.sample(
+                "maker_utxos",
+                &[("maker", id.as_str()), ("pool", pool)],
+                f64::from(count),
+            );
+        }
+    }
+    out.family(
+        "maker_swaps_total",
+        "counter",
+        "Swaps recorded in swap reports, by status.",
+    );
+    for (id, m) in &makers {
+        for (status, count) in &m.swaps {
+            out.sample(
+                "maker_swaps_total",
+                &[("maker", id.as_str()), ("status", status.as_str())],
+                *count as f64,
+            );
+        }
+    }
+    out.family(
+        "maker_swap_fees_earned_sats_total",
+        "counter",
+        "Swap fees earned according to swap reports, in sats.",
+    );
+    for (id, m) in &makers {
+        out.sample(
+            "maker_swap_fees_earned_sats_total",
+            &[("maker", id.as_str())],
+            m.fees_earned as f64,
+        );
+    }
+    out.family(
+        "maker_swap_fees_paid_sats_total",
+        "counter",
+        "Swap fees paid according to swap reports, in sats.",
+    );
+    for (id, m) in &makers {
+        out.sample(
+            "maker_swap_fees_paid_sats_total",
+            &[("maker", id.as_str())],
+            m.fees_paid as f64,
+        );
+    }
+    out.family(
+        "maker_swap_mining_fees_sats_total",
+        "counter",
+        "Mining fees paid in swaps, in sats.",
+    );
+    for (id, m) in &makers {
+        out.sample(
+            "maker_swap_mining_fees_sats_total",
+            &[("maker", id.as_str())],
+            m.mining_fees as f64,
+        );
+    }
+    out.family(
+        "maker_swap_volume_sats_total",
+        "counter",
+        "Incoming swap volume, in sats.",
+    );
+    for (id, m) in &makers {
+        out.sample(
+            "maker_swap_volume_sats_total",
+            &[("maker", id.as_str())],
+            m.volume as f64,
+        );
+    }
+
+    out.family(
+        "bitcoind_up",
+        "gauge",
+        "Whether the node answers RPC calls.",
+    );
+    for (rpc, node) in &nodes {
+        out.sample(
+            "bitcoind_up",
+            &[("rpc", rpc.as_str())],
+            f64::from(u8::from(node.up)),
+        );
+    }
+    out.family(
+        "bitcoind_block_height",
+        "gauge",
+        "Block height of the node.",
+    );
+    for (rpc, node) in &nodes {
+        if let Some(height) = node.height {
+            out.sample(
+                "bitcoind_block_height",
+                &[("rpc", rpc.as_str())],
+                height as f64,
+            );
+        }
+    }
+    out.family(
+        "bitcoind_verification_progress",
+        "gauge",
+        "Initial block download progress from 0 to 1.",
+    );
+    for (rpc, node) in &nodes {
+        if let Some(progress) = node.progress {
+            out.sample(
+                "bitcoind_verification_progress",
+                &[("rpc", rpc.as_str())],
+                progress,
+            );
+        }
+    }
+
+    match http_metrics().lock() {
+        Ok(metrics) => out.http(&metrics),
+        Err(e) => tracing::warn!("HTTP metrics unavailable: {e}"),
+    }
+
+    (
+        StatusCode::OK,
+        [(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("text/plain; version=0.0.4; charset=utf-8"),
+        )],
+        out.out,
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{escape_label, Exposition, HttpMetrics, LATENCY_BUCKETS};
+
+    #[test]
+    fn samples_escape_label_values() {
+        assert_eq!(escape_label("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+        let mut out = Exposition::default();
+        out.family("maker_up", "gauge", "Up.");
+        out.sample("maker_up", &[("maker", "m\"1")], 1.0);
+        out.sample("maker_count", &[], 2.0);
+        assert_eq!(
+            out.out,
+            "# HELP maker_up Up.\n# TYPE maker_up gauge\nmaker_up{maker=\"m\\\"1\"} 1\nmaker_count 2\n"
+        );
+    }
+
+    #[test]
+    fn latency_histogram_is_cumulative() {
+        let mut metrics = HttpMetrics::default();
+        let key = ("GET".to_string(), "/api/health".to_string());
+        let histogram = metrics.latencies.entry(key).or_default();
+        histogram.observe(0.001);
+        histogram.observe(0.2);
+        histogram.observe(30.0);
+
+        let mut out = Exposition::default();
+        out.http(&metrics);
+        let bucket = |le: &str| {
+            let prefix = format!(
+                "http_request_duration_seconds_bucket{{method=\"GET\",path=\"/api/health\",le=\"{le}\"}} "
+            );
+            out.out
+                .lines()
+                .find_map(|line| line.strip_prefix(prefix.as_str()))
+                .map(str::to_string)
+        };
+        assert_eq!(bucket("0.005").as_deref(), Some("1"));
+        assert_eq!(bucket("0.25").as_deref(), Some("2"));
+        assert_eq!(
+            bucket(&LATENCY_BUCKETS[10].to_string()).as_deref(),
+            Some("2")
+        );
+        assert_eq!(bucket("+Inf").as_deref(), Some("3"));
+        assert!(out.out.contains(
+            "http_request_duration_seconds_count{method=\"GET\",path=\"/api/health\"} 3\n"
+        ));
+    }
+}
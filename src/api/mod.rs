@@ -1,10 +1,26 @@
+pub mod addresses;
+pub mod alerts;
+pub mod analytics;
 pub mod bitcoind;
 pub mod dto;
+pub mod events;
+pub mod export;
+pub mod fees;
 pub mod fidelity;
+pub mod history;
+pub mod labels;
 pub mod makers;
+pub mod metrics;
 pub mod monitoring;
 pub mod onboarding;
+pub mod recovery;
+pub mod renewal;
+pub mod rescan;
+pub mod returns;
+pub mod swap_index;
+pub mod transactions;
 pub mod wallet;
+pub mod webhooks;
 
 use std::sync::Arc;
 
@@ -32,18 +48,64 @@ pub type AppState = Arc<Mutex<MakerManager>>;
         makers::start_maker,
         makers::stop_maker,
         makers::restart_maker,
+        makers::unlock_maker,
+        makers::change_password,
         wallet::get_balance,
         wallet::get_utxos,
         wallet::get_swap_utxos,
         wallet::get_contract_utxos,
         wallet::get_fidelity_utxos,
+        wallet::get_frozen_utxos,
+        wallet::freeze_utxo,
+        wallet::unfreeze_utxo,
         wallet::send_to_address,
         wallet::get_new_address,
+        addresses::list_addresses,
         wallet::sync_wallet,
+        wallet::backup_wallet,
+        rescan::rescan,
+        rescan::reimport_descriptors,
+        fees::get_fees,
+        labels::list_labels,
+        labels::set_label,
+        labels::delete_label,
+        labels::export_labels,
+        labels::import_labels,
+        transactions::list_transactions,
+        export::export_transactions,
+        export::export_swap_reports,
+        transactions::list_outgoing,
+        transactions::bump_fee,
         fidelity::list_fidelity,
+        fidelity::create_fidelity,
+        fidelity::redeem_fidelity,
+        fidelity::estimate_bond_value,
+        renewal::get_policy,
+        renewal::set_policy,
+        renewal::get_log,
+        renewal::evaluate_now,
         monitoring::get_status,
         monitoring::get_swaps,
         monitoring::get_swap_reports,
+        monitoring::get_swap_report_page,
+        monitoring::get_swap_report,
+        analytics::get_analytics,
+        analytics::get_fleet_analytics,
+        returns::get_returns,
+        recovery::get_recovery,
+        events::get_events,
+        webhooks::list_webhooks,
+        webhooks::create_webhook,
+        webhooks::update_webhook,
+        webhooks::delete_webhook,
+        webhooks::list_deliveries,
+        webhooks::test_webhook,
+        alerts::list_alerts,
+        alerts::list_rules,
+        alerts::create_rule,
+        alerts::update_rule,
+        alerts::delete_rule,
+        history::get_balance_history,
         monitoring::get_logs,
         monitoring::get_logs_stream,
         monitoring::get_tor_address,
@@ -54,27 +116,87 @@ pub type AppState = Arc<Mutex<MakerManager>>;
         bitcoind::start,
         bitcoind::stop,
         onboarding::run_startup_check,
+        metrics::get_metrics,
         health_check,
     ),
     components(schemas(
         dto::CreateMakerRequest,
+        dto::RestoreWalletRequest,
+        dto::WalletBackupRequest,
+        dto::UnlockMakerRequest,
+        dto::ChangePasswordRequest,
         dto::UpdateMakerConfigRequest,
         dto::SendToAddressRequest,
+        dto::FeePreset,
+        dto::FeeTarget,
+        dto::FeeEstimateInfo,
+        dto::FeeEstimatesInfo,
+        dto::BumpMethodDto,
+        dto::BumpFeeRequest,
+        dto::BumpFeeResponse,
+        dto::OutgoingTxInfo,
+        dto::TxCategory,
+        dto::TransactionInfo,
+        dto::TransactionPage,
+        dto::LabelTypeDto,
+        dto::LabelDto,
+        dto::LabelImportResponse,
+        dto::AddressTypeDto,
+        dto::NewAddressInfo,
+        dto::AddressInfo,
+        dto::FidelityBondDto,
+        dto::CreateFidelityRequest,
+        dto::FidelityFundingInfo,
+        dto::RedeemFidelityRequest,
+        dto::AdvertisedBondInfo,
+        dto::BondValueEstimate,
+        dto::RenewalPolicyDto,
+        dto::RenewalActionDto,
+        dto::RenewalRecordDto,
         dto::MakerInfo,
         dto::MakerInfoDetailed,
         dto::MakerStateDto,
         dto::BalanceInfo,
+        dto::UtxoPoolDto,
         dto::UtxoInfo,
+        dto::FrozenUtxoInfo,
+        dto::RescanRequest,
+        dto::RescanKindDto,
+        dto::RescanStatus,
         dto::MakerStatus,
         dto::HealthResponse,
         dto::RpcStatusInfo,
         dto::SwapReportDto,
+        dto::SwapReportPage,
+        dto::AnalyticsWindow,
+        dto::ExportFormat,
+        dto::SwapStats,
+        dto::AnalyticsBucket,
+        dto::SwapAnalytics,
+        dto::MakerSwapStats,
+        dto::FleetAnalytics,
+        dto::ReturnOnCapital,
+        dto::SwapErrorCategory,
+        dto::ErrorCategoryStats,
+        dto::RecoveryInfo,
+        dto::PendingRecovery,
+        dto::RecoveryInsights,
+        dto::UtxoCountsInfo,
+        dto::BalanceHistoryPoint,
         dto::StartupCheckKind,
         dto::StartupCheckRequest,
         dto::StartupCheckResponse,
         dto::StartBitcoindRequest,
         dto::BitcoindStatusInfo,
         dto::CombinedLogLine,
+        dto::WebhookRequest,
+        dto::WebhookInfo,
+        dto::WebhookDeliveryInfo,
+        dto::AlertConditionDto,
+        dto::AlertRuleRequest,
+        dto::AlertRuleInfo,
+        dto::AlertStateDto,
+        dto::AlertInfo,
     )),
     tags(
         (name = "makers", description = "Maker management"),
@@ -83,6 +205,8 @@ pub type AppState = Arc<Mutex<MakerManager>>;
         (name = "monitoring", description = "Status and monitoring"),
         (name = "bitcoind", description = "Bitcoin node management"),
         (name = "onboarding", description = "Environment setup checks"),
+        (name = "webhooks", description = "Event notifications"),
+        (name = "alerts", description = "Alert rules and alerts"),
     )
 )]
 pub struct ApiDoc;
@@ -92,8 +216,22 @@ pub fn api_router() -> Router<AppState> {
     Router::new()
         .merge(makers::routes())
         .merge(wallet::routes())
+        .merge(addresses::routes())
+        .merge(rescan::routes())
+        .merge(fees::routes())
+        .merge(transactions::routes())
+        .merge(export::routes())
+        .merge(labels::routes())
         .merge(fidelity::routes())
+        .merge(renewal::routes())
         .merge(monitoring::routes())
+        .merge(analytics::routes())
+        .merge(returns::routes())
+        .merge(recovery::routes())
+        .merge(events::routes())
+        .merge(webhooks::routes())
+        .merge(alerts::routes())
+        .merge(history::routes())
         .merge(bitcoind::routes())
         .merge(onboarding::routes())
         .route("/health", get(health_check))
@@ -124,10 +262,12 @@ async fn health_check(State(state): State<AppState>) -> Json<ApiResponse<HealthR
             async move {
                 let alive = state.lock().await.ping(&id).await.is_ok();
                 let is_server_running = state.lock().await.is_server_running(&id);
+                let rescan = state.lock().await.rescan_job(&id).map(Into::into);
                 MakerStatus {
                     id,
                     alive,
                     is_server_running,
+                    rescan,
                 }
             }
         })
@@ -1,5 +1,5 @@
 use std::convert::Infallible;
-use std::fs;
+use std::sync::Arc;
 use std::time::Duration;
 
 use axum::{
@@ -22,8 +22,11 @@ use crate::utils::log_writer::read_last_n_lines;
 use super::{
     dto::{
         ApiResponse, CombinedLogLine, MakerStatus, RpcStatusInfo, SwapHistoryDto, SwapReportDto,
-        UtxoInfo,
+        SwapReportPage,
     },
+    rescan,
+    swap_index::{self, ReportFilter},
+    wallet::UtxoContext,
     AppState,
 };
 
@@ -32,6 +35,11 @@ pub fn routes() -> Router<AppState> {
         .route("/makers/{id}/status", get(get_status))
         .route("/makers/{id}/swaps", get(get_swaps))
         .route("/makers/{id}/swap-reports", get(get_swap_reports))
+        .route("/makers/{id}/swap-reports/page", get(get_swap_report_page))
+        .route(
+            "/makers/{id}/swap-reports/by-id/{swap_id}",
+            get(get_swap_report),
+        )
         .route("/makers/{id}/logs", get(get_logs))
         .route("/makers/{id}/logs/stream", get(get_logs_stream))
         .route("/makers/{id}/logs/download", get(get_logs_download))
@@ -64,6 +72,7 @@ async fn get_status(
     }
     let alive = state.lock().await.ping(&id).await.is_ok();
     let is_server_running = state.lock().await.is_server_running(&id);
+    let rescan = rescan::rescan_status(&state, &id).await;
 
     (
         StatusCode::OK,
@@ -71,6 +80,7 @@ async fn get_status(
             id,
             alive,
             is_server_running,
+            rescan,
         })),
     )
 }
@@ -86,22 +96,24 @@ async fn get_status(
     params(("id" = String, Path, description = "Maker ID")),
     responses(
         (status = 200, description = "Swap history", body = ApiResponse<SwapHistoryDto>),
-        (status = 404, description = "Maker not found", body = ApiResponse<SwapHistoryDto>)
+        (status = 404, description = "Maker not found", body = ApiResponse<SwapHistoryDto>),
+        (status = 500, description = "UTXO conversion failure", body = ApiResponse<SwapHistoryDto>)
     )
 )]
 async fn get_swaps(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> (StatusCode, Json<ApiResponse<SwapHistoryDto>>) {
-    if !state.lock().await.has_maker(&id) {
+    let Some(context) = UtxoContext::load(&state, &id).await else {
         return (
             StatusCode::NOT_FOUND,
             Json(ApiResponse::err(format!("Maker '{id}' not found"))),
         );
-    }
+    };
 
-    let active = match state.lock().await.get_swap_utxos(&id).await {
-        Ok(MessageResponse::SwapUtxoResp { utxos }) => convert_utxos(&id, "active", utxos),
+    let response = state.lock().await.get_swap_utxos(&id).await;
+    let active = match response {
+        Ok(MessageResponse::SwapUtxoResp { utxos }) => utxos,
         Ok(other) => {
             warn!(
                 "Unexpected active swap UTXO response for maker '{id}': {:?}",
@@ -115,8 +127,9 @@ async fn get_swaps(
         }
     };
 
-    let completed = match state.lock().await.get_swept_swap_utxos(&id).await {
-        Ok(MessageResponse::SweptSwapUtxoResp { utxos }) => convert_utxos(&id, "completed", utxos),
+    let response = state.lock().await.get_swept_swap_utxos(&id).await;
+    let completed = match response {
+        Ok(MessageResponse::SweptSwapUtxoResp { utxos }) => utxos,
         Ok(other) => {
             warn!(
                 "Unexpected completed swap UTXO response for maker '{id}': {:?}",
@@ -130,12 +143,49 @@ async fn get_swaps(
         }
     };
 
+    let active = context.describe(active).await;
+    let completed = context.describe(completed).await;
+
     (
         StatusCode::OK,
         Json(ApiResponse::ok(SwapHistoryDto { active, completed })),
     )
 }
 
+#[derive(Deserialize)]
+struct SwapReportsQuery {
+    status: Option<String>,
+    role: Option<String>,
+    from: Option<u64>,
+    to: Option<u64>,
+    min_amount: Option<u64>,
+    max_amount: Option<u64>,
+    /// Substring of the swap ID or a txid
+    q: Option<String>,
+    cursor: Option<String>,
+    limit: Option<usize>,
+}
+
+/// Indexed swap reports of a maker, newest first, or `None` if the maker doesn't exist.
+async fn indexed_reports(
+    state: &AppState,
+    id: &str,
+) -> Option<Result<Vec<Arc<SwapReportDto>>, (StatusCode, String)>> {
+    let config = state.lock().await.get_maker_config(id).cloned()?;
+    let Some(data_dir) = config.data_directory else {
+        return Some(Ok(Vec::new()));
+    };
+    let id = id.to_string();
+    let result = tokio::task::spawn_blocking(move || {
+        swap_index::refresh(&data_dir.join("swap_reports"), &id)
+    })
+    .await;
+    Some(result.unwrap_or_else(|e| Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))))
+}
+
+/// List swap reports of a maker
+///
+/// Every report, newest first. Use `/swap-reports/page` to filter and page through them.
 #[utoipa::path(
     get,
     path = "/api/makers/{id}/swap-reports",
@@ -151,94 +201,143 @@ async fn get_swap_reports(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> (StatusCode, Json<ApiResponse<Vec<SwapReportDto>>>) {
-    let manager = state.lock().await;
-    let Some(config) = manager.get_maker_config(&id).cloned() else {
-        return (
+    match indexed_reports(&state, &id).await {
+        Some(Ok(reports)) => (
+            StatusCode::OK,
+            Json(ApiResponse::ok(
+                reports.iter().map(|report| (**report).clone()).collect(),
+            )),
+        ),
+        Some(Err((status, msg))) => (status, Json(ApiResponse::err(msg))),
+        None => (
             StatusCode::NOT_FOUND,
             Json(ApiResponse::err(format!("Maker '{id}' not found"))),
-        );
-    };
-    drop(manager);
+        ),
+    }
+}
 
-    let Some(data_dir) = config.data_directory else {
+/// Filter and page through the swap reports of a maker
+///
+/// Reports are returned newest first. All filters are optional and combine; `from`/`to`
+/// bound the end of the swap and `min_amount`/`max_amount` its incoming amount. Pages hold
+/// 50 reports unless `limit` (at most 500) says otherwise; pass the returned `next_cursor`
+/// as `cursor` to fetch the following page.
+#[utoipa::path(
+    get,
+    path = "/api/makers/{id}/swap-reports/page",
+    tag = "monitoring",
+    params(
+        ("id" = String, Path, description = "Maker ID"),
+        ("status" = Option<String>, Query, description = "Swap status, case-insensitive"),
+        ("role" = Option<String>, Query, description = "Swap role, case-insensitive"),
+        ("from" = Option<u64>, Query, description = "Earliest end time as a unix timestamp"),
+        ("to" = Option<u64>, Query, description = "Latest end time as a unix timestamp"),
+        ("min_amount" = Option<u64>, Query, description = "Minimum incoming amount in sats"),
+        ("max_amount" = Option<u64>, Query, description = "Maximum incoming amount in sats"),
+        ("q" = Option<String>, Query, description = "Text to find in the swap ID or any of its txids"),
+        ("cursor" = Option<String>, Query, description = "next_cursor of the previous page"),
+        ("limit" = Option<usize>, Query, description = "Page size, 1 to 500 (default 50)")
+    ),
+    responses(
+        (status = 200, description = "Page of swap reports", body = ApiResponse<SwapReportPage>),
+        (status = 400, description = "Invalid cursor or limit", body = ApiResponse<SwapReportPage>),
+        (status = 404, description = "Maker not found", body = ApiResponse<SwapReportPage>),
+        (status = 500, description = "Internal error", body = ApiResponse<SwapReportPage>)
+    )
+)]
+async fn get_swap_report_page(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<SwapReportsQuery>,
+) -> (StatusCode, Json<ApiResponse<SwapReportPage>>) {
+    let limit = query.limit.unwrap_or(swap_index::DEFAULT_PAGE_SIZE);
+    if !(1..=swap_index::MAX_PAGE_SIZE).contains(&limit) {
         return (
-            StatusCode::OK,
-            Json(ApiResponse::ok(Vec::<SwapReportDto>::new())),
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::err(format!(
+                "limit must be between 1 and {}",
+                swap_index::MAX_PAGE_SIZE
+            ))),
         );
+    }
+    let reports = match indexed_reports(&state, &id).await {
+        Some(Ok(reports)) => reports,
+        Some(Err((status, msg))) => return (status, Json(ApiResponse::err(msg))),
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+            )
+        }
     };
-
-    let reports_dir = data_dir.join("swap_reports");
-
-    let result = tokio::task::spawn_blocking(move || load_swap_reports(reports_dir, id)).await;
-
-    match result {
-        Ok(Ok(reports)) => (StatusCode::OK, Json(ApiResponse::ok(reports))),
-        Ok(Err((status, msg))) => (status, Json(ApiResponse::err(msg))),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::err(e.to_string())),
+    let filter = ReportFilter {
+        status: query.status,
+        role: query.role,
+        from: query.from,
+        to: query.to,
+        min_amount: query.min_amount,
+        max_amount: query.max_amount,
+        search: query.q.filter(|q| !q.is_empty()),
+    };
+    match swap_index::page(&reports, &filter, query.cursor.as_deref(), limit) {
+        Ok((page, next_cursor)) => (
+            StatusCode::OK,
+            Json(ApiResponse::ok(SwapReportPage {
+                reports: page.iter().map(|report| (**report).clone()).collect(),
+                next_cursor,
+            })),
         ),
+        Err(e) => (StatusCode::BAD_REQUEST, Json(ApiResponse::err(e))),
     }
 }
 
-fn load_swap_reports(
-    reports_dir: std::path::PathBuf,
-    id: String,
-) -> Result<Vec<SwapReportDto>, (StatusCode, String)> {
-    let entries = match fs::read_dir(&reports_dir) {
-        Ok(entries) => entries,
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
-        Err(e) => {
-            return Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("Failed to read swap reports: {e}"),
-            ))
+/// Get a single swap report of a maker
+#[utoipa::path(
+    get,
+    path = "/api/makers/{id}/swap-reports/by-id/{swap_id}",
+    tag = "monitoring",
+    params(
+        ("id" = String, Path, description = "Maker ID"),
+        ("swap_id" = String, Path, description = "Swap ID")
+    ),
+    responses(
+        (status = 200, description = "Swap report", body = ApiResponse<SwapReportDto>),
+        (status = 404, description = "Maker or swap report not found", body = ApiResponse<SwapReportDto>),
+        (status = 500, description = "Internal error", body = ApiResponse<SwapReportDto>)
+    )
+)]
+async fn get_swap_report(
+    State(state): State<AppState>,
+    Path((id, swap_id)): Path<(String, String)>,
+) -> (StatusCode, Json<ApiResponse<SwapReportDto>>) {
+    let reports = match indexed_reports(&state, &id).await {
+        Some(Ok(reports)) => reports,
+        Some(Err((status, msg))) => return (status, Json(ApiResponse::err(msg))),
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+            )
         }
     };
-
-    let mut reports = Vec::new();
-    for entry in entries.flatten() {
-        let path = entry.path();
-        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
-            continue;
-        }
-        match fs::read_to_string(&path)
-            .ok()
-            .and_then(|raw| serde_json::from_str::<SwapReportDto>(&raw).ok())
-        {
-            Some(report) => reports.push(report),
-            None => warn!(
-                "Failed to parse swap report for maker '{}' at {}",
-                id,
-                path.display()
-            ),
-        }
+    match reports.iter().find(|report| report.swap_id == swap_id) {
+        Some(report) => (StatusCode::OK, Json(ApiResponse::ok((**report).clone()))),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::err(format!(
+                "Swap report '{swap_id}' not found for maker '{id}'"
+            ))),
+        ),
     }
-
-    reports.sort_by(|a, b| b.end_timestamp.cmp(&a.end_timestamp));
-    Ok(reports)
 }
 
-fn convert_utxos<T>(id: &str, label: &str, utxos: Vec<T>) -> Vec<UtxoInfo>
-where
-    T: serde::Serialize,
-{
-    utxos
-        .into_iter()
-        .filter_map(|u| match serde_json::to_value(&u) {
-            Ok(value) => match serde_json::from_value::<UtxoInfo>(value) {
-                Ok(info) => Some(info),
-                Err(e) => {
-                    warn!("Failed to decode {label} swap UTXO for maker '{id}': {e}");
-                    None
-                }
-            },
-            Err(e) => {
-                warn!("Failed to serialize {label} swap UTXO for maker '{id}': {e}");
-                None
-            }
-        })
-        .collect()
+/// Swap reports in `reports_dir`, newest first. Blocking; served from the incremental index.
+pub(crate) fn load_swap_reports(
+    reports_dir: std::path::PathBuf,
+    id: String,
+) -> Result<Vec<SwapReportDto>, (StatusCode, String)> {
+    swap_index::refresh(&reports_dir, &id)
+        .map(|reports| reports.iter().map(|report| (**report).clone()).collect())
 }
 
 /// Get recent log entries for a maker.
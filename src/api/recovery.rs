@@ -0,0 +1,398 @@
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::get,
+    Json, Router,
+};
+use serde::Deserialize;
+
+use super::{
+    analytics::{is_successful, maker_swap_reports},
+    dto::{
+        ApiResponse, ErrorCategoryStats, PendingRecovery, RecoveryInfo, RecoveryInsights,
+        SwapErrorCategory, SwapReportDto, UtxoInfo,
+    },
+    metrics::sample_node,
+    wallet::UtxoContext,
+    AppState,
+};
+use crate::maker_manager::message::MessageResponse;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/makers/{id}/recovery", get(get_recovery))
+}
+
+#[derive(Deserialize)]
+struct RecoveryQuery {
+    from: Option<u64>,
+    to: Option<u64>,
+}
+
+/// Infers the broad cause of a failure from its error message. Keywords match whole words,
+/// so "refunds" is not read as "funds".
+fn categorize(error: Option<&str>) -> SwapErrorCategory {
+    let Some(error) = error.filter(|e| !e.trim().is_empty()) else {
+        return SwapErrorCategory::Unknown;
+    };
+    let error = error.to_ascii_lowercase();
+    let words: Vec<&str> = error
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    let mentions = |phrases: &[&str]| {
+        phrases.iter().any(|phrase| {
+            let phrase: Vec<&str> = phrase.split(' ').collect();
+            words.windows(phrase.len()).any(|w| w == phrase.as_slice())
+        })
+    };
+    if mentions(&["timeout", "timed out", "deadline"]) {
+        SwapErrorCategory::Timeout
+    } else if mentions(&["insufficient", "not enough", "funds", "liquidity"]) {
+        SwapErrorCategory::InsufficientFunds
+    } else if mentions(&[
+        "connect",
+        "connection",
+        "connected",
+        "disconnected",
+        "socks",
+        "socks5",
+        "tor",
+        "network",
+        "peer",
+        "broken pipe",
+        "refused",
+        "unreachable",
+    ]) {
+        SwapErrorCategory::Network
+    } else if mentions(&[
+        "broadcast",
+        "broadcasting",
+        "rpc",
+        "mempool",
+        "bitcoind",
+        "rejected",
+    ]) {
+        SwapErrorCategory::Broadcast
+    } else if mentions(&[
+        "signature",
+        "signatures",
+        "invalid",
+        "protocol",
+        "unexpected",
+        "mismatch",
+    ]) {
+        SwapErrorCategory::Protocol
+    } else {
+        SwapErrorCategory::Other
+    }
+}
+
+/// Whether a swap's contracts were spent back by a recovery transaction.
+fn is_recovered(report: &SwapReportDto) -> bool {
+    report
+        .recovery_txids
+        .as_ref()
+        .is_some_and(|txids| !txids.is_empty())
+        || report.status.to_ascii_lowercase().contains("recover")
+}
+
+/// Failure counts by category and the recovered swaps of `reports` (newest first).
+fn summarise(reports: &[SwapReportDto]) -> (Vec<ErrorCategoryStats>, Vec<RecoveryInfo>) {
+    let mut categories: HashMap<SwapErrorCategory, ErrorCategoryStats> = HashMap::new();
+    let mut recoveries = Vec::new();
+    for report in reports.iter().filter(|r| !is_successful(&r.status)) {
+        let category = categorize(report.error_message.as_deref());
+        let recovered = is_recovered(report);
+        let stats = categories
+            .entry(category)
+            .or_insert_with(|| ErrorCategoryStats {
+                category,
+                failed: 0,
+                recovered: 0,
+                last_seen: 0,
+                last_error: None,
+            });
+        if recovered {
+            stats.recovered += 1;
+        } else {
+            stats.failed += 1;
+        }
+        if report.end_timestamp >= stats.last_seen {
+            stats.last_seen = report.end_timestamp;
+            stats.last_error = report.error_message.clone();
+        }
+        if recovered {
+            recoveries.push(RecoveryInfo {
+                swap_id: report.swap_id.clone(),
+                role: report.role.clone(),
+                status: report.status.clone(),
+                end_timestamp: report.end_timestamp,
+                category,
+                error_message: report.error_message.clone(),
+                recovery_duration_seconds: report.recovery_duration_seconds,
+                contract_txids: report
+                    .incoming_contract_txid
+                    .iter()
+                    .chain(&report.outgoing_contract_txid)
+                    .cloned()
+                    .collect(),
+                recovery_txids: report.recovery_txids.clone().unwrap_or_default(),
+            });
+        }
+    }
+    let mut categories: Vec<_> = categories.into_values().collect();
+    categories.sort_by(|a, b| {
+        (b.failed + b.recovered)
+            .cmp(&(a.failed + a.recovered))
+            .then(a.category.cmp(&b.category))
+    });
+    recoveries.sort_by(|a, b| b.end_timestamp.cmp(&a.end_timestamp));
+    (categories, recoveries)
+}
+
+/// Counts down the timelock of each contract UTXO, soonest recoverable first.
+///
+/// Contracts use relative timelocks: the timelock path opens `timelock` blocks after the
+/// contract confirmed. The incoming and outgoing contracts of a swap have different
+/// timelocks, so each is read from the contract's own script.
+fn pending_recoveries(contracts: Vec<UtxoInfo>) -> Vec<PendingRecovery> {
+    let mut pending: Vec<_> = contracts
+        .into_iter()
+        .map(|utxo| {
+            let timelock = utxo.contract_timelock;
+            let confirmed = utxo.confirmations > 0;
+            PendingRecovery {
+                timelock,
+                blocks_remaining: timelock
+                    .filter(|_| confirmed)
+                    .map(|t| u32::from(t).saturating_sub(utxo.confirmations)),
+                spendable_at_height: timelock
+                    .zip(utxo.block_height)
+                    .map(|(t, height)| u64::from(height) + u64::from(t)),
+                utxo,
+            }
+        })
+        .collect();
+    pending.sort_by_key(|p| (p.blocks_remaining.is_none(), p.blocks_remaining));
+    pending
+}
+
+/// Failed swaps and recoveries of a maker
+///
+/// Groups the failed swaps in the maker's reports (ending between `from` and `to`) by the
+/// category of their error and lists every recovered swap with its contract and recovery
+/// txids. Contract outputs still in the wallet are listed with the blocks left until their
+/// timelock path can be spent; if the wallet can't list them, `pending_error` says why.
+#[utoipa::path(
+    get,
+    path = "/api/makers/{id}/recovery",
+    tag = "monitoring",
+    params(
+        ("id" = String, Path, description = "Maker ID"),
+        ("from" = Option<u64>, Query, description = "Earliest end time as a unix timestamp"),
+        ("to" = Option<u64>, Query, description = "Latest end time as a unix timestamp")
+    ),
+    responses(
+        (status = 200, description = "Failure and recovery insights", body = ApiResponse<RecoveryInsights>),
+        (status = 404, description = "Maker not found", body = ApiResponse<RecoveryInsights>),
+        (status = 500, description = "Failed to read swap reports", body = ApiResponse<RecoveryInsights>)
+    )
+)]
+async fn get_recovery(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<RecoveryQuery>,
+) -> (StatusCode, Json<ApiResponse<RecoveryInsights>>) {
+    let reports = match maker_swap_reports(&state, &id).await {
+        Some(Ok(reports)) => reports,
+        Some(Err((status, msg))) => return (status, Json(ApiResponse::err(msg))),
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+            )
+        }
+    };
+    let in_period: Vec<SwapReportDto> = reports
+        .iter()
+        .filter(|r| {
+            query.from.is_none_or(|from| r.end_timestamp >= from)
+                && query.to.is_none_or(|to| r.end_timestamp <= to)
+        })
+        .cloned()
+        .collect();
+    let (categories, recoveries) = summarise(&in_period);
+
+    let (pending, pending_error) = match contract_utxos(&state, &id).await {
+        Ok(contracts) => (pending_recoveries(contracts), None),
+        Err(e) => (Vec::new(), Some(e)),
+    };
+    let tip_height = match state.lock().await.get_config(&id) {
+        Some(config) => sample_node(config).await.height,
+        None => None,
+    };
+
+    (
+        StatusCode::OK,
+        Json(ApiResponse::ok(RecoveryInsights {
+            failed: categories.iter().map(|c| c.failed).sum(),
+            recovered: categories.iter().map(|c| c.recovered).sum(),
+            categories,
+            recoveries,
+            pending,
+            tip_height,
+            pending_error,
+        })),
+    )
+}
+
+async fn contract_utxos(state: &AppState, id: &String) -> Result<Vec<UtxoInfo>, String> {
+    let context = UtxoContext::load(state, id)
+        .await
+        .ok_or_else(|| format!("Maker '{id}' not found"))?;
+    let response = state.lock().await.get_contract_utxos(id).await;
+    match response {
+        Ok(MessageResponse::ContractUtxoResp { utxos }) => Ok(context.describe(utxos).await),
+        Ok(MessageResponse::ServerError(e)) => Err(e),
+        Ok(other) => Err(format!("Unexpected response: {other}")),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{categorize, pending_recoveries, summarise};
+    use crate::api::dto::{SwapErrorCategory, SwapReportDto, UtxoInfo, UtxoPoolDto};
+
+    fn report(swap_id: &str, end: u64, status: &str, error: Option<&str>) -> SwapReportDto {
+        serde_json::from_value(serde_json::json!({
+            "swap_id": swap_id,
+            "role": "Maker",
+            "status": status,
+            "swap_duration_seconds": 60.0,
+            "start_timestamp": end - 60,
+            "end_timestamp": end,
+            "network": "regtest",
+            "error_message": error,
+            "incoming_amount": 1_000,
+            "outgoing_amount": 1_000,
+            "fee_paid_or_earned": 0,
+            "incoming_contract_txid": format!("{swap_id}-in"),
+            "timelock": 20,
+            "total_maker_fees": 0,
+            "mining_fee": 0,
+            "fee_percentage": 0.0,
+            "input_utxos": []
+        }))
+        .unwrap()
+    }
+
+    fn contract(
+        swap_id: &str,
+        timelock: Option<u16>,
+        confirmations: u32,
+        block_height: Option<u32>,
+    ) -> UtxoInfo {
+        UtxoInfo {
+            outpoint: format!("{swap_id}:0"),
+            txid: swap_id.into(),
+            vout: 0,
+            addr: Some("bcrt1q".into()),
+            amount: 1_000,
+            confirmations,
+            script_type: "p2wsh".into(),
+            derivation_path: None,
+            block_height,
+            block_time: None,
+            utxo_type: UtxoPoolDto::Contract,
+            fidelity_index: None,
+            contract_timelock: timelock,
+            swap_id: Some(swap_id.into()),
+            label: None,
+            frozen: false,
+        }
+    }
+
+    #[test]
+    fn errors_are_categorized_by_message() {
+        assert_eq!(
+            categorize(Some("Peer timed out waiting for message")),
+            SwapErrorCategory::Timeout
+        );
+        assert_eq!(
+            categorize(Some("Connection refused")),
+            SwapErrorCategory::Network
+        );
+        assert_eq!(
+            categorize(Some("Insufficient funds for swap")),
+            SwapErrorCategory::InsufficientFunds
+        );
+        assert_eq!(
+            categorize(Some("Invalid contract signature")),
+            SwapErrorCategory::Protocol
+        );
+        assert_eq!(
+            categorize(Some("sendrawtransaction RPC error")),
+            SwapErrorCategory::Broadcast
+        );
+        assert_eq!(
+            categorize(Some("Not enough liquidity")),
+            SwapErrorCategory::InsufficientFunds
+        );
+        // Keywords only match whole words
+        assert_eq!(
+            categorize(Some("Swap refunds could not be signed")),
+            SwapErrorCategory::Other
+        );
+        assert_eq!(
+            categorize(Some("Peer disconnected")),
+            SwapErrorCategory::Network
+        );
+        assert_eq!(categorize(Some("boom")), SwapErrorCategory::Other);
+        assert_eq!(categorize(Some("  ")), SwapErrorCategory::Unknown);
+        assert_eq!(categorize(None), SwapErrorCategory::Unknown);
+    }
+
+    #[test]
+    fn failures_are_grouped_and_recoveries_linked() {
+        let mut recovered = report("r", 300, "Failed", Some("Peer timed out"));
+        recovered.recovery_txids = Some(vec!["rec1".into(), "rec2".into()]);
+        let reports = [
+            report("ok", 400, "Success", None),
+            recovered,
+            report("t", 200, "Failed", Some("Read timeout")),
+            report("n", 100, "Failed", Some("Connection refused")),
+        ];
+        let (categories, recoveries) = summarise(&reports);
+
+        assert_eq!(categories.len(), 2);
+        assert_eq!(categories[0].category, SwapErrorCategory::Timeout);
+        assert_eq!((categories[0].failed, categories[0].recovered), (1, 1));
+        assert_eq!(categories[0].last_seen, 300);
+        assert_eq!(categories[0].last_error.as_deref(), Some("Peer timed out"));
+        assert_eq!(categories[1].category, SwapErrorCategory::Network);
+
+        assert_eq!(recoveries.len(), 1);
+        assert_eq!(recoveries[0].swap_id, "r");
+        assert_eq!(recoveries[0].contract_txids, ["r-in"]);
+        assert_eq!(recoveries[0].recovery_txids, ["rec1", "rec2"]);
+    }
+
+    #[test]
+    fn pending_contracts_count_down_their_own_timelock() {
+        // Both contracts of swap "a", with different timelocks
+        let pending = pending_recoveries(vec![
+            contract("a", Some(20), 5, Some(1_000)),
+            contract("a", Some(40), 5, Some(1_000)),
+            contract("b", Some(20), 25, Some(990)),
+            contract("c", None, 0, None),
+        ]);
+        let remaining: Vec<_> = pending.iter().map(|p| p.blocks_remaining).collect();
+        assert_eq!(remaining, [Some(0), Some(15), Some(35), None]);
+        assert_eq!(pending[1].spendable_at_height, Some(1_020));
+        assert_eq!(pending[2].spendable_at_height, Some(1_040));
+        assert_eq!(pending[3].timelock, None);
+    }
+}
@@ -0,0 +1,372 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use super::{
+    dto::{ApiResponse, RenewalPolicyDto, RenewalRecordDto},
+    fees,
+    fidelity::{chain_tip, feerate_or_normal},
+    AppState,
+};
+use crate::maker_manager::{
+    message::MessageResponse,
+    renewal::{self, RenewalPolicy, RenewalRecord, RenewalStep},
+    unix_now, MakerConfig, MakerManager, MakerManagerError,
+};
+
+/// How often the watcher checks the makers' nodes for a new block.
+const RENEWAL_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/makers/{id}/fidelity/renewal",
+            get(get_policy).put(set_policy),
+        )
+        .route("/makers/{id}/fidelity/renewal/log", get(get_log))
+        .route("/makers/{id}/fidelity/renewal/evaluate", post(evaluate_now))
+}
+
+/// Get the fidelity bond renewal policy of a maker
+#[utoipa::path(
+    get,
+    path = "/api/makers/{id}/fidelity/renewal",
+    tag = "fidelity",
+    params(("id" = String, Path, description = "Maker ID")),
+    responses(
+        (status = 200, description = "Renewal policy", body = ApiResponse<RenewalPolicyDto>),
+        (status = 404, description = "Maker not found", body = ApiResponse<RenewalPolicyDto>)
+    )
+)]
+async fn get_policy(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<RenewalPolicyDto>>) {
+    match state.lock().await.renewal_policy(&id) {
+        Some(policy) => (StatusCode::OK, Json(ApiResponse::ok(policy.into()))),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+        ),
+    }
+}
+
+/// Set the fidelity bond renewal policy of a maker
+///
+/// `blocks_before_expiry` must be shorter than the maker's `fidelity_timelock`, otherwise
+/// every renewed bond would immediately fall inside the renewal window again.
+#[utoipa::path(
+    put,
+    path = "/api/makers/{id}/fidelity/renewal",
+    tag = "fidelity",
+    params(("id" = String, Path, description = "Maker ID")),
+    request_body = RenewalPolicyDto,
+    responses(
+        (status = 200, description = "Policy saved", body = ApiResponse<RenewalPolicyDto>),
+        (status = 400, description = "Invalid policy", body = ApiResponse<RenewalPolicyDto>),
+        (status = 404, description = "Maker not found", body = ApiResponse<RenewalPolicyDto>),
+        (status = 500, description = "Failed to save the policy", body = ApiResponse<RenewalPolicyDto>)
+    )
+)]
+async fn set_policy(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(body): Json<RenewalPolicyDto>,
+) -> (StatusCode, Json<ApiResponse<RenewalPolicyDto>>) {
+    let mut mgr = state.lock().await;
+    let Some(config) = mgr.get_config(&id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+        );
+    };
+    if body.blocks_before_expiry == 0 || body.blocks_before_expiry >= config.fidelity_timelock {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::err(format!(
+                "blocks_before_expiry must be between 1 and {} (the maker's fidelity_timelock)",
+                config.fidelity_timelock.saturating_sub(1)
+            ))),
+        );
+    }
+    if let Some(feerate) = body.feerate {
+        if let Err(e) = fees::validate_feerate(feerate) {
+            return (StatusCode::BAD_REQUEST, Json(ApiResponse::err(e)));
+        }
+    }
+    let policy = RenewalPolicy::from(body);
+    match mgr.set_renewal_policy(&id, policy.clone()) {
+        Ok(()) => (StatusCode::OK, Json(ApiResponse::ok(policy.into()))),
+        Err(MakerManagerError::NotFound(_)) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::err(e.to_string())),
+        ),
+    }
+}
+
+/// Audit log of automatic renewal actions, newest first
+#[utoipa::path(
+    get,
+    path = "/api/makers/{id}/fidelity/renewal/log",
+    tag = "fidelity",
+    params(("id" = String, Path, description = "Maker ID")),
+    responses(
+        (status = 200, description = "Renewal audit records", body = ApiResponse<Vec<RenewalRecordDto>>),
+        (status = 404, description = "Maker not found", body = ApiResponse<Vec<RenewalRecordDto>>)
+    )
+)]
+async fn get_log(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<Vec<RenewalRecordDto>>>) {
+    let mgr = state.lock().await;
+    if !mgr.has_maker(&id) {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+        );
+    }
+    let log = mgr.renewal_log(&id).into_iter().map(Into::into).collect();
+    (StatusCode::OK, Json(ApiResponse::ok(log)))
+}
+
+/// Evaluate the renewal policy of a maker against the current tip right away
+///
+/// Returns the actions taken, or planned in dry-run mode. Unchanged outcomes that are
+/// already in the audit log are returned but not logged again. A dry run fails when no
+/// feerate is set and none can be estimated.
+#[utoipa::path(
+    post,
+    path = "/api/makers/{id}/fidelity/renewal/evaluate",
+    tag = "fidelity",
+    params(("id" = String, Path, description = "Maker ID")),
+    responses(
+        (status = 200, description = "Actions taken or planned", body = ApiResponse<Vec<RenewalRecordDto>>),
+        (status = 404, description = "Maker not found", body = ApiResponse<Vec<RenewalRecordDto>>),
+        (status = 409, description = "An evaluation is already running", body = ApiResponse<Vec<RenewalRecordDto>>),
+        (status = 500, description = "Evaluation failed", body = ApiResponse<Vec<RenewalRecordDto>>),
+        (status = 503, description = "Bitcoin Core unreachable", body = ApiResponse<Vec<RenewalRecordDto>>)
+    )
+)]
+async fn evaluate_now(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<Vec<RenewalRecordDto>>>) {
+    let Some(config) = state.lock().await.get_config(&id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+        );
+    };
+    let tip = match chain_tip(config).await {
+        Ok(tip) => tip,
+        Err((status, msg)) => return (status, Json(ApiResponse::err(msg))),
+    };
+    match evaluate(&state, &id, tip).await {
+        Ok(records) => (
+            StatusCode::OK,
+            Json(ApiResponse::ok(
+                records.into_iter().map(Into::into).collect(),
+            )),
+        ),
+        Err(MakerManagerError::NotFound(_)) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+        ),
+        Err(e @ MakerManagerError::RenewalInProgress(_)) => {
+            (StatusCode::CONFLICT, Json(ApiResponse::err(e.to_string())))
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::err(e.to_string())),
+        ),
+    }
+}
+
+/// Spawns the task that evaluates every enabled renewal policy once per new block.
+pub fn spawn_watcher(state: AppState) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut evaluated: HashMap<String, u32> = HashMap::new();
+        let mut interval = tokio::time::interval(RENEWAL_POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            let ids = state.lock().await.renewal_makers();
+            for id in ids {
+                let Some(config) = state.lock().await.get_config(&id) else {
+                    continue;
+                };
+                let tip = match chain_tip(config).await {
+                    Ok(tip) => tip,
+                    Err((_, e)) => {
+                        debug!("Skipping fidelity renewal for maker '{id}': {e}");
+                        continue;
+                    }
+                };
+                if evaluated.get(&id) == Some(&tip) {
+                    continue;
+                }
+                evaluated.insert(id.clone(), tip);
+                match evaluate(&state, &id, tip).await {
+                    Ok(_) => {}
+                    // A manual evaluation is handling this block
+                    Err(MakerManagerError::RenewalInProgress(_)) => {
+                        debug!("Fidelity renewal for maker '{id}' is already running")
+                    }
+                    Err(e) => warn!("Fidelity renewal for maker '{id}' failed: {e}"),
+                }
+            }
+        }
+    })
+}
+
+/// Plans and, unless the policy is a dry run, executes a maker's renewal at `tip`.
+/// Every step is recorded in the maker's audit log.
+///
+/// Only one evaluation runs per maker at a time; a concurrent one fails with
+/// [`MakerManagerError::RenewalInProgress`].
+pub(crate) async fn evaluate(
+    state: &AppState,
+    id: &str,
+    tip: u32,
+) -> Result<Vec<RenewalRecord>, MakerManagerError> {
+    let id = id.to_string();
+    state.lock().await.begin_renewal(&id)?;
+    let result = run_renewal(state, &id, tip).await;
+    state.lock().await.finish_renewal(&id);
+    result.map_err(|e| MakerManagerError::Other(anyhow::anyhow!(e)))
+}
+
+/// Lists a maker's bonds and plans its renewal at `tip`.
+async fn plan_steps(
+    mgr: &MakerManager,
+    id: &String,
+    policy: &RenewalPolicy,
+    config: &MakerConfig,
+    tip: u32,
+) -> Result<Vec<RenewalStep>, String> {
+    let bonds = match mgr.list_fidelity(id).await {
+        Ok(MessageResponse::ListBonds(bonds)) => bonds,
+        Ok(other) => return Err(format!("Cannot list fidelity bonds: {other}")),
+        Err(e) => return Err(format!("Cannot list fidelity bonds: {e}")),
+    };
+    Ok(renewal::plan(
+        policy,
+        &bonds,
+        tip,
+        config.fidelity_amount,
+        config.fidelity_timelock,
+    ))
+}
+
+async fn run_renewal(
+    state: &AppState,
+    id: &String,
+    tip: u32,
+) -> Result<Vec<RenewalRecord>, String> {
+    let (policy, config) = {
+        let mgr = state.lock().await;
+        match (mgr.renewal_policy(id), mgr.get_config(id)) {
+            (Some(policy), Some(config)) => (policy, config),
+            _ => return Err(format!("Maker '{id}' not found")),
+        }
+    };
+    let steps = {
+        let mgr = state.lock().await;
+        plan_steps(&mgr, id, &policy, &config, tip).await?
+    };
+    if steps.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let feerate = match policy.feerate {
+        Some(feerate) => Ok(feerate),
+        None => feerate_or_normal(config.clone(), None, None)
+            .await
+            .map_err(|(_, e)| e),
+    };
+    if policy.dry_run {
+        if let Err(e) = &feerate {
+            return Err(format!("Cannot estimate the renewal feerate: {e}"));
+        }
+    }
+    let mut redeemed = false;
+    let mut records = Vec::new();
+    for step in steps {
+        let mut record = RenewalRecord {
+            at: unix_now(),
+            height: tip,
+            step,
+            dry_run: policy.dry_run,
+            feerate: feerate.as_ref().ok().copied(),
+            bond_index: match step {
+                RenewalStep::Redeem { index } => Some(index),
+                RenewalStep::Create { .. } => None,
+            },
+            txid: None,
+            error: None,
+        };
+        if !policy.dry_run {
+            // Re-plan under the lock the step is broadcast with, so a bond created or
+            // redeemed since planning, e.g. through the fidelity endpoints, isn't repeated
+            let mgr = state.lock().await;
+            let current = plan_steps(&mgr, id, &policy, &config, tip).await;
+            match (&feerate, &current, step) {
+                (_, Ok(current), _) if !current.contains(&step) => continue,
+                (_, Err(e), _) | (Err(e), _, _) => record.error = Some(e.clone()),
+                (Ok(feerate), _, RenewalStep::Redeem { index }) => {
+                    match mgr.redeem_fidelity(id, index, *feerate, tip).await {
+                        Ok(MessageResponse::FidelitySpend(txid)) => {
+                            record.txid = Some(txid);
+                            redeemed = true;
+                        }
+                        Ok(other) => record.error = Some(other.to_string()),
+                        Err(e) => record.error = Some(e.to_string()),
+                    }
+                }
+                // Redeemed coins are unconfirmed; fund the new bond on the next block
+                (Ok(_), _, RenewalStep::Create { .. }) if redeemed => continue,
+                (Ok(feerate), _, RenewalStep::Create { amount, locktime }) => {
+                    match mgr
+                        .create_fidelity(id, amount, locktime, *feerate, false)
+                        .await
+                    {
+                        Ok(MessageResponse::FidelityCreateResp(funding)) if funding.sufficient => {
+                            record.bond_index = funding.index;
+                            record.txid = funding.txid;
+                        }
+                        Ok(MessageResponse::FidelityCreateResp(funding)) => {
+                            record.error = Some(format!(
+                                "Insufficient spendable balance: bond needs {} sats plus ~{} sats fee, wallet has {} sats",
+                                funding.amount, funding.fee, funding.spendable
+                            ));
+                        }
+                        Ok(other) => record.error = Some(other.to_string()),
+                        Err(e) => record.error = Some(e.to_string()),
+                    }
+                }
+            }
+        }
+        let logged = state.lock().await.record_renewal(id, record.clone());
+        if logged {
+            match &record.error {
+                Some(e) => warn!("Maker '{id}': fidelity renewal {step:?} failed: {e}"),
+                None if record.dry_run => info!("Maker '{id}': fidelity renewal would {step:?}"),
+                None => info!("Maker '{id}': fidelity renewal {step:?} done"),
+            }
+        }
+        records.push(record);
+    }
+    Ok(records)
+}
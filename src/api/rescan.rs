@@ -0,0 +1,398 @@
+use std::time::Duration;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::post,
+    Json, Router,
+};
+use coinswap::bitcoind::bitcoincore_rpc::{
+    self,
+    json::{ImportMultiResult, ScanningDetails},
+    jsonrpc, Client, RpcApi,
+};
+use serde_json::{json, Value};
+use tracing::{info, warn};
+
+use super::{
+    dto::{ApiResponse, RescanRequest, RescanStatus},
+    wallet, AppState,
+};
+use crate::maker_manager::{message::MessageResponse, MakerConfig, MakerManagerError, RescanKind};
+
+/// How often Core is polled while a scan outlives the RPC call that started it.
+const SCAN_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/makers/{id}/rescan", post(rescan))
+        .route(
+            "/makers/{id}/descriptors/reimport",
+            post(reimport_descriptors),
+        )
+}
+
+/// Rescan the maker's Bitcoin Core wallet from a block height
+///
+/// Runs in the background; progress is reported under `rescan` in
+/// `GET /api/makers/{id}/status`. The coinswap wallet is synced once Core is done.
+#[utoipa::path(
+    post,
+    path = "/api/makers/{id}/rescan",
+    tag = "wallet",
+    params(("id" = String, Path, description = "Maker ID")),
+    request_body = RescanRequest,
+    responses(
+        (status = 202, description = "Rescan started", body = ApiResponse<RescanStatus>),
+        (status = 400, description = "Heights out of range", body = ApiResponse<RescanStatus>),
+        (status = 404, description = "Maker not found", body = ApiResponse<RescanStatus>),
+        (status = 409, description = "A rescan is already running", body = ApiResponse<RescanStatus>),
+        (status = 423, description = "Maker locked", body = ApiResponse<RescanStatus>),
+        (status = 503, description = "Bitcoin Core unreachable", body = ApiResponse<RescanStatus>)
+    )
+)]
+async fn rescan(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(body): Json<RescanRequest>,
+) -> (StatusCode, Json<ApiResponse<RescanStatus>>) {
+    let from_height = body.from_height.unwrap_or(0);
+    if body.stop_height.is_some_and(|stop| stop < from_height) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::err(
+                "stop_height must not be below from_height",
+            )),
+        );
+    }
+    let stop_height = body.stop_height;
+    start(state, id, RescanKind::Rescan, from_height, move |client| {
+        let result = client
+            .rescan_blockchain(Some(from_height as usize), stop_height.map(|h| h as usize))
+            .map(|_| ());
+        finish_scan(client, result)
+    })
+    .await
+}
+
+/// Re-import the wallet's descriptors into the Bitcoin Core watch-only wallet
+///
+/// Every descriptor Core holds for the wallet is imported again with a timestamp at
+/// `from_height`, which makes Core rescan them from there. Progress is reported like a rescan.
+#[utoipa::path(
+    post,
+    path = "/api/makers/{id}/descriptors/reimport",
+    tag = "wallet",
+    params(("id" = String, Path, description = "Maker ID")),
+    request_body = RescanRequest,
+    responses(
+        (status = 202, description = "Re-import started", body = ApiResponse<RescanStatus>),
+        (status = 400, description = "Height out of range", body = ApiResponse<RescanStatus>),
+        (status = 404, description = "Maker not found", body = ApiResponse<RescanStatus>),
+        (status = 409, description = "A rescan is already running", body = ApiResponse<RescanStatus>),
+        (status = 423, description = "Maker locked", body = ApiResponse<RescanStatus>),
+        (status = 503, description = "Bitcoin Core unreachable", body = ApiResponse<RescanStatus>)
+    )
+)]
+async fn reimport_descriptors(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(body): Json<RescanRequest>,
+) -> (StatusCode, Json<ApiResponse<RescanStatus>>) {
+    let from_height = body.from_height.unwrap_or(0);
+    start(
+        state,
+        id,
+        RescanKind::Reimport,
+        from_height,
+        move |client| {
+            let result = reimport(client, from_height);
+            finish_scan(client, result)
+        },
+    )
+    .await
+}
+
+/// Validates the start height, records the job and runs `work` in the background.
+async fn start<F>(
+    state: AppState,
+    id: String,
+    kind: RescanKind,
+    from_height: u64,
+    work: F,
+) -> (StatusCode, Json<ApiResponse<RescanStatus>>)
+where
+    F: FnOnce(&Client) -> Result<(), String> + Send + 'static,
+{
+    let Some(config) = state.lock().await.get_config(&id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+        );
+    };
+    let tip = {
+        let config = config.clone();
+        let id = id.clone();
+        tokio::task::spawn_blocking(move || {
+            config
+                .wallet_rpc_client(&id)
+                .and_then(|client| Ok(client.get_block_count()?))
+                .map_err(|e| e.to_string())
+        })
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|r| r)
+    };
+    let tip = match tip {
+        Ok(tip) => tip,
+        Err(e) => {
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(ApiResponse::err(format!("Bitcoin Core unreachable: {e}"))),
+            )
+        }
+    };
+    if from_height > tip {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::err(format!(
+                "from_height {from_height} is above the chain tip {tip}"
+            ))),
+        );
+    }
+
+    let job = match state.lock().await.begin_rescan(&id, kind, from_height) {
+        Ok(job) => job,
+        Err(MakerManagerError::NotFound(_)) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+            )
+        }
+        Err(e @ MakerManagerError::RescanInProgress(_)) => {
+            return (StatusCode::CONFLICT, Json(ApiResponse::err(e.to_string())))
+        }
+        Err(e @ MakerManagerError::Locked(_)) => {
+            return (StatusCode::LOCKED, Json(ApiResponse::err(e.to_string())))
+        }
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::err(e.to_string())),
+            )
+        }
+    };
+    info!("Maker '{id}': {kind:?} from height {from_height} started");
+    tokio::spawn(run(state, id, config, work));
+    (StatusCode::ACCEPTED, Json(ApiResponse::ok(job.into())))
+}
+
+/// Runs the Core side of a repair, then syncs the coinswap wallet so it picks up
+/// whatever Core found.
+async fn run<F>(state: AppState, id: String, config: MakerConfig, work: F)
+where
+    F: FnOnce(&Client) -> Result<(), String> + Send + 'static,
+{
+    let scanned = {
+        let id = id.clone();
+        tokio::task::spawn_blocking(move || {
+            let client = config.wallet_rpc_client(&id).map_err(|e| e.to_string())?;
+            work(&client)
+        })
+        .await
+        .unwrap_or_else(|e| Err(e.to_string()))
+    };
+
+    let result = match scanned {
+        Ok(()) => {
+            let synced = state.lock().await.sync_wallet(&id).await;
+            match synced {
+                Ok(MessageResponse::ServerError(e)) => Err(format!("Wallet sync failed: {e}")),
+                Ok(_) => Ok(()),
+                Err(e) => Err(format!("Wallet sync failed: {e}")),
+            }
+        }
+        Err(e) => Err(e),
+    };
+
+    match &result {
+        Ok(()) => {
+            info!("Maker '{id}': rescan finished");
+            // Core forgets coin locks on rescans of a fresh node; re-apply the frozen set
+            wallet::relock_frozen(&state, &id).await;
+        }
+        Err(e) => warn!("Maker '{id}': rescan failed: {e}"),
+    }
+    state.lock().await.finish_rescan(&id, result.err());
+}
+
+/// Imports every descriptor of the wallet again, timestamped at `from_height`.
+fn reimport(client: &Client, from_height: u64) -> Result<(), CoreError> {
+    let timestamp = if from_height == 0 {
+        0
+    } else {
+        let hash = client.get_block_hash(from_height).map_err(before_scan)?;
+        client
+            .get_block_header_info(&hash)
+            .map_err(before_scan)?
+            .time as u64
+    };
+    let listed: Value = client.call("listdescriptors", &[]).map_err(before_scan)?;
+    let requests: Vec<Value> = listed["descriptors"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .map(|descriptor| import_request(descriptor, timestamp))
+        .collect();
+    if requests.is_empty() {
+        return Err(CoreError::Other(
+            "Bitcoin Core holds no descriptors for this wallet; sync the wallet first".to_string(),
+        ));
+    }
+    let results: Vec<ImportMultiResult> =
+        client.call("importdescriptors", &[Value::Array(requests)])?;
+    let failures: Vec<String> = results
+        .into_iter()
+        .filter(|r| !r.success)
+        .map(|r| {
+            r.error
+                .map(|e| e.message)
+                .unwrap_or_else(|| "unknown error".to_string())
+        })
+        .collect();
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(CoreError::Other(format!(
+            "{} descriptors failed to import: {}",
+            failures.len(),
+            failures.join("; ")
+        )))
+    }
+}
+
+/// Turns a `listdescriptors` entry back into an `importdescriptors` request.
+fn import_request(descriptor: &Value, timestamp: u64) -> Value {
+    let mut request = json!({
+        "desc": descriptor["desc"],
+        "timestamp": timestamp,
+    });
+    for (from, to) in [
+        ("active", "active"),
+        ("internal", "internal"),
+        ("range", "range"),
+        ("next", "next_index"),
+    ] {
+        if let Some(value) = descriptor.get(from) {
+            request[to] = value.clone();
+        }
+    }
+    request
+}
+
+/// Errors from the Core side of a repair.
+enum CoreError {
+    Rpc(coinswap::bitcoind::bitcoincore_rpc::Error),
+    Other(String),
+}
+
+impl From<coinswap::bitcoind::bitcoincore_rpc::Error> for CoreError {
+    fn from(e: coinswap::bitcoind::bitcoincore_rpc::Error) -> Self {
+        Self::Rpc(e)
+    }
+}
+
+/// Failures of the calls preceding a scan are never mistaken for a scan timeout.
+fn before_scan(e: bitcoincore_rpc::Error) -> CoreError {
+    CoreError::Other(e.to_string())
+}
+
+/// Scans block the RPC call that starts them until Core is done, which for long scans
+/// outlives the client's HTTP timeout. An error answered by Core means the scan did not
+/// run; any other failure (a timeout) means it did, so wait until Core is no longer
+/// scanning. A scan that already ended by the first poll counts as finished.
+fn finish_scan<E: Into<CoreError>>(client: &Client, result: Result<(), E>) -> Result<(), String> {
+    let error = match result.map_err(Into::into) {
+        Ok(()) => return Ok(()),
+        Err(CoreError::Other(e)) => return Err(e),
+        Err(CoreError::Rpc(e @ bitcoincore_rpc::Error::JsonRpc(jsonrpc::Error::Rpc(_)))) => {
+            return Err(e.to_string())
+        }
+        Err(CoreError::Rpc(e)) => e,
+    };
+    loop {
+        match client.get_wallet_info() {
+            Ok(info) if matches!(info.scanning, Some(ScanningDetails::Scanning { .. })) => {
+                std::thread::sleep(SCAN_POLL_INTERVAL);
+            }
+            Ok(_) => return Ok(()),
+            Err(_) => return Err(error.to_string()),
+        }
+    }
+}
+
+/// Latest rescan of a maker, with Core's progress while it is still running.
+pub(crate) async fn rescan_status(state: &AppState, id: &str) -> Option<RescanStatus> {
+    let (job, config) = {
+        let mgr = state.lock().await;
+        let id = id.to_string();
+        (mgr.rescan_job(&id)?, mgr.get_config(&id)?)
+    };
+    let mut status = RescanStatus::from(job);
+    if status.running {
+        let id = id.to_string();
+        status.progress = tokio::task::spawn_blocking(move || {
+            match config
+                .wallet_rpc_client(&id)
+                .ok()?
+                .get_wallet_info()
+                .ok()?
+                .scanning
+            {
+                Some(ScanningDetails::Scanning { progress, .. }) => Some(progress),
+                _ => None,
+            }
+        })
+        .await
+        .ok()
+        .flatten();
+    }
+    Some(status)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::import_request;
+
+    #[test]
+    fn import_request_keeps_descriptor_metadata() {
+        let listed = json!({
+            "desc": "wpkh([d34db33f/84h/1h/0h]tpubD6NzVbkrYhZ4X/0/*)#abcd1234",
+            "timestamp": 1700000000,
+            "active": true,
+            "internal": false,
+            "range": [0, 999],
+            "next": 12
+        });
+        assert_eq!(
+            import_request(&listed, 1650000000),
+            json!({
+                "desc": "wpkh([d34db33f/84h/1h/0h]tpubD6NzVbkrYhZ4X/0/*)#abcd1234",
+                "timestamp": 1650000000,
+                "active": true,
+                "internal": false,
+                "range": [0, 999],
+                "next_index": 12
+            })
+        );
+
+        let imported = json!({ "desc": "addr(bcrt1qxyz)#abcd1234", "timestamp": 1700000000 });
+        assert_eq!(
+            import_request(&imported, 0),
+            json!({ "desc": "addr(bcrt1qxyz)#abcd1234", "timestamp": 0 })
+        );
+    }
+}
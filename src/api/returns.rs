@@ -0,0 +1,252 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::get,
+    Json, Router,
+};
+use serde::Deserialize;
+
+use super::{
+    analytics::{aggregate, maker_swap_reports, window_bounds, Period},
+    dto::{AnalyticsWindow, ApiResponse, ReturnOnCapital},
+    AppState,
+};
+use crate::maker_manager::{
+    capital::{self, CapitalAverage, CapitalSample},
+    message::MessageResponse,
+    unix_now,
+};
+
+const YEAR_SECS: f64 = 365.0 * 86_400.0;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/makers/{id}/returns", get(get_returns))
+}
+
+#[derive(Deserialize)]
+struct ReturnsQuery {
+    window: Option<AnalyticsWindow>,
+    from: Option<u64>,
+    to: Option<u64>,
+    /// Annual rate to charge fidelity capital at
+    opportunity_rate: Option<f64>,
+}
+
+/// Current committed balances of a maker, if its wallet answers.
+async fn current_capital(state: &AppState, id: &String) -> Option<CapitalSample> {
+    let balances = state.lock().await.get_balances(id).await;
+    match balances {
+        Ok(MessageResponse::TotalBalanceResp(b)) => Some(CapitalSample {
+            at: unix_now(),
+            regular: b.regular.to_sat(),
+            swap: b.swap.to_sat(),
+            fidelity: b.fidelity.to_sat(),
+        }),
+        _ => None,
+    }
+}
+
+/// Annualises `profit` earned on `capital` over `period_secs`.
+fn annualise(profit: i64, capital: f64, period_secs: u64) -> Option<f64> {
+    (capital > 0.0 && period_secs > 0)
+        .then(|| profit as f64 / capital * YEAR_SECS / period_secs as f64)
+}
+
+/// Yields and fidelity opportunity cost of `net_profit` earned over `from..to`.
+fn return_on_capital(
+    net_profit: i64,
+    swaps: u64,
+    capital: CapitalAverage,
+    from: u64,
+    to: u64,
+    opportunity_rate: Option<f64>,
+) -> ReturnOnCapital {
+    let period_secs = to - from;
+    let liquid_yield = annualise(net_profit, capital.liquid(), period_secs);
+    let opportunity_rate = opportunity_rate.or(liquid_yield);
+    // Losing liquidity doesn't make locking coins profitable
+    let fidelity_opportunity_cost = opportunity_rate
+        .map_or(0.0, |rate| {
+            capital.fidelity * rate.max(0.0) * period_secs as f64 / YEAR_SECS
+        })
+        .round() as u64;
+    ReturnOnCapital {
+        from,
+        to,
+        swaps,
+        net_profit,
+        avg_regular: capital.regular as u64,
+        avg_swap: capital.swap as u64,
+        avg_fidelity: capital.fidelity as u64,
+        avg_committed: capital.total() as u64,
+        samples: 0,
+        estimated: false,
+        annualised_yield: annualise(net_profit, capital.total(), period_secs),
+        liquid_yield,
+        opportunity_rate,
+        fidelity_opportunity_cost,
+        profit_after_fidelity_cost: net_profit - fidelity_opportunity_cost as i64,
+    }
+}
+
+/// Return on committed capital of a maker
+///
+/// Divides the net swap profit of the period (fees earned minus mining fees, from swap
+/// reports) by the time-weighted average of the maker's regular, swap and fidelity balances,
+/// as recorded in its history, and annualises the result. The fidelity bond's opportunity cost charges
+/// the average bonded capital at `opportunity_rate`, by default the yield the maker earns on
+/// its liquid balances. Without samples in the period the current balances are used.
+#[utoipa::path(
+    get,
+    path = "/api/makers/{id}/returns",
+    tag = "monitoring",
+    params(
+        ("id" = String, Path, description = "Maker ID"),
+        ("window" = Option<AnalyticsWindow>, Query, description = "Reporting period (default month)"),
+        ("from" = Option<u64>, Query, description = "Period start as a unix timestamp, required for the custom window"),
+        ("to" = Option<u64>, Query, description = "Period end as a unix timestamp (default now)"),
+        ("opportunity_rate" = Option<f64>, Query, description = "Annual rate charged on fidelity capital, e.g. 0.05")
+    ),
+    responses(
+        (status = 200, description = "Return on capital", body = ApiResponse<ReturnOnCapital>),
+        (status = 400, description = "Invalid period or rate", body = ApiResponse<ReturnOnCapital>),
+        (status = 404, description = "Maker not found", body = ApiResponse<ReturnOnCapital>),
+        (status = 500, description = "Failed to read swap reports", body = ApiResponse<ReturnOnCapital>),
+        (status = 503, description = "No balance samples and the maker's wallet is unavailable", body = ApiResponse<ReturnOnCapital>)
+    )
+)]
+async fn get_returns(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<ReturnsQuery>,
+) -> (StatusCode, Json<ApiResponse<ReturnOnCapital>>) {
+    let window = query.window.unwrap_or(AnalyticsWindow::Month);
+    let (from, to) = match window_bounds(window, query.from, query.to, unix_now()) {
+        Ok(bounds) => bounds,
+        Err(e) => return (StatusCode::BAD_REQUEST, Json(ApiResponse::err(e))),
+    };
+    if query
+        .opportunity_rate
+        .is_some_and(|rate| !rate.is_finite() || rate < 0.0)
+    {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::err(
+                "opportunity_rate must be a non-negative number",
+            )),
+        );
+    }
+    let reports = match maker_swap_reports(&state, &id).await {
+        Some(Ok(reports)) => reports,
+        Some(Err((status, msg))) => return (status, Json(ApiResponse::err(msg))),
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+            )
+        }
+    };
+    let period = Period {
+        from,
+        to,
+        bucket_secs: to - from,
+    };
+    let (stats, _) = aggregate(&reports, period);
+
+    let samples: Vec<CapitalSample> = state
+        .lock()
+        .await
+        .history_points(&id, from, to)
+        .iter()
+        .filter_map(CapitalSample::from_history)
+        .collect();
+    let in_period = samples
+        .iter()
+        .filter(|sample| (from..to).contains(&sample.at))
+        .count();
+    let (capital, estimated) = match capital::average(&samples, from, to) {
+        Some(capital) => (capital, false),
+        None => match current_capital(&state, &id).await {
+            Some(now) => (
+                CapitalAverage {
+                    regular: now.regular as f64,
+                    swap: now.swap as f64,
+                    fidelity: now.fidelity as f64,
+                    covered_secs: 0,
+                },
+                true,
+            ),
+            None => {
+                return (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    Json(ApiResponse::err(format!(
+                        "No balance samples for maker '{id}' in this period and its wallet is unavailable"
+                    ))),
+                )
+            }
+        },
+    };
+
+    let mut returns = return_on_capital(
+        stats.net_profit,
+        stats.swaps,
+        capital,
+        from,
+        to,
+        query.opportunity_rate,
+    );
+    returns.samples = in_period;
+    returns.estimated = estimated;
+    (StatusCode::OK, Json(ApiResponse::ok(returns)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::return_on_capital;
+    use crate::maker_manager::capital::CapitalAverage;
+
+    const DAYS_30: u64 = 30 * 86_400;
+
+    fn capital(regular: f64, fidelity: f64) -> CapitalAverage {
+        CapitalAverage {
+            regular,
+            swap: 0.0,
+            fidelity,
+            covered_secs: DAYS_30,
+        }
+    }
+
+    #[test]
+    fn yields_are_annualised_over_committed_and_liquid_capital() {
+        // 10k sats over 30 days on 1M liquid + 1M bonded
+        let r = return_on_capital(
+            10_000,
+            4,
+            capital(1_000_000.0, 1_000_000.0),
+            0,
+            DAYS_30,
+            None,
+        );
+        let liquid = 10_000.0 / 1_000_000.0 * 365.0 / 30.0;
+        assert!((r.liquid_yield.unwrap() - liquid).abs() < 1e-9);
+        assert!((r.annualised_yield.unwrap() - liquid / 2.0).abs() < 1e-9);
+        // Bonded coins would have earned as much as the liquid ones
+        assert_eq!(r.opportunity_rate, r.liquid_yield);
+        assert_eq!(r.fidelity_opportunity_cost, 10_000);
+        assert_eq!(r.profit_after_fidelity_cost, 0);
+        assert_eq!(r.avg_committed, 2_000_000);
+    }
+
+    #[test]
+    fn explicit_rate_and_losses() {
+        let r = return_on_capital(0, 0, capital(0.0, 3_650_000.0), 0, DAYS_30, Some(0.05));
+        assert_eq!(r.liquid_yield, None);
+        assert_eq!(r.fidelity_opportunity_cost, 15_000);
+        assert_eq!(r.profit_after_fidelity_cost, -15_000);
+
+        let r = return_on_capital(-500, 1, capital(1_000_000.0, 1_000_000.0), 0, DAYS_30, None);
+        assert!(r.liquid_yield.unwrap() < 0.0);
+        assert_eq!(r.fidelity_opportunity_cost, 0);
+        assert_eq!(r.profit_after_fidelity_cost, -500);
+    }
+}
@@ -0,0 +1,379 @@
+//! Incremental index of the swap reports in a maker's `swap_reports/` directory.
+//!
+//! Reports are written once per swap and never change. A refresh is a single `stat` of
+//! the directory while its modification time stands still; otherwise it lists the
+//! directory and only parses files that are new or whose size or modification time
+//! changed.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, SystemTime};
+
+use axum::http::StatusCode;
+use tracing::warn;
+
+use super::dto::SwapReportDto;
+
+/// How long after its last change a directory's listing is trusted, since a file created
+/// within the same modification-time tick as the listing wouldn't move the time again.
+const SETTLE_TIME: Duration = Duration::from_secs(2);
+
+/// Page size when a request doesn't give one.
+pub(crate) const DEFAULT_PAGE_SIZE: usize = 50;
+/// Largest page a request may ask for.
+pub(crate) const MAX_PAGE_SIZE: usize = 500;
+
+struct IndexedFile {
+    modified: Option<SystemTime>,
+    len: u64,
+    /// `None` if the file could not be parsed, so it is not retried until it changes
+    report: Option<Arc<SwapReportDto>>,
+}
+
+#[derive(Default)]
+struct DirIndex {
+    /// Modification time of the directory when it was last listed
+    modified: Option<SystemTime>,
+    listed_at: Option<SystemTime>,
+    files: HashMap<PathBuf, IndexedFile>,
+    /// Parsed reports, newest first
+    reports: Vec<Arc<SwapReportDto>>,
+}
+
+impl DirIndex {
+    /// Whether the listing still matches a directory last modified at `modified`. Reports
+    /// that failed to parse may be half written, so their directory is listed again.
+    fn is_current(&self, modified: Option<SystemTime>) -> bool {
+        let settled = match (modified, self.listed_at) {
+            (Some(modified), Some(listed_at)) => modified + SETTLE_TIME < listed_at,
+            _ => false,
+        };
+        settled
+            && self.modified == modified
+            && self.files.values().all(|file| file.report.is_some())
+    }
+}
+
+fn indexes() -> &'static Mutex<HashMap<PathBuf, DirIndex>> {
+    static INDEXES: OnceLock<Mutex<HashMap<PathBuf, DirIndex>>> = OnceLock::new();
+    INDEXES.get_or_init(Default::default)
+}
+
+/// Drops the index of `reports_dir`, e.g. once its maker is deleted.
+pub(crate) fn forget(reports_dir: &Path) {
+    if let Ok(mut indexes) = indexes().lock() {
+        indexes.remove(reports_dir);
+    }
+}
+
+/// Brings the index of `reports_dir` up to date and returns its reports, newest first.
+/// Blocking; call from `spawn_blocking`.
+pub(crate) fn refresh(
+    reports_dir: &Path,
+    id: &str,
+) -> Result<Vec<Arc<SwapReportDto>>, (StatusCode, String)> {
+    let read_error = |e: std::io::Error| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to read swap reports: {e}"),
+        )
+    };
+    let dir_modified = match fs::metadata(reports_dir) {
+        Ok(meta) => meta.modified().ok(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            forget(reports_dir);
+            return Ok(Vec::new());
+        }
+        Err(e) => return Err(read_error(e)),
+    };
+    let mut indexes = indexes()
+        .lock()
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if let Some(index) = indexes.get(reports_dir) {
+        if index.is_current(dir_modified) {
+            return Ok(index.reports.clone());
+        }
+    }
+
+    let listed_at = SystemTime::now();
+    let entries = match fs::read_dir(reports_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            indexes.remove(reports_dir);
+            return Ok(Vec::new());
+        }
+        Err(e) => return Err(read_error(e)),
+    };
+    let mut old = indexes.remove(reports_dir).unwrap_or_default().files;
+    let mut files = HashMap::with_capacity(old.len());
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        let (modified, len) = (meta.modified().ok(), meta.len());
+        let file = match old.remove(&path) {
+            Some(file) if file.modified == modified && file.len == len => file,
+            _ => {
+                let report = fs::read_to_string(&path)
+                    .ok()
+                    .and_then(|raw| serde_json::from_str::<SwapReportDto>(&raw).ok());
+                if report.is_none() {
+                    warn!(
+                        "Failed to parse swap report for maker '{}' at {}",
+                        id,
+                        path.display()
+                    );
+                }
+                IndexedFile {
+                    modified,
+                    len,
+                    report: report.map(Arc::new),
+                }
+            }
+        };
+        files.insert(path, file);
+    }
+
+    let mut reports: Vec<_> = files
+        .values()
+        .filter_map(|file| file.report.clone())
+        .collect();
+    reports.sort_by(|a, b| sort_key(b).cmp(&sort_key(a)));
+    indexes.insert(
+        reports_dir.to_path_buf(),
+        DirIndex {
+            modified: dir_modified,
+            listed_at: Some(listed_at),
+            files,
+            reports: reports.clone(),
+        },
+    );
+    Ok(reports)
+}
+
+/// Position of a report in the newest-first order; also its pagination cursor.
+fn sort_key(report: &SwapReportDto) -> (u64, &str) {
+    (report.end_timestamp, report.swap_id.as_str())
+}
+
+pub(crate) fn cursor(report: &SwapReportDto) -> String {
+    format!("{}:{}", report.end_timestamp, report.swap_id)
+}
+
+fn parse_cursor(cursor: &str) -> Option<(u64, &str)> {
+    let (end, swap_id) = cursor.split_once(':')?;
+    Some((end.parse().ok()?, swap_id))
+}
+
+/// Criteria a report must meet to be listed. Unset fields match everything.
+#[derive(Debug, Default)]
+pub(crate) struct ReportFilter {
+    pub status: Option<String>,
+    pub role: Option<String>,
+    /// Unix timestamps bounding `end_timestamp`, inclusive
+    pub from: Option<u64>,
+    pub to: Option<u64>,
+    /// Bounds on `incoming_amount` in sats, inclusive
+    pub min_amount: Option<u64>,
+    pub max_amount: Option<u64>,
+    /// Case-insensitive substring of the swap ID or any of the swap's txids
+    pub search: Option<String>,
+}
+
+impl ReportFilter {
+    pub fn matches(&self, report: &SwapReportDto) -> bool {
+        let eq = |want: &Option<String>, have: &str| {
+            want.as_ref()
+                .is_none_or(|want| want.eq_ignore_ascii_case(have))
+        };
+        eq(&self.status, &report.status)
+            && eq(&self.role, &report.role)
+            && self.from.is_none_or(|from| report.end_timestamp >= from)
+            && self.to.is_none_or(|to| report.end_timestamp <= to)
+            && self
+                .min_amount
+                .is_none_or(|min| report.incoming_amount >= min)
+            && self
+                .max_amount
+                .is_none_or(|max| report.incoming_amount <= max)
+            && self.search.as_ref().is_none_or(|q| {
+                let q = q.to_ascii_lowercase();
+                txids(report).any(|id| id.to_ascii_lowercase().contains(&q))
+            })
+    }
+}
+
+/// The swap ID and every txid a report mentions.
+fn txids(report: &SwapReportDto) -> impl Iterator<Item = &str> {
+    std::iter::once(report.swap_id.as_str())
+        .chain(report.incoming_contract_txid.as_deref())
+        .chain(report.outgoing_contract_txid.as_deref())
+        .chain(report.funding_txids.iter().flatten().map(String::as_str))
+        .chain(report.recovery_txids.iter().flatten().map(String::as_str))
+}
+
+/// One page of at most `limit` of `reports` (newest first) matching `filter`, starting
+/// after `after`. Returns the page and the cursor of its last report if more follow.
+pub(crate) fn page(
+    reports: &[Arc<SwapReportDto>],
+    filter: &ReportFilter,
+    after: Option<&str>,
+    limit: usize,
+) -> Result<(Vec<Arc<SwapReportDto>>, Option<String>), String> {
+    let after = after
+        .map(|cursor| parse_cursor(cursor).ok_or_else(|| format!("Invalid cursor '{cursor}'")))
+        .transpose()?;
+    let mut matching = reports
+        .iter()
+        .filter(|report| after.is_none_or(|after| sort_key(report) < after))
+        .filter(|report| filter.matches(report));
+    let page: Vec<_> = matching.by_ref().take(limit).cloned().collect();
+    let next = match (page.last(), matching.next()) {
+        (Some(last), Some(_)) => Some(cursor(last)),
+        _ => None,
+    };
+    Ok((page, next))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use super::{forget, indexes, page, refresh, DirIndex, IndexedFile, ReportFilter};
+    use crate::api::dto::SwapReportDto;
+
+    fn report(swap_id: &str, end: u64, status: &str, amount: u64) -> SwapReportDto {
+        serde_json::from_value(serde_json::json!({
+            "swap_id": swap_id,
+            "role": "Maker",
+            "status": status,
+            "swap_duration_seconds": 60.0,
+            "start_timestamp": end - 60,
+            "end_timestamp": end,
+            "network": "regtest",
+            "incoming_amount": amount,
+            "outgoing_amount": amount,
+            "fee_paid_or_earned": 100,
+            "incoming_contract_txid": format!("{swap_id}aa"),
+            "timelock": 20,
+            "total_maker_fees": 0,
+            "mining_fee": 0,
+            "fee_percentage": 0.0,
+            "input_utxos": []
+        }))
+        .unwrap()
+    }
+
+    /// A fresh directory per test, so parallel tests and reruns never share an index.
+    fn temp_dir(name: &str) -> PathBuf {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let dir =
+            std::env::temp_dir().join(format!("maker-swap-{name}-{}-{n}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn ids(reports: &[Arc<SwapReportDto>]) -> Vec<&str> {
+        reports.iter().map(|r| r.swap_id.as_str()).collect()
+    }
+
+    #[test]
+    fn pages_follow_the_cursor() {
+        let reports: Vec<_> = [
+            report("c", 300, "Success", 1_000),
+            report("b", 200, "Failed", 2_000),
+            report("a", 200, "Success", 3_000),
+        ]
+        .into_iter()
+        .map(Arc::new)
+        .collect();
+        let all = ReportFilter::default();
+
+        let (first, next) = page(&reports, &all, None, 2).unwrap();
+        assert_eq!(ids(&first), ["c", "b"]);
+        assert_eq!(next.as_deref(), Some("200:b"));
+        let (second, next) = page(&reports, &all, Some("200:b"), 2).unwrap();
+        assert_eq!(ids(&second), ["a"]);
+        assert_eq!(next, None);
+        assert!(page(&reports, &all, Some("nope"), 2).is_err());
+
+        let filter = ReportFilter {
+            status: Some("success".into()),
+            min_amount: Some(2_000),
+            ..ReportFilter::default()
+        };
+        let (found, _) = page(&reports, &filter, None, 10).unwrap();
+        assert_eq!(ids(&found), ["a"]);
+        let search = ReportFilter {
+            search: Some("BAA".into()),
+            ..ReportFilter::default()
+        };
+        let (found, _) = page(&reports, &search, None, 10).unwrap();
+        assert_eq!(ids(&found), ["b"]);
+    }
+
+    #[test]
+    fn refresh_picks_up_new_and_removed_files() {
+        let dir = temp_dir("index");
+        let write = |name: &str, r: &SwapReportDto| {
+            std::fs::write(dir.join(name), serde_json::to_string(r).unwrap()).unwrap()
+        };
+        write("a.json", &report("a", 100, "Success", 1));
+        std::fs::write(dir.join("broken.json"), "{").unwrap();
+        assert_eq!(refresh(&dir, "m").unwrap().len(), 1);
+
+        write("b.json", &report("b", 200, "Success", 1));
+        let reports = refresh(&dir, "m").unwrap();
+        assert_eq!(ids(&reports), ["b", "a"]);
+
+        std::fs::remove_file(dir.join("a.json")).unwrap();
+        assert_eq!(refresh(&dir, "m").unwrap().len(), 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(refresh(&dir, "m").unwrap().is_empty());
+    }
+
+    #[test]
+    fn settled_directories_are_served_from_the_index() {
+        let dir = temp_dir("settled");
+        let listed = Arc::new(report("a", 100, "Success", 1));
+        let file = |report: Option<Arc<SwapReportDto>>| IndexedFile {
+            modified: None,
+            len: 0,
+            report,
+        };
+        let changed = std::fs::metadata(&dir).unwrap().modified().unwrap();
+        let index = |listed_at, report| DirIndex {
+            modified: Some(changed),
+            listed_at: Some(listed_at),
+            files: HashMap::from([(dir.join("a.json"), file(report))]),
+            reports: vec![listed.clone()],
+        };
+        let later = changed + Duration::from_secs(60);
+        assert!(index(later, Some(listed.clone())).is_current(Some(changed)));
+        assert!(!index(later, Some(listed.clone())).is_current(Some(later)));
+        // Listed right after a change, or with a half-written report: list again
+        assert!(!index(changed, Some(listed.clone())).is_current(Some(changed)));
+        assert!(!index(later, None).is_current(Some(changed)));
+
+        indexes()
+            .lock()
+            .unwrap()
+            .insert(dir.clone(), index(later, Some(listed.clone())));
+        assert_eq!(ids(&refresh(&dir, "m").unwrap()), ["a"]);
+        forget(&dir);
+        assert!(refresh(&dir, "m").unwrap().is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
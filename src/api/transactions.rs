@@ -0,0 +1,485 @@
+use std::collections::HashSet;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+use coinswap::bitcoin::{OutPoint, Txid};
+use coinswap::bitcoind::bitcoincore_rpc::{json::WalletTxInfo, Client, RpcApi};
+use serde::Deserialize;
+
+use super::{
+    dto::{
+        ApiResponse, BumpFeeRequest, BumpFeeResponse, OutgoingTxInfo, SwapReportDto,
+        TransactionInfo, TransactionPage, TxCategory,
+    },
+    fees,
+    monitoring::load_swap_reports,
+    AppState,
+};
+use crate::maker_manager::{
+    labels::{LabelStore, LabelType},
+    message::{BumpMethod, MessageResponse, WalletTxHints},
+    MakerConfig, MakerManagerError,
+};
+
+/// Minimum feerate increase (sat/vB) a replacement must pay over the original (BIP125 rule 4).
+const MIN_RBF_INCREMENT: f64 = 1.0;
+const DEFAULT_PAGE_LIMIT: usize = 50;
+const MAX_PAGE_LIMIT: usize = 500;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/makers/{id}/transactions", get(list_transactions))
+        .route("/makers/{id}/transactions/outgoing", get(list_outgoing))
+        .route("/makers/{id}/transactions/{txid}/bump", post(bump_fee))
+}
+
+/// List transactions broadcast through the dashboard, newest first
+#[utoipa::path(
+    get,
+    path = "/api/makers/{id}/transactions/outgoing",
+    tag = "wallet",
+    params(("id" = String, Path, description = "Maker ID")),
+    responses(
+        (status = 200, description = "Tracked outgoing transactions", body = ApiResponse<Vec<OutgoingTxInfo>>),
+        (status = 404, description = "Maker not found", body = ApiResponse<Vec<OutgoingTxInfo>>)
+    )
+)]
+async fn list_outgoing(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<Vec<OutgoingTxInfo>>>) {
+    let mgr = state.lock().await;
+    let Some(labels) = mgr.labels(&id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+        );
+    };
+    let txs = mgr
+        .outgoing_txs(&id)
+        .into_iter()
+        .map(|tx| {
+            let label = labels
+                .label_of(LabelType::Tx, &tx.txid.to_string())
+                .or_else(|| labels.label_of(LabelType::Addr, &tx.address));
+            OutgoingTxInfo { label, ..tx.into() }
+        })
+        .collect();
+    (StatusCode::OK, Json(ApiResponse::ok(txs)))
+}
+
+/// Bump the fee of a stuck withdrawal via RBF or CPFP
+#[utoipa::path(
+    post,
+    path = "/api/makers/{id}/transactions/{txid}/bump",
+    tag = "wallet",
+    params(
+        ("id" = String, Path, description = "Maker ID"),
+        ("txid" = String, Path, description = "Txid of the transaction to bump")
+    ),
+    request_body = BumpFeeRequest,
+    responses(
+        (status = 200, description = "Bump broadcast", body = ApiResponse<BumpFeeResponse>),
+        (status = 400, description = "Invalid txid or feerate", body = ApiResponse<BumpFeeResponse>),
+        (status = 404, description = "Maker or transaction not found", body = ApiResponse<BumpFeeResponse>),
+        (status = 409, description = "Transaction already replaced or change output frozen", body = ApiResponse<BumpFeeResponse>),
+        (status = 503, description = "Fee estimation unavailable", body = ApiResponse<BumpFeeResponse>),
+        (status = 500, description = "Internal error", body = ApiResponse<BumpFeeResponse>)
+    )
+)]
+async fn bump_fee(
+    State(state): State<AppState>,
+    Path((id, txid)): Path<(String, String)>,
+    Json(body): Json<BumpFeeRequest>,
+) -> (StatusCode, Json<ApiResponse<BumpFeeResponse>>) {
+    let Ok(txid) = txid.parse::<Txid>() else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::err(format!("Invalid txid '{txid}'"))),
+        );
+    };
+    let (config, original, frozen) = {
+        let mgr = state.lock().await;
+        let Some(config) = mgr.get_config(&id) else {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+            );
+        };
+        let Some(original) = mgr.get_outgoing_tx(&id, &txid).cloned() else {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::err(format!(
+                    "Transaction {txid} was not sent through the dashboard for maker '{id}'"
+                ))),
+            );
+        };
+        let frozen = mgr.frozen_utxos(&id).unwrap_or_default();
+        (config, original, frozen)
+    };
+    if let Some(replacement) = original.replaced_by {
+        let e = MakerManagerError::Replaced(txid, replacement);
+        return (StatusCode::CONFLICT, Json(ApiResponse::err(e.to_string())));
+    }
+
+    let feerate = match fees::resolve_feerate(config, body.feerate, body.fee_target).await {
+        Ok(feerate) => feerate,
+        Err((status, msg)) => return (status, Json(ApiResponse::err(msg))),
+    };
+    let method: BumpMethod = body.method.into();
+    if method == BumpMethod::Rbf && feerate < original.feerate + MIN_RBF_INCREMENT {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::err(format!(
+                "Replacement feerate must be at least {} sat/vB (original {} sat/vB)",
+                original.feerate + MIN_RBF_INCREMENT,
+                original.feerate
+            ))),
+        );
+    }
+    if let Some(change) = original.change.filter(|c| frozen.contains(c)) {
+        if method == BumpMethod::Cpfp {
+            return (
+                StatusCode::CONFLICT,
+                Json(ApiResponse::err(format!(
+                    "Change output {change} is frozen; unfreeze it to bump via CPFP"
+                ))),
+            );
+        }
+    }
+    if method == BumpMethod::Cpfp && feerate <= original.feerate {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::err(format!(
+                "Target package feerate must exceed the original {} sat/vB",
+                original.feerate
+            ))),
+        );
+    }
+
+    match state
+        .lock()
+        .await
+        .bump_fee(&id, &txid, feerate, method)
+        .await
+    {
+        Ok(MessageResponse::BumpFeeResp(tx)) => (
+            StatusCode::OK,
+            Json(ApiResponse::ok(BumpFeeResponse {
+                method: body.method,
+                txid: tx.txid.to_string(),
+                bumped_txid: txid.to_string(),
+                feerate: tx.feerate,
+                fee: tx.fee,
+            })),
+        ),
+        Ok(MessageResponse::ServerError(e)) => {
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::err(e)))
+        }
+        Ok(other) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::err(format!("Unexpected response: {other}"))),
+        ),
+        // Replaced by a concurrent bump since the check above
+        Err(e @ MakerManagerError::Replaced(..)) => {
+            (StatusCode::CONFLICT, Json(ApiResponse::err(e.to_string())))
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::err(e.to_string())),
+        ),
+    }
+}
+
+#[derive(Deserialize)]
+struct TransactionsQuery {
+    offset: Option<usize>,
+    limit: Option<usize>,
+}
+
+/// List the wallet's transaction history, newest first
+#[utoipa::path(
+    get,
+    path = "/api/makers/{id}/transactions",
+    tag = "wallet",
+    params(
+        ("id" = String, Path, description = "Maker ID"),
+        ("offset" = Option<usize>, Query, description = "Number of transactions to skip (default 0)"),
+        ("limit" = Option<usize>, Query, description = "Page size (default 50, max 500)")
+    ),
+    responses(
+        (status = 200, description = "Page of classified wallet transactions", body = ApiResponse<TransactionPage>),
+        (status = 404, description = "Maker not found", body = ApiResponse<TransactionPage>),
+        (status = 500, description = "Internal error", body = ApiResponse<TransactionPage>)
+    )
+)]
+async fn list_transactions(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<TransactionsQuery>,
+) -> (StatusCode, Json<ApiResponse<TransactionPage>>) {
+    let offset = query.offset.unwrap_or(0);
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_PAGE_LIMIT)
+        .clamp(1, MAX_PAGE_LIMIT);
+
+    let context = match WalletHistory::load(&state, &id).await {
+        Some(Ok(context)) => context,
+        Some(Err(e)) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::err(e))),
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+            )
+        }
+    };
+
+    let result = tokio::task::spawn_blocking(move || {
+        let wallet = context.open()?;
+        let total = wallet.txs.len();
+        let transactions = wallet
+            .txs
+            .iter()
+            .skip(offset)
+            .take(limit)
+            .map(|info| wallet.describe(info))
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok::<_, String>(TransactionPage {
+            total,
+            offset,
+            limit,
+            transactions,
+        })
+    })
+    .await;
+
+    match result {
+        Ok(Ok(page)) => (StatusCode::OK, Json(ApiResponse::ok(page))),
+        Ok(Err(e)) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::err(e))),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::err(e.to_string())),
+        ),
+    }
+}
+
+/// What the dashboard and the maker know about a wallet's transactions, gathered under the
+/// manager lock so the wallet can be read off it.
+pub(crate) struct WalletHistory {
+    id: String,
+    config: MakerConfig,
+    labels: LabelStore,
+    outgoing: HashSet<Txid>,
+    hints: WalletTxHints,
+}
+
+impl WalletHistory {
+    /// `None` if the maker doesn't exist.
+    pub(crate) async fn load(state: &AppState, id: &String) -> Option<Result<Self, String>> {
+        let mgr = state.lock().await;
+        let config = mgr.get_config(id)?;
+        let labels = mgr.labels(id).unwrap_or_default();
+        let outgoing: HashSet<Txid> = mgr.outgoing_txs(id).iter().map(|tx| tx.txid).collect();
+        let hints = match mgr.get_tx_hints(id).await {
+            Ok(MessageResponse::TxHintsResp(hints)) => hints,
+            Ok(MessageResponse::ServerError(e)) => return Some(Err(e)),
+            Ok(other) => return Some(Err(format!("Unexpected response: {other}"))),
+            Err(e) => return Some(Err(e.to_string())),
+        };
+        Some(Ok(Self {
+            id: id.clone(),
+            config,
+            labels,
+            outgoing,
+            hints,
+        }))
+    }
+
+    /// Lists the wallet's transactions, newest first. Blocking.
+    pub(crate) fn open(self) -> Result<WalletTxs, String> {
+        let reports = match &self.config.data_directory {
+            Some(data_dir) => load_swap_reports(data_dir.join("swap_reports"), self.id.clone())
+                .map_err(|(_, msg)| msg)?,
+            None => Vec::new(),
+        };
+        let classifier = TxClassifier::new(self.hints, self.outgoing, &reports);
+        let client = self
+            .config
+            .wallet_rpc_client(&self.id)
+            .map_err(|e| e.to_string())?;
+
+        // listsinceblock yields one entry per wallet output; collapse them per transaction
+        let listed = client
+            .list_since_block(None, None, Some(true), None)
+            .map_err(|e| format!("listsinceblock failed: {e}"))?;
+        let mut seen = HashSet::new();
+        let mut txs: Vec<WalletTxInfo> = listed
+            .transactions
+            .into_iter()
+            .filter(|entry| seen.insert(entry.info.txid))
+            .map(|entry| entry.info)
+            .collect();
+        txs.sort_by(|a, b| b.time.cmp(&a.time).then(a.txid.cmp(&b.txid)));
+
+        Ok(WalletTxs {
+            client,
+            classifier,
+            labels: self.labels,
+            txs,
+        })
+    }
+}
+
+/// A maker's wallet transactions, ready to be described one by one.
+pub(crate) struct WalletTxs {
+    client: Client,
+    classifier: TxClassifier,
+    labels: LabelStore,
+    /// Newest first
+    pub(crate) txs: Vec<WalletTxInfo>,
+}
+
+impl WalletTxs {
+    /// Fetches the details of one listed transaction and classifies it. Blocking.
+    pub(crate) fn describe(&self, info: &WalletTxInfo) -> Result<TransactionInfo, String> {
+        let details = self
+            .client
+            .get_transaction(&info.txid, Some(true))
+            .map_err(|e| format!("gettransaction {} failed: {e}", info.txid))?;
+        let tx = details
+            .transaction()
+            .map_err(|e| format!("Failed to decode {}: {e}", info.txid))?;
+        let fee = details.fee.map(|fee| fee.unsigned_abs().to_sat());
+        let amount = details.amount.to_sat() - fee.unwrap_or(0) as i64;
+        let spent: Vec<OutPoint> = tx.input.iter().map(|i| i.previous_output).collect();
+        let txid = info.txid.to_string();
+        Ok(TransactionInfo {
+            label: self.labels.label_of(LabelType::Tx, &txid),
+            txid,
+            category: self.classifier.classify(info.txid, &spent, amount),
+            amount,
+            fee,
+            confirmations: info.confirmations,
+            block_height: info.blockheight,
+            block_time: info.blocktime,
+            time: info.time,
+        })
+    }
+}
+
+/// Assigns a [`TxCategory`] to wallet transactions using what the maker's wallet, the
+/// dashboard and the swap reports know about them.
+struct TxClassifier {
+    fidelity_outpoints: HashSet<OutPoint>,
+    swap_in: HashSet<Txid>,
+    sweeps: HashSet<Txid>,
+    outgoing: HashSet<Txid>,
+    /// Funding and contract txids from swap reports, in either direction
+    swaps: HashSet<Txid>,
+    recoveries: HashSet<Txid>,
+}
+
+impl TxClassifier {
+    fn new(hints: WalletTxHints, outgoing: HashSet<Txid>, reports: &[SwapReportDto]) -> Self {
+        let parse = |txid: &String| txid.parse::<Txid>().ok();
+        let mut swaps = HashSet::new();
+        let mut recoveries = HashSet::new();
+        for report in reports {
+            swaps.extend(report.incoming_contract_txid.iter().filter_map(parse));
+            swaps.extend(report.outgoing_contract_txid.iter().filter_map(parse));
+            swaps.extend(report.funding_txids.iter().flatten().filter_map(parse));
+            recoveries.extend(report.recovery_txids.iter().flatten().filter_map(parse));
+        }
+        Self {
+            fidelity_outpoints: hints.fidelity_outpoints.into_iter().collect(),
+            swap_in: hints.swap_in_txids.into_iter().collect(),
+            sweeps: hints.sweep_txids.into_iter().collect(),
+            outgoing,
+            swaps,
+            recoveries,
+        }
+    }
+
+    /// `spent` are the transaction's input outpoints, `delta` its net effect on the wallet.
+    fn classify(&self, txid: Txid, spent: &[OutPoint], delta: i64) -> TxCategory {
+        if self.recoveries.contains(&txid) {
+            TxCategory::ContractRecovery
+        } else if spent.iter().any(|op| self.fidelity_outpoints.contains(op)) {
+            TxCategory::FidelityRedemption
+        } else if self.fidelity_outpoints.iter().any(|op| op.txid == txid) {
+            TxCategory::FidelityCreation
+        } else if self.sweeps.contains(&txid) {
+            TxCategory::Sweep
+        } else if self.swap_in.contains(&txid) || (self.swaps.contains(&txid) && delta >= 0) {
+            TxCategory::SwapIn
+        } else if self.swaps.contains(&txid) {
+            TxCategory::SwapOut
+        } else if self.outgoing.contains(&txid) || delta < 0 {
+            TxCategory::Withdrawal
+        } else {
+            TxCategory::Deposit
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use coinswap::bitcoin::{hashes::Hash, OutPoint, Txid};
+
+    use super::TxClassifier;
+    use crate::api::dto::TxCategory;
+    use crate::maker_manager::message::WalletTxHints;
+
+    fn txid(n: u8) -> Txid {
+        Txid::from_byte_array([n; 32])
+    }
+
+    fn classifier() -> TxClassifier {
+        let hints = WalletTxHints {
+            fidelity_outpoints: vec![OutPoint::new(txid(1), 0)],
+            swap_in_txids: vec![txid(2)],
+            sweep_txids: vec![txid(3)],
+        };
+        let mut classifier = TxClassifier::new(hints, HashSet::from([txid(4)]), &[]);
+        classifier.swaps.insert(txid(5));
+        classifier.recoveries.insert(txid(6));
+        classifier
+    }
+
+    #[test]
+    fn classifies_wallet_and_swap_transactions() {
+        let c = classifier();
+        assert_eq!(
+            c.classify(txid(1), &[], -1_000),
+            TxCategory::FidelityCreation
+        );
+        assert_eq!(
+            c.classify(txid(9), &[OutPoint::new(txid(1), 0)], 50_000),
+            TxCategory::FidelityRedemption
+        );
+        assert_eq!(c.classify(txid(2), &[], 10_000), TxCategory::SwapIn);
+        assert_eq!(c.classify(txid(3), &[], 10_000), TxCategory::Sweep);
+        assert_eq!(c.classify(txid(5), &[], -10_000), TxCategory::SwapOut);
+        assert_eq!(c.classify(txid(5), &[], 10_000), TxCategory::SwapIn);
+        assert_eq!(
+            c.classify(txid(6), &[], 10_000),
+            TxCategory::ContractRecovery
+        );
+    }
+
+    #[test]
+    fn falls_back_to_balance_delta() {
+        let c = classifier();
+        assert_eq!(c.classify(txid(4), &[], -5_000), TxCategory::Withdrawal);
+        assert_eq!(c.classify(txid(7), &[], -5_000), TxCategory::Withdrawal);
+        assert_eq!(c.classify(txid(8), &[], 5_000), TxCategory::Deposit);
+    }
+}
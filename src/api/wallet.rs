@@ -1,15 +1,31 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
+
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
     routing::{get, post},
     Json, Router,
 };
+use coinswap::bitcoin::{OutPoint, Script, ScriptBuf, Txid};
+use coinswap::bitcoind::bitcoincore_rpc::RpcApi;
+use serde::Deserialize;
+use tracing::warn;
 
 use super::{
-    dto::{ApiResponse, BalanceInfo, SendToAddressRequest, UtxoInfo},
-    AppState,
+    addresses,
+    dto::{
+        AddressTypeDto, ApiResponse, BalanceInfo, FrozenUtxoInfo, NewAddressInfo,
+        SendToAddressRequest, SwapReportDto, UtxoInfo, WalletBackupRequest,
+    },
+    fees, swap_index, AppState,
+};
+use crate::maker_manager::{
+    labels::{Label, LabelStore, LabelType},
+    message::{MessageResponse, UtxoPool, WalletAddressType, WalletUtxo},
+    MakerConfig, MakerManagerError,
 };
-use crate::maker_manager::message::MessageResponse;
 
 pub fn routes() -> Router<AppState> {
     Router::new()
@@ -18,9 +34,16 @@ pub fn routes() -> Router<AppState> {
         .route("/makers/{id}/utxos/swap", get(get_swap_utxos))
         .route("/makers/{id}/utxos/contract", get(get_contract_utxos))
         .route("/makers/{id}/utxos/fidelity", get(get_fidelity_utxos))
+        .route("/makers/{id}/utxos/frozen", get(get_frozen_utxos))
+        .route("/makers/{id}/utxos/{outpoint}/freeze", post(freeze_utxo))
+        .route(
+            "/makers/{id}/utxos/{outpoint}/unfreeze",
+            post(unfreeze_utxo),
+        )
         .route("/makers/{id}/send", post(send_to_address))
         .route("/makers/{id}/address", get(get_new_address))
         .route("/makers/{id}/sync", post(sync_wallet))
+        .route("/makers/{id}/wallet/backup", post(backup_wallet))
 }
 
 /// Get wallet balances for a maker
@@ -44,17 +67,28 @@ async fn get_balance(
             Json(ApiResponse::err(format!("Maker '{id}' not found"))),
         );
     }
-    match state.lock().await.get_balances(&id).await {
-        Ok(MessageResponse::TotalBalanceResp(b)) => (
-            StatusCode::OK,
-            Json(ApiResponse::ok(BalanceInfo {
-                regular: b.regular.to_sat(),
-                swap: b.swap.to_sat(),
-                contract: b.contract.to_sat(),
-                fidelity: b.fidelity.to_sat(),
-                spendable: b.spendable.to_sat(),
-            })),
-        ),
+    let response = state.lock().await.get_balances(&id).await;
+    match response {
+        Ok(MessageResponse::TotalBalanceResp(b)) => {
+            let frozen = match frozen_coins(&state, &id).await {
+                Ok(coins) => Some(coins.iter().map(|coin| coin.amount.to_sat()).sum()),
+                Err(e) => {
+                    warn!("Could not look up frozen coins of maker '{id}': {e}");
+                    None
+                }
+            };
+            (
+                StatusCode::OK,
+                Json(ApiResponse::ok(BalanceInfo {
+                    regular: b.regular.to_sat(),
+                    swap: b.swap.to_sat(),
+                    contract: b.contract.to_sat(),
+                    fidelity: b.fidelity.to_sat(),
+                    spendable: b.spendable.to_sat(),
+                    frozen,
+                })),
+            )
+        }
         Ok(MessageResponse::ServerError(e)) => {
             (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::err(e)))
         }
@@ -77,30 +111,36 @@ async fn get_balance(
     params(("id" = String, Path, description = "Maker ID")),
     responses(
         (status = 200, description = "List of UTXOs", body = ApiResponse<Vec<UtxoInfo>>),
-        (status = 500, description = "Internal error", body = ApiResponse<Vec<UtxoInfo>>)
+        (status = 404, description = "Maker not found", body = ApiResponse<Vec<UtxoInfo>>),
+        (status = 500, description = "Internal error or UTXO conversion failure", body = ApiResponse<Vec<UtxoInfo>>)
     )
 )]
 async fn get_utxos(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> (StatusCode, Json<ApiResponse<Vec<UtxoInfo>>>) {
-    if !state.lock().await.has_maker(&id) {
+    let Some(context) = UtxoContext::load(&state, &id).await else {
         return (
             StatusCode::NOT_FOUND,
             Json(ApiResponse::err(format!("Maker '{id}' not found"))),
         );
-    }
-    match state.lock().await.get_utxos(&id).await {
-        Ok(MessageResponse::UtxoResp { utxos }) => {
-            let infos = utxos
-                .into_iter()
-                .filter_map(|u| {
-                    serde_json::to_value(&u)
-                        .ok()
-                        .and_then(|v| serde_json::from_value(v).ok())
-                })
-                .collect();
-            (StatusCode::OK, Json(ApiResponse::ok(infos)))
+    };
+    let response = state.lock().await.get_utxos(&id).await;
+    match response {
+        Ok(MessageResponse::UtxoResp { mut utxos }) => {
+            // Locked coins are missing from coinswap's list; add them back so frozen
+            // coins stay visible
+            match frozen_coins(&state, &id).await {
+                Ok(frozen) => {
+                    let listed: HashSet<OutPoint> = utxos.iter().map(|u| u.outpoint).collect();
+                    utxos.extend(frozen.into_iter().filter(|u| !listed.contains(&u.outpoint)));
+                }
+                Err(e) => warn!("Could not look up frozen coins of maker '{id}': {e}"),
+            }
+            (
+                StatusCode::OK,
+                Json(ApiResponse::ok(context.describe(utxos).await)),
+            )
         }
         Ok(MessageResponse::ServerError(e)) => {
             (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::err(e)))
@@ -125,6 +165,9 @@ async fn get_utxos(
     request_body = SendToAddressRequest,
     responses(
         (status = 200, description = "Transaction ID", body = ApiResponse<String>),
+        (status = 400, description = "Invalid or absurd feerate", body = ApiResponse<String>),
+        (status = 404, description = "Maker not found", body = ApiResponse<String>),
+        (status = 503, description = "Fee estimation unavailable", body = ApiResponse<String>),
         (status = 500, description = "Internal error", body = ApiResponse<String>)
     )
 )]
@@ -133,20 +176,24 @@ async fn send_to_address(
     Path(id): Path<String>,
     Json(body): Json<SendToAddressRequest>,
 ) -> (StatusCode, Json<ApiResponse<String>>) {
-    if !state.lock().await.has_maker(&id) {
+    let Some(config) = state.lock().await.get_config(&id) else {
         return (
             StatusCode::NOT_FOUND,
             Json(ApiResponse::err(format!("Maker '{id}' not found"))),
         );
-    }
+    };
+    let feerate = match fees::resolve_feerate(config, body.feerate, body.fee_target).await {
+        Ok(feerate) => feerate,
+        Err((status, msg)) => return (status, Json(ApiResponse::err(msg))),
+    };
     match state
         .lock()
         .await
-        .send_to_address(&id, body.address, body.amount, body.feerate)
+        .send_to_address(&id, body.address, body.amount, feerate)
         .await
     {
-        Ok(MessageResponse::SendToAddressResp(txid)) => {
-            (StatusCode::OK, Json(ApiResponse::ok(txid)))
+        Ok(MessageResponse::SendToAddressResp(tx)) => {
+            (StatusCode::OK, Json(ApiResponse::ok(tx.txid.to_string())))
         }
         Ok(MessageResponse::ServerError(e)) => {
             (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::err(e)))
@@ -162,41 +209,125 @@ async fn send_to_address(
     }
 }
 
-/// Generate a new address for a maker
+#[derive(Deserialize)]
+struct NewAddressQuery {
+    #[serde(rename = "type", default)]
+    address_type: AddressTypeDto,
+    /// Derive a new address even if an issued one is still unused
+    #[serde(default)]
+    fresh: bool,
+    label: Option<String>,
+}
+
+/// Get a receive address for a maker, optionally labelling it.
+/// Returns the newest issued address of the requested type that has not received funds yet,
+/// unless `fresh` is set.
 #[utoipa::path(
     get,
     path = "/api/makers/{id}/address",
     tag = "wallet",
-    params(("id" = String, Path, description = "Maker ID")),
+    params(
+        ("id" = String, Path, description = "Maker ID"),
+        ("type" = Option<AddressTypeDto>, Query, description = "Address type (default p2tr)"),
+        ("fresh" = Option<bool>, Query, description = "Always derive a new address"),
+        ("label" = Option<String>, Query, description = "Label to attach to the address")
+    ),
     responses(
-        (status = 200, description = "New wallet address", body = ApiResponse<String>),
-        (status = 500, description = "Internal error", body = ApiResponse<String>)
+        (status = 200, description = "Receive address", body = ApiResponse<NewAddressInfo>),
+        (status = 404, description = "Maker not found", body = ApiResponse<NewAddressInfo>),
+        (status = 500, description = "Internal error", body = ApiResponse<NewAddressInfo>)
     )
 )]
 async fn get_new_address(
     State(state): State<AppState>,
     Path(id): Path<String>,
-) -> (StatusCode, Json<ApiResponse<String>>) {
-    if !state.lock().await.has_maker(&id) {
+    Query(query): Query<NewAddressQuery>,
+) -> (StatusCode, Json<ApiResponse<NewAddressInfo>>) {
+    let Some(config) = state.lock().await.get_config(&id) else {
         return (
             StatusCode::NOT_FOUND,
             Json(ApiResponse::err(format!("Maker '{id}' not found"))),
         );
-    }
-    match state.lock().await.get_new_address(&id).await {
-        Ok(MessageResponse::NewAddressResp(addr)) => (StatusCode::OK, Json(ApiResponse::ok(addr))),
-        Ok(MessageResponse::ServerError(e)) => {
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::err(e)))
+    };
+    let address_type: WalletAddressType = query.address_type.into();
+
+    // Reusing unused addresses keeps repeated UI refreshes from walking past the gap limit
+    let reusable = if query.fresh {
+        None
+    } else {
+        let issued = state.lock().await.issued_addresses(&id);
+        if issued.iter().any(|a| a.address_type == address_type) {
+            match addresses::address_usage(config, id.clone()).await {
+                Ok(usage) => issued
+                    .into_iter()
+                    .filter(|a| a.address_type == address_type)
+                    .find(|a| !usage.contains_key(&a.address))
+                    .map(|a| a.address),
+                Err(e) => {
+                    warn!("Cannot check address usage for maker '{id}', deriving a new one: {e}");
+                    None
+                }
+            }
+        } else {
+            None
         }
-        Ok(other) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::err(format!("Unexpected response: {other}"))),
-        ),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::err(e.to_string())),
-        ),
-    }
+    };
+
+    let mut mgr = state.lock().await;
+    let (address, reused) = match reusable {
+        Some(address) => (address, true),
+        None => match mgr.get_new_address(&id, address_type).await {
+            Ok(MessageResponse::NewAddressResp(address)) => (address, false),
+            Ok(MessageResponse::ServerError(e)) => {
+                return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::err(e)))
+            }
+            Ok(other) => {
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ApiResponse::err(format!("Unexpected response: {other}"))),
+                )
+            }
+            Err(e) => {
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ApiResponse::err(e.to_string())),
+                )
+            }
+        },
+    };
+
+    let label = match query.label.filter(|l| !l.trim().is_empty()) {
+        Some(label) => {
+            let record = Label {
+                label_type: LabelType::Addr,
+                reference: address.clone(),
+                label: Some(label.clone()),
+                origin: None,
+                spendable: None,
+            };
+            if let Err(e) = mgr.set_label(&id, record) {
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ApiResponse::err(format!(
+                        "Address {address} was issued but labelling it failed: {e}"
+                    ))),
+                );
+            }
+            Some(label)
+        }
+        None => mgr
+            .labels(&id)
+            .and_then(|labels| labels.label_of(LabelType::Addr, &address)),
+    };
+    (
+        StatusCode::OK,
+        Json(ApiResponse::ok(NewAddressInfo {
+            address,
+            address_type: query.address_type,
+            reused,
+            label,
+        })),
+    )
 }
 
 /// Trigger a wallet sync for a maker
@@ -220,11 +351,15 @@ async fn sync_wallet(
             Json(ApiResponse::err(format!("Maker '{id}' not found"))),
         );
     }
-    match state.lock().await.sync_wallet(&id).await {
-        Ok(MessageResponse::Pong) => (
-            StatusCode::OK,
-            Json(ApiResponse::ok("Wallet synced".to_string())),
-        ),
+    let result = state.lock().await.sync_wallet(&id).await;
+    match result {
+        Ok(MessageResponse::Pong) => {
+            relock_frozen(&state, &id).await;
+            (
+                StatusCode::OK,
+                Json(ApiResponse::ok("Wallet synced".to_string())),
+            )
+        }
         Ok(MessageResponse::ServerError(e)) => {
             (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::err(e)))
         }
@@ -247,31 +382,26 @@ async fn sync_wallet(
     params(("id" = String, Path, description = "Maker ID")),
     responses(
         (status = 200, description = "Swap UTXOs", body = ApiResponse<Vec<UtxoInfo>>),
-        (status = 500, description = "Internal error", body = ApiResponse<Vec<UtxoInfo>>)
+        (status = 404, description = "Maker not found", body = ApiResponse<Vec<UtxoInfo>>),
+        (status = 500, description = "Internal error or UTXO conversion failure", body = ApiResponse<Vec<UtxoInfo>>)
     )
 )]
 async fn get_swap_utxos(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> (StatusCode, Json<ApiResponse<Vec<UtxoInfo>>>) {
-    if !state.lock().await.has_maker(&id) {
+    let Some(context) = UtxoContext::load(&state, &id).await else {
         return (
             StatusCode::NOT_FOUND,
             Json(ApiResponse::err(format!("Maker '{id}' not found"))),
         );
-    }
-    match state.lock().await.get_swap_utxos(&id).await {
-        Ok(MessageResponse::SwapUtxoResp { utxos }) => {
-            let infos = utxos
-                .into_iter()
-                .filter_map(|u| {
-                    serde_json::to_value(&u)
-                        .ok()
-                        .and_then(|v| serde_json::from_value(v).ok())
-                })
-                .collect();
-            (StatusCode::OK, Json(ApiResponse::ok(infos)))
-        }
+    };
+    let response = state.lock().await.get_swap_utxos(&id).await;
+    match response {
+        Ok(MessageResponse::SwapUtxoResp { utxos }) => (
+            StatusCode::OK,
+            Json(ApiResponse::ok(context.describe(utxos).await)),
+        ),
         Ok(MessageResponse::ServerError(e)) => {
             (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::err(e)))
         }
@@ -294,31 +424,26 @@ async fn get_swap_utxos(
     params(("id" = String, Path, description = "Maker ID")),
     responses(
         (status = 200, description = "Contract UTXOs", body = ApiResponse<Vec<UtxoInfo>>),
-        (status = 500, description = "Internal error", body = ApiResponse<Vec<UtxoInfo>>)
+        (status = 404, description = "Maker not found", body = ApiResponse<Vec<UtxoInfo>>),
+        (status = 500, description = "Internal error or UTXO conversion failure", body = ApiResponse<Vec<UtxoInfo>>)
     )
 )]
 async fn get_contract_utxos(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> (StatusCode, Json<ApiResponse<Vec<UtxoInfo>>>) {
-    if !state.lock().await.has_maker(&id) {
+    let Some(context) = UtxoContext::load(&state, &id).await else {
         return (
             StatusCode::NOT_FOUND,
             Json(ApiResponse::err(format!("Maker '{id}' not found"))),
         );
-    }
-    match state.lock().await.get_contract_utxos(&id).await {
-        Ok(MessageResponse::ContractUtxoResp { utxos }) => {
-            let infos = utxos
-                .into_iter()
-                .filter_map(|u| {
-                    serde_json::to_value(&u)
-                        .ok()
-                        .and_then(|v| serde_json::from_value(v).ok())
-                })
-                .collect();
-            (StatusCode::OK, Json(ApiResponse::ok(infos)))
-        }
+    };
+    let response = state.lock().await.get_contract_utxos(&id).await;
+    match response {
+        Ok(MessageResponse::ContractUtxoResp { utxos }) => (
+            StatusCode::OK,
+            Json(ApiResponse::ok(context.describe(utxos).await)),
+        ),
         Ok(MessageResponse::ServerError(e)) => {
             (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::err(e)))
         }
@@ -341,31 +466,26 @@ async fn get_contract_utxos(
     params(("id" = String, Path, description = "Maker ID")),
     responses(
         (status = 200, description = "Fidelity UTXOs", body = ApiResponse<Vec<UtxoInfo>>),
-        (status = 500, description = "Internal error", body = ApiResponse<Vec<UtxoInfo>>)
+        (status = 404, description = "Maker not found", body = ApiResponse<Vec<UtxoInfo>>),
+        (status = 500, description = "Internal error or UTXO conversion failure", body = ApiResponse<Vec<UtxoInfo>>)
     )
 )]
 async fn get_fidelity_utxos(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> (StatusCode, Json<ApiResponse<Vec<UtxoInfo>>>) {
-    if !state.lock().await.has_maker(&id) {
+    let Some(context) = UtxoContext::load(&state, &id).await else {
         return (
             StatusCode::NOT_FOUND,
             Json(ApiResponse::err(format!("Maker '{id}' not found"))),
         );
-    }
-    match state.lock().await.get_fidelity_utxos(&id).await {
-        Ok(MessageResponse::FidelityUtxoResp { utxos }) => {
-            let infos = utxos
-                .into_iter()
-                .filter_map(|u| {
-                    serde_json::to_value(&u)
-                        .ok()
-                        .and_then(|v| serde_json::from_value(v).ok())
-                })
-                .collect();
-            (StatusCode::OK, Json(ApiResponse::ok(infos)))
-        }
+    };
+    let response = state.lock().await.get_fidelity_utxos(&id).await;
+    match response {
+        Ok(MessageResponse::FidelityUtxoResp { utxos }) => (
+            StatusCode::OK,
+            Json(ApiResponse::ok(context.describe(utxos).await)),
+        ),
         Ok(MessageResponse::ServerError(e)) => {
             (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::err(e)))
         }
@@ -379,3 +499,597 @@ async fn get_fidelity_utxos(
         ),
     }
 }
+
+/// Dashboard-side state needed to describe a maker's UTXOs.
+pub(crate) struct UtxoContext {
+    id: String,
+    config: MakerConfig,
+    labels: LabelStore,
+    frozen: BTreeSet<OutPoint>,
+    /// Swap ID by the txids recorded in the maker's swap reports.
+    swap_txids: HashMap<String, String>,
+}
+
+impl UtxoContext {
+    /// Collects labels, frozen set and swap report txids, or `None` if the maker is not
+    /// registered.
+    pub(crate) async fn load(state: &AppState, id: &str) -> Option<Self> {
+        let (config, labels, frozen) = {
+            let mgr = state.lock().await;
+            (
+                mgr.get_maker_config(id)?.clone(),
+                mgr.labels(id)?,
+                mgr.frozen_utxos(id)?,
+            )
+        };
+        let swap_txids = match config.data_directory.clone() {
+            Some(data_dir) => {
+                let reports_dir = data_dir.join("swap_reports");
+                let id = id.to_string();
+                tokio::task::spawn_blocking(move || swap_index::refresh(&reports_dir, &id))
+                    .await
+                    .ok()
+                    .and_then(Result::ok)
+                    .map(|reports| swap_txid_index(&reports))
+                    .unwrap_or_default()
+            }
+            None => HashMap::new(),
+        };
+        Some(Self {
+            id: id.to_string(),
+            config,
+            labels,
+            frozen,
+            swap_txids,
+        })
+    }
+
+    /// Converts wallet UTXOs into DTOs. Block data is best effort.
+    pub(crate) async fn describe(&self, utxos: Vec<WalletUtxo>) -> Vec<UtxoInfo> {
+        let blocks = self.block_info(&utxos).await;
+        utxos
+            .into_iter()
+            .map(|utxo| self.utxo_info(utxo, &blocks))
+            .collect()
+    }
+
+    fn utxo_info(
+        &self,
+        utxo: WalletUtxo,
+        blocks: &HashMap<Txid, (Option<u32>, Option<u64>)>,
+    ) -> UtxoInfo {
+        let outpoint = utxo.outpoint.to_string();
+        let addr = utxo.address;
+        let txid = utxo.outpoint.txid.to_string();
+        let (block_height, block_time) =
+            blocks.get(&utxo.outpoint.txid).copied().unwrap_or_default();
+        UtxoInfo {
+            label: self
+                .labels
+                .label_of(LabelType::Output, &outpoint)
+                .or_else(|| {
+                    let addr = addr.as_deref()?;
+                    self.labels.label_of(LabelType::Addr, addr)
+                }),
+            frozen: self.frozen.contains(&utxo.outpoint),
+            swap_id: self.swap_txids.get(&txid).cloned(),
+            script_type: script_type(&utxo.script_pubkey).to_string(),
+            derivation_path: utxo.descriptor.as_deref().and_then(derivation_path),
+            block_height,
+            block_time,
+            utxo_type: utxo.pool.into(),
+            fidelity_index: utxo.fidelity_index,
+            contract_timelock: utxo.contract_timelock,
+            amount: utxo.amount.to_sat(),
+            confirmations: utxo.confirmations,
+            vout: utxo.outpoint.vout,
+            outpoint,
+            txid,
+            addr,
+        }
+    }
+
+    /// Height and time of the block confirming each UTXO's transaction. Falls back to a
+    /// height derived from the confirmation count when the RPC lookup fails.
+    async fn block_info(&self, utxos: &[WalletUtxo]) -> HashMap<Txid, (Option<u32>, Option<u64>)> {
+        let confirmed: HashMap<Txid, u32> = utxos
+            .iter()
+            .filter(|u| u.confirmations > 0)
+            .map(|u| (u.outpoint.txid, u.confirmations))
+            .collect();
+        if confirmed.is_empty() {
+            return HashMap::new();
+        }
+        let config = self.config.clone();
+        let id = self.id.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            let client = config.wallet_rpc_client(&id).map_err(|e| e.to_string())?;
+            let tip = client.get_block_count().ok();
+            Ok::<_, String>(
+                confirmed
+                    .into_iter()
+                    .map(|(txid, confirmations)| {
+                        let fallback =
+                            tip.map(|tip| (tip + 1).saturating_sub(confirmations as u64) as u32);
+                        match client.get_transaction(&txid, Some(true)) {
+                            Ok(tx) => (txid, (tx.info.blockheight.or(fallback), tx.info.blocktime)),
+                            Err(_) => (txid, (fallback, None)),
+                        }
+                    })
+                    .collect(),
+            )
+        })
+        .await;
+        match result {
+            Ok(Ok(blocks)) => blocks,
+            Ok(Err(e)) => {
+                warn!("Failed to look up UTXO blocks for maker '{}': {e}", self.id);
+                HashMap::new()
+            }
+            Err(e) => {
+                warn!("Failed to look up UTXO blocks for maker '{}': {e}", self.id);
+                HashMap::new()
+            }
+        }
+    }
+}
+
+/// Maps every funding, contract and recovery txid in the reports to its swap ID.
+fn swap_txid_index(reports: &[Arc<SwapReportDto>]) -> HashMap<String, String> {
+    let mut index = HashMap::new();
+    for report in reports {
+        let txids = report
+            .incoming_contract_txid
+            .iter()
+            .chain(report.outgoing_contract_txid.iter())
+            .chain(report.funding_txids.iter().flatten())
+            .chain(report.recovery_txids.iter().flatten());
+        for txid in txids {
+            index.insert(txid.clone(), report.swap_id.clone());
+        }
+    }
+    index
+}
+
+fn script_type(script: &Script) -> &'static str {
+    if script.is_p2wpkh() {
+        "p2wpkh"
+    } else if script.is_p2wsh() {
+        "p2wsh"
+    } else if script.is_p2tr() {
+        "p2tr"
+    } else if script.is_p2sh() {
+        "p2sh"
+    } else if script.is_p2pkh() {
+        "p2pkh"
+    } else {
+        "unknown"
+    }
+}
+
+/// Extracts the BIP-32 path from a descriptor's key origin, e.g.
+/// `wpkh([d34db33f/84h/1h/0h/0/5]02...)#checksum` gives `m/84h/1h/0h/0/5`.
+fn derivation_path(descriptor: &str) -> Option<String> {
+    let (_, origin) = descriptor.split_once('[')?;
+    let (origin, _) = origin.split_once(']')?;
+    let (_, path) = origin.split_once('/')?;
+    Some(format!("m/{path}"))
+}
+
+/// List the outpoints of a maker's frozen UTXOs
+#[utoipa::path(
+    get,
+    path = "/api/makers/{id}/utxos/frozen",
+    tag = "wallet",
+    params(("id" = String, Path, description = "Maker ID")),
+    responses(
+        (status = 200, description = "Frozen outpoints (`txid:vout`)", body = ApiResponse<Vec<String>>),
+        (status = 404, description = "Maker not found", body = ApiResponse<Vec<String>>)
+    )
+)]
+async fn get_frozen_utxos(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<Vec<String>>>) {
+    match state.lock().await.frozen_utxos(&id) {
+        Some(frozen) => (
+            StatusCode::OK,
+            Json(ApiResponse::ok(
+                frozen.iter().map(|op| op.to_string()).collect(),
+            )),
+        ),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+        ),
+    }
+}
+
+/// Freeze a UTXO so that neither sends nor swap funding spend it
+#[utoipa::path(
+    post,
+    path = "/api/makers/{id}/utxos/{outpoint}/freeze",
+    tag = "wallet",
+    params(
+        ("id" = String, Path, description = "Maker ID"),
+        ("outpoint" = String, Path, description = "UTXO as `txid:vout`")
+    ),
+    responses(
+        (status = 200, description = "UTXO frozen", body = ApiResponse<FrozenUtxoInfo>),
+        (status = 400, description = "Malformed outpoint", body = ApiResponse<FrozenUtxoInfo>),
+        (status = 404, description = "Maker not found", body = ApiResponse<FrozenUtxoInfo>),
+        (status = 500, description = "Failed to persist frozen set", body = ApiResponse<FrozenUtxoInfo>)
+    )
+)]
+async fn freeze_utxo(
+    State(state): State<AppState>,
+    Path((id, outpoint)): Path<(String, String)>,
+) -> (StatusCode, Json<ApiResponse<FrozenUtxoInfo>>) {
+    set_frozen(state, id, outpoint, true).await
+}
+
+/// Unfreeze a previously frozen UTXO
+#[utoipa::path(
+    post,
+    path = "/api/makers/{id}/utxos/{outpoint}/unfreeze",
+    tag = "wallet",
+    params(
+        ("id" = String, Path, description = "Maker ID"),
+        ("outpoint" = String, Path, description = "UTXO as `txid:vout`")
+    ),
+    responses(
+        (status = 200, description = "UTXO unfrozen", body = ApiResponse<FrozenUtxoInfo>),
+        (status = 400, description = "Malformed outpoint", body = ApiResponse<FrozenUtxoInfo>),
+        (status = 404, description = "Maker not found", body = ApiResponse<FrozenUtxoInfo>),
+        (status = 500, description = "Failed to persist frozen set", body = ApiResponse<FrozenUtxoInfo>)
+    )
+)]
+async fn unfreeze_utxo(
+    State(state): State<AppState>,
+    Path((id, outpoint)): Path<(String, String)>,
+) -> (StatusCode, Json<ApiResponse<FrozenUtxoInfo>>) {
+    set_frozen(state, id, outpoint, false).await
+}
+
+async fn set_frozen(
+    state: AppState,
+    id: String,
+    outpoint: String,
+    frozen: bool,
+) -> (StatusCode, Json<ApiResponse<FrozenUtxoInfo>>) {
+    let Ok(parsed) = outpoint.parse::<OutPoint>() else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::err(format!(
+                "Invalid outpoint '{outpoint}', expected txid:vout"
+            ))),
+        );
+    };
+    let config = {
+        let mut mgr = state.lock().await;
+        match mgr.set_utxo_frozen(&id, parsed, frozen) {
+            Ok(_) => mgr.get_config(&id),
+            Err(MakerManagerError::NotFound(_)) => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    Json(ApiResponse::err(format!("Maker '{id}' not found"))),
+                )
+            }
+            Err(e) => {
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ApiResponse::err(e.to_string())),
+                )
+            }
+        }
+    };
+
+    let core_locked = match config {
+        Some(config) => match lock_in_core(config, id.clone(), vec![parsed], frozen).await {
+            Ok(()) => frozen,
+            Err(e) => {
+                warn!("Could not update Core lock for {parsed} of maker '{id}': {e}");
+                false
+            }
+        },
+        None => false,
+    };
+    (
+        StatusCode::OK,
+        Json(ApiResponse::ok(FrozenUtxoInfo {
+            outpoint: parsed.to_string(),
+            frozen,
+            core_locked,
+        })),
+    )
+}
+
+/// Export a maker's wallet backup, encrypted with a passphrase
+///
+/// The request must repeat the maker ID in `confirm` and, for encrypted wallets, carry the
+/// current wallet password. The backup can be restored through `POST /api/makers`.
+#[utoipa::path(
+    post,
+    path = "/api/makers/{id}/wallet/backup",
+    tag = "wallet",
+    params(("id" = String, Path, description = "Maker ID")),
+    request_body = WalletBackupRequest,
+    responses(
+        (status = 200, description = "Encrypted coinswap wallet backup", content_type = "application/json"),
+        (status = 400, description = "Missing confirmation or passphrase", body = ApiResponse<String>),
+        (status = 401, description = "Wrong wallet password", body = ApiResponse<String>),
+        (status = 404, description = "Maker not found", body = ApiResponse<String>),
+        (status = 423, description = "Maker locked", body = ApiResponse<String>),
+        (status = 500, description = "Internal error", body = ApiResponse<String>)
+    )
+)]
+async fn backup_wallet(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(body): Json<WalletBackupRequest>,
+) -> Response {
+    let error = |status: StatusCode, msg: String| {
+        (status, Json(ApiResponse::<String>::err(msg))).into_response()
+    };
+    if !state.lock().await.has_maker(&id) {
+        return error(StatusCode::NOT_FOUND, format!("Maker '{id}' not found"));
+    }
+    if body.confirm != id {
+        return error(
+            StatusCode::BAD_REQUEST,
+            format!("Set `confirm` to '{id}' to export the wallet backup"),
+        );
+    }
+    if body.passphrase.is_empty() {
+        return error(
+            StatusCode::BAD_REQUEST,
+            "A backup passphrase is required".to_string(),
+        );
+    }
+    let checked = state
+        .lock()
+        .await
+        .check_wallet_password(&id, body.password.as_deref());
+    match checked {
+        Ok(()) => {}
+        Err(e @ MakerManagerError::WrongPassword(_)) => {
+            return error(StatusCode::UNAUTHORIZED, e.to_string());
+        }
+        Err(e @ MakerManagerError::Locked(_)) => return error(StatusCode::LOCKED, e.to_string()),
+        Err(e) => return error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    }
+
+    let response = state.lock().await.wallet_backup(&id, body.passphrase).await;
+    let backup = match response {
+        Ok(MessageResponse::WalletBackupResp(backup)) => backup,
+        Ok(MessageResponse::ServerError(e)) => {
+            return error(StatusCode::INTERNAL_SERVER_ERROR, e);
+        }
+        Ok(other) => {
+            return error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Unexpected response: {other}"),
+            );
+        }
+        Err(e) => return error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    };
+
+    // Sanitize the maker ID so it can't inject characters into the header value.
+    let safe_id: String = id
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+        .collect();
+    let disposition = format!("attachment; filename=\"maker-{safe_id}-wallet-backup.json\"");
+    let headers = [
+        (
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        ),
+        (
+            header::CONTENT_DISPOSITION,
+            HeaderValue::from_str(&disposition)
+                .unwrap_or_else(|_| HeaderValue::from_static("attachment")),
+        ),
+        (header::CACHE_CONTROL, HeaderValue::from_static("no-store")),
+    ];
+    (StatusCode::OK, headers, backup).into_response()
+}
+
+/// An entry of Core's `listlockunspent`.
+#[derive(Deserialize)]
+struct LockedOutput {
+    txid: Txid,
+    vout: u32,
+}
+
+/// Locks a maker's frozen UTXOs in Core again and forgets the ones that have been spent.
+///
+/// Core keeps coin locks in memory only, so they are lost when bitcoind restarts or the
+/// wallet is reloaded. This runs whenever a maker is loaded, unlocked or started, after
+/// syncs and rescans, and when the events watcher sees the maker's node come back.
+pub(crate) async fn reapply_frozen(state: &AppState, id: &String) -> Result<(), String> {
+    let (config, frozen) = {
+        let mgr = state.lock().await;
+        match (mgr.get_config(id), mgr.frozen_utxos(id)) {
+            (Some(config), Some(frozen)) if !frozen.is_empty() => (config, frozen),
+            _ => return Ok(()),
+        }
+    };
+    let (wallet_config, wallet_id) = (config.clone(), id.clone());
+    let (spent, unlocked) = tokio::task::spawn_blocking(move || {
+        let client = wallet_config
+            .wallet_rpc_client(&wallet_id)
+            .map_err(|e| e.to_string())?;
+        let locked: Vec<LockedOutput> = client
+            .call("listlockunspent", &[])
+            .map_err(|e| e.to_string())?;
+        // Spent means confirmed in the wallet but no longer in the UTXO set; coins the node
+        // hasn't seen yet (e.g. while it syncs) stay frozen
+        let (spent, unspent): (Vec<OutPoint>, Vec<OutPoint>) = frozen.into_iter().partition(|op| {
+            let confirmed = client
+                .get_transaction(&op.txid, Some(true))
+                .is_ok_and(|tx| tx.info.confirmations > 0);
+            confirmed && matches!(client.get_tx_out(&op.txid, op.vout, Some(true)), Ok(None))
+        });
+        let unlocked = unspent
+            .into_iter()
+            .filter(|op| {
+                !locked
+                    .iter()
+                    .any(|l| l.txid == op.txid && l.vout == op.vout)
+            })
+            .collect();
+        Ok::<_, String>((spent, unlocked))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    if !spent.is_empty() {
+        state
+            .lock()
+            .await
+            .forget_frozen_utxos(id, &spent)
+            .map_err(|e| e.to_string())?;
+    }
+    if unlocked.is_empty() {
+        return Ok(());
+    }
+    lock_in_core(config, id.clone(), unlocked, true).await
+}
+
+/// The maker's frozen UTXOs that Core currently holds locked.
+///
+/// Core leaves locked coins out of `listunspent`, so coinswap's UTXO list and balances
+/// don't include them; they are looked up here with `gettxout` instead. Their wallet pool
+/// isn't known outside coinswap, so they are reported as regular coins.
+pub(crate) async fn frozen_coins(state: &AppState, id: &String) -> Result<Vec<WalletUtxo>, String> {
+    let (config, frozen) = {
+        let mgr = state.lock().await;
+        match (mgr.get_config(id), mgr.frozen_utxos(id)) {
+            (Some(config), Some(frozen)) if !frozen.is_empty() => (config, frozen),
+            _ => return Ok(Vec::new()),
+        }
+    };
+    let id = id.clone();
+    tokio::task::spawn_blocking(move || {
+        let client = config.wallet_rpc_client(&id).map_err(|e| e.to_string())?;
+        let locked: Vec<LockedOutput> = client
+            .call("listlockunspent", &[])
+            .map_err(|e| e.to_string())?;
+        let mut coins = Vec::new();
+        for lock in locked {
+            let outpoint = OutPoint::new(lock.txid, lock.vout);
+            if !frozen.contains(&outpoint) {
+                continue;
+            }
+            let Some(out) = client
+                .get_tx_out(&outpoint.txid, outpoint.vout, Some(true))
+                .map_err(|e| e.to_string())?
+            else {
+                continue;
+            };
+            coins.push(WalletUtxo {
+                outpoint,
+                address: out
+                    .script_pub_key
+                    .address
+                    .map(|addr| addr.assume_checked().to_string()),
+                script_pubkey: ScriptBuf::from(out.script_pub_key.hex),
+                amount: out.value,
+                confirmations: out.confirmations,
+                descriptor: None,
+                pool: UtxoPool::Regular,
+                fidelity_index: None,
+                contract_timelock: None,
+            });
+        }
+        Ok(coins)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// [`reapply_frozen`], logging failures.
+pub(crate) async fn relock_frozen(state: &AppState, id: &String) {
+    if let Err(e) = reapply_frozen(state, id).await {
+        warn!("Could not re-apply UTXO locks for maker '{id}': {e}");
+    }
+}
+
+/// Locks (or unlocks) outpoints in the maker's Bitcoin Core wallet.
+///
+/// coinswap funds swaps from the coins Core reports as unspent, and Core leaves locked
+/// coins out of that list; this is how frozen coins are kept out of swap funding. Locks
+/// only live in Core's memory; see [`reapply_frozen`].
+pub(crate) async fn lock_in_core(
+    config: MakerConfig,
+    id: String,
+    outpoints: Vec<OutPoint>,
+    lock: bool,
+) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        let client = config.wallet_rpc_client(&id).map_err(|e| e.to_string())?;
+        // One call per outpoint so a single spent coin doesn't keep the others unlocked
+        let errors: Vec<String> = outpoints
+            .iter()
+            .filter_map(|outpoint| {
+                let result = if lock {
+                    client.lock_unspent(std::slice::from_ref(outpoint))
+                } else {
+                    client.unlock_unspent(std::slice::from_ref(outpoint))
+                };
+                match result {
+                    Ok(true) => None,
+                    Ok(false) => Some(format!("{outpoint}: lockunspent returned false")),
+                    Err(e) => Some(format!("{outpoint}: {e}")),
+                }
+            })
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use coinswap::bitcoin::ScriptBuf;
+
+    use super::{derivation_path, script_type};
+
+    const KEY: &str = "02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5";
+
+    #[test]
+    fn derivation_path_from_key_origin() {
+        assert_eq!(
+            derivation_path(&format!("wpkh([d34db33f/84h/1h/0h/0/5]{KEY})#8fhd9pwu")).as_deref(),
+            Some("m/84h/1h/0h/0/5")
+        );
+        assert_eq!(
+            derivation_path(&format!("tr([d34db33f/86'/1'/0'/1/0]{})", &KEY[2..])).as_deref(),
+            Some("m/86'/1'/0'/1/0")
+        );
+        assert_eq!(derivation_path(&format!("wpkh([d34db33f]{KEY})")), None);
+        assert_eq!(derivation_path("addr(bcrt1qxyz)#abc"), None);
+    }
+
+    #[test]
+    fn script_types() {
+        let script = |hex: &str| ScriptBuf::from_hex(hex).unwrap();
+        let zeros20 = "00".repeat(20);
+        let zeros32 = "00".repeat(32);
+        assert_eq!(script_type(&script(&format!("0014{zeros20}"))), "p2wpkh");
+        assert_eq!(script_type(&script(&format!("0020{zeros32}"))), "p2wsh");
+        assert_eq!(script_type(&script(&format!("5120{zeros32}"))), "p2tr");
+        assert_eq!(script_type(&script(&format!("a914{zeros20}87"))), "p2sh");
+        assert_eq!(
+            script_type(&script(&format!("76a914{zeros20}88ac"))),
+            "p2pkh"
+        );
+        assert_eq!(script_type(&script("6a")), "unknown");
+    }
+}
@@ -0,0 +1,377 @@
+use std::time::Duration;
+
+use axum::{
+    extract::{Path, State},
+    http::{StatusCode, Uri},
+    routing::{get, post, put},
+    Json, Router,
+};
+use tokio::{sync::broadcast::error::RecvError, task::JoinHandle};
+use tracing::{debug, warn};
+
+use super::{
+    dto::{ApiResponse, WebhookDeliveryInfo, WebhookInfo, WebhookRequest},
+    AppState,
+};
+use crate::maker_manager::{
+    unix_now,
+    webhooks::{self, Delivery, Webhook, MAX_ATTEMPTS},
+};
+
+/// Delay before the first retry of a failed delivery; doubled for each further retry.
+const RETRY_BASE: Duration = Duration::from_secs(10);
+
+/// How long a webhook endpoint gets to answer.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/webhooks", get(list_webhooks).post(create_webhook))
+        .route("/webhooks/{id}", put(update_webhook).delete(delete_webhook))
+        .route("/webhooks/{id}/deliveries", get(list_deliveries))
+        .route("/webhooks/{id}/test", post(test_webhook))
+}
+
+fn not_found<T: serde::Serialize>(id: u64) -> (StatusCode, Json<ApiResponse<T>>) {
+    (
+        StatusCode::NOT_FOUND,
+        Json(ApiResponse::err(format!("Webhook {id} not found"))),
+    )
+}
+
+/// Checks the URL and event filter of a webhook request.
+fn validate(body: &WebhookRequest) -> Result<(), String> {
+    let uri: Uri = body
+        .url
+        .parse()
+        .map_err(|e| format!("Invalid webhook URL '{}': {e}", body.url))?;
+    if !matches!(uri.scheme_str(), Some("http" | "https")) || uri.host().is_none() {
+        return Err(format!(
+            "Invalid webhook URL '{}': expected an http:// or https:// URL",
+            body.url
+        ));
+    }
+    webhooks::validate_events(&body.events)
+}
+
+/// List webhooks
+#[utoipa::path(
+    get,
+    path = "/api/webhooks",
+    tag = "webhooks",
+    responses(
+        (status = 200, description = "Configured webhooks", body = ApiResponse<Vec<WebhookInfo>>)
+    )
+)]
+async fn list_webhooks(
+    State(state): State<AppState>,
+) -> (StatusCode, Json<ApiResponse<Vec<WebhookInfo>>>) {
+    let hooks = state.lock().await.webhooks();
+    (
+        StatusCode::OK,
+        Json(ApiResponse::ok(hooks.into_iter().map(Into::into).collect())),
+    )
+}
+
+/// Add a webhook
+///
+/// Matching events are posted to `url` as JSON, the same objects the `/api/events` stream
+/// carries, with an `X-Webhook-Event` header naming the event type and an
+/// `X-Webhook-Timestamp` header with the Unix time of the attempt. With a secret, the
+/// `X-Webhook-Signature` header holds `sha256=` and the hex HMAC-SHA256 of
+/// `{timestamp}.{body}` keyed with it, so receivers can reject stale or replayed deliveries.
+/// Any 2xx response counts as delivered; other outcomes are retried with exponential
+/// backoff, up to 5 attempts. Retries still pending when the dashboard stops are dropped.
+#[utoipa::path(
+    post,
+    path = "/api/webhooks",
+    tag = "webhooks",
+    request_body = WebhookRequest,
+    responses(
+        (status = 201, description = "Webhook added", body = ApiResponse<WebhookInfo>),
+        (status = 400, description = "Invalid URL or event type", body = ApiResponse<WebhookInfo>),
+        (status = 500, description = "Failed to save the webhook", body = ApiResponse<WebhookInfo>)
+    )
+)]
+async fn create_webhook(
+    State(state): State<AppState>,
+    Json(body): Json<WebhookRequest>,
+) -> (StatusCode, Json<ApiResponse<WebhookInfo>>) {
+    if let Err(e) = validate(&body) {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::err(e)));
+    }
+    let webhook = Webhook {
+        id: 0,
+        url: body.url,
+        secret: body.secret.filter(|s| !s.is_empty()),
+        events: body.events,
+        enabled: body.enabled.unwrap_or(true),
+    };
+    match state.lock().await.add_webhook(webhook) {
+        Ok(webhook) => (StatusCode::CREATED, Json(ApiResponse::ok(webhook.into()))),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::err(e.to_string())),
+        ),
+    }
+}
+
+/// Update a webhook
+///
+/// Replaces the URL, event filter and enabled flag. The secret is kept when omitted and
+/// removed when empty.
+#[utoipa::path(
+    put,
+    path = "/api/webhooks/{id}",
+    tag = "webhooks",
+    params(("id" = u64, Path, description = "Webhook ID")),
+    request_body = WebhookRequest,
+    responses(
+        (status = 200, description = "Webhook updated", body = ApiResponse<WebhookInfo>),
+        (status = 400, description = "Invalid URL or event type", body = ApiResponse<WebhookInfo>),
+        (status = 404, description = "Webhook not found", body = ApiResponse<WebhookInfo>),
+        (status = 500, description = "Failed to save the webhook", body = ApiResponse<WebhookInfo>)
+    )
+)]
+async fn update_webhook(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+    Json(body): Json<WebhookRequest>,
+) -> (StatusCode, Json<ApiResponse<WebhookInfo>>) {
+    if let Err(e) = validate(&body) {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::err(e)));
+    }
+    let mut mgr = state.lock().await;
+    let Some(current) = mgr.webhook(id) else {
+        return not_found(id);
+    };
+    let webhook = Webhook {
+        id,
+        url: body.url,
+        secret: match body.secret {
+            None => current.secret,
+            Some(secret) => Some(secret).filter(|s| !s.is_empty()),
+        },
+        events: body.events,
+        enabled: body.enabled.unwrap_or(true),
+    };
+    match mgr.update_webhook(webhook.clone()) {
+        Ok(true) => (StatusCode::OK, Json(ApiResponse::ok(webhook.into()))),
+        Ok(false) => not_found(id),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::err(e.to_string())),
+        ),
+    }
+}
+
+/// Delete a webhook and its delivery log
+#[utoipa::path(
+    delete,
+    path = "/api/webhooks/{id}",
+    tag = "webhooks",
+    params(("id" = u64, Path, description = "Webhook ID")),
+    responses(
+        (status = 200, description = "Webhook removed", body = ApiResponse<String>),
+        (status = 404, description = "Webhook not found", body = ApiResponse<String>),
+        (status = 500, description = "Failed to save the webhooks", body = ApiResponse<String>)
+    )
+)]
+async fn delete_webhook(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    match state.lock().await.remove_webhook(id) {
+        Ok(true) => (
+            StatusCode::OK,
+            Json(ApiResponse::ok(format!("Webhook {id} removed"))),
+        ),
+        Ok(false) => not_found(id),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::err(e.to_string())),
+        ),
+    }
+}
+
+/// Delivery attempts of a webhook, newest first
+#[utoipa::path(
+    get,
+    path = "/api/webhooks/{id}/deliveries",
+    tag = "webhooks",
+    params(("id" = u64, Path, description = "Webhook ID")),
+    responses(
+        (status = 200, description = "Delivery log", body = ApiResponse<Vec<WebhookDeliveryInfo>>),
+        (status = 404, description = "Webhook not found", body = ApiResponse<Vec<WebhookDeliveryInfo>>)
+    )
+)]
+async fn list_deliveries(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+) -> (StatusCode, Json<ApiResponse<Vec<WebhookDeliveryInfo>>>) {
+    match state.lock().await.webhook_deliveries(id) {
+        Some(log) => (
+            StatusCode::OK,
+            Json(ApiResponse::ok(log.into_iter().map(Into::into).collect())),
+        ),
+        None => not_found(id),
+    }
+}
+
+/// Send a test event to a webhook
+///
+/// Posts a `ping` event once, without retries, whether or not the webhook is enabled, and
+/// returns the logged attempt.
+#[utoipa::path(
+    post,
+    path = "/api/webhooks/{id}/test",
+    tag = "webhooks",
+    params(("id" = u64, Path, description = "Webhook ID")),
+    responses(
+        (status = 200, description = "Delivery attempt; check `success`", body = ApiResponse<WebhookDeliveryInfo>),
+        (status = 404, description = "Webhook not found", body = ApiResponse<WebhookDeliveryInfo>)
+    )
+)]
+async fn test_webhook(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+) -> (StatusCode, Json<ApiResponse<WebhookDeliveryInfo>>) {
+    let Some(webhook) = state.lock().await.webhook(id) else {
+        return not_found(id);
+    };
+    let body = serde_json::json!({ "type": "ping", "webhook_id": id, "at": unix_now() });
+    let delivery = deliver(
+        &state,
+        &webhook,
+        None,
+        "ping",
+        body.to_string(),
+        1,
+        RETRY_BASE,
+    )
+    .await;
+    (StatusCode::OK, Json(ApiResponse::ok(delivery.into())))
+}
+
+/// Posts `body` to a webhook once, signed for `timestamp`. Returns the response status, if
+/// any, and the error if the attempt failed.
+fn post_once(
+    webhook: &Webhook,
+    event_type: &str,
+    timestamp: u64,
+    body: &str,
+) -> (Option<u16>, Option<String>) {
+    let agent = ureq::AgentBuilder::new().timeout(DELIVERY_TIMEOUT).build();
+    let mut request = agent
+        .post(&webhook.url)
+        .set("Content-Type", "application/json")
+        .set(
+            "User-Agent",
+            concat!("maker-dashboard/", env!("CARGO_PKG_VERSION")),
+        )
+        .set("X-Webhook-Event", event_type)
+        .set("X-Webhook-Timestamp", &timestamp.to_string());
+    if let Some(secret) = &webhook.secret {
+        request = request.set(
+            "X-Webhook-Signature",
+            &webhooks::signature(secret, timestamp, body.as_bytes()),
+        );
+    }
+    match request.send_string(body) {
+        Ok(response) => (Some(response.status()), None),
+        Err(ureq::Error::Status(status, _)) => (Some(status), Some(format!("HTTP {status}"))),
+        Err(e) => (None, Some(e.to_string())),
+    }
+}
+
+/// Delivers `body` to a webhook, making up to `attempts` attempts with exponential backoff
+/// from `retry_base`. Every attempt is logged; returns the last one.
+pub async fn deliver(
+    state: &AppState,
+    webhook: &Webhook,
+    event_id: Option<u64>,
+    event_type: &str,
+    body: String,
+    attempts: u32,
+    retry_base: Duration,
+) -> Delivery {
+    let mut attempt = 1;
+    loop {
+        let (hook, kind, payload) = (webhook.clone(), event_type.to_string(), body.clone());
+        let at = unix_now();
+        let (status, error) =
+            tokio::task::spawn_blocking(move || post_once(&hook, &kind, at, &payload))
+                .await
+                .unwrap_or_else(|e| (None, Some(e.to_string())));
+        let delivery = state.lock().await.record_delivery(Delivery {
+            id: 0,
+            webhook_id: webhook.id,
+            event_id,
+            event_type: event_type.to_string(),
+            attempt,
+            at,
+            status,
+            error,
+        });
+        match &delivery.error {
+            None => return delivery,
+            Some(e) if attempt >= attempts => {
+                warn!(
+                    "Giving up on delivering {event_type} to webhook {} after {attempt} attempts: {e}",
+                    webhook.id
+                );
+                return delivery;
+            }
+            Some(e) => debug!(
+                "Delivering {event_type} to webhook {} failed (attempt {attempt}): {e}",
+                webhook.id
+            ),
+        }
+        tokio::time::sleep(webhooks::backoff(attempt, retry_base)).await;
+        attempt += 1;
+    }
+}
+
+/// Spawns the task that delivers published events to the webhooks subscribed to them.
+/// Each delivery runs on its own task, so a slow endpoint doesn't hold up the others.
+pub fn spawn_dispatcher(state: AppState) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let bus = state.lock().await.events();
+        let mut sub = bus.subscribe(None);
+        let mut last = sub.last_id;
+        loop {
+            let events = match sub.receiver.recv().await {
+                Ok(event) => vec![event],
+                Err(RecvError::Lagged(_)) => bus.since(last),
+                Err(RecvError::Closed) => return,
+            };
+            for event in events.into_iter().filter(|event| event.id > last) {
+                last = event.id;
+                let (event_id, event_type) = (event.id, event.kind.name());
+                let hooks = state.lock().await.webhooks();
+                let body = match serde_json::to_string(&event) {
+                    Ok(body) => body,
+                    Err(e) => {
+                        warn!("Cannot serialize event {event_id}: {e}");
+                        continue;
+                    }
+                };
+                for webhook in hooks.into_iter().filter(|w| w.wants(event_type)) {
+                    let (state, body) = (state.clone(), body.clone());
+                    tokio::spawn(async move {
+                        deliver(
+                            &state,
+                            &webhook,
+                            Some(event_id),
+                            event_type,
+                            body,
+                            MAX_ATTEMPTS,
+                            RETRY_BASE,
+                        )
+                        .await;
+                    });
+                }
+            }
+        }
+    })
+}
@@ -55,4 +55,8 @@ pub struct Cli {
     /// Application config and data directory. Stores maker configs and wallet data.
     #[arg(long, env = "DASHBOARD_CONFIG_DIR")]
     pub config_dir: Option<PathBuf>,
+
+    /// Seconds between the balance and status samples recorded for each maker's history
+    #[arg(long, default_value_t = 60, value_parser = clap::value_parser!(u64).range(1..), env = "DASHBOARD_HISTORY_INTERVAL")]
+    pub history_interval: u64,
 }
@@ -42,8 +42,9 @@ async fn main() {
         localhost_only: !args.allow_remote,
         config_dir,
     };
+    let history_interval = std::time::Duration::from_secs(args.history_interval);
 
-    match Server::new(config) {
+    match Server::new(config).map(|server| server.with_history_interval(history_interval)) {
         Ok(server) => {
             server
                 .run()
@@ -0,0 +1,474 @@
+//! Threshold alerts.
+//!
+//! Alert rules are evaluated periodically against an [`Observation`] of every maker they
+//! apply to. A rule whose condition holds raises an alert for that maker, which stays
+//! firing, without being raised again, until the condition clears and it is resolved.
+//! Raised and resolved alerts are passed to the [`Notifier`]s.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use super::events::{EventBus, EventKind};
+use super::maker_pool::MakerId;
+
+/// Number of resolved alerts kept.
+pub const RESOLVED_LIMIT: usize = 200;
+
+/// What a rule checks for.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AlertCondition {
+    /// Spendable balance below `sats`.
+    LowBalance { sats: u64 },
+    /// No successful swap for `hours`. Until a maker has one, counted from when the
+    /// dashboard started watching it.
+    NoSuccessfulSwap { hours: u64 },
+    /// The maker has not answered for `minutes` while its server should be running.
+    MakerDown { minutes: u64 },
+    /// The maker's node verified less than `progress` (0 to 1) of the chain.
+    NodeSyncBelow { progress: f64 },
+    /// Less than `bytes` free on the disk of the maker's data directory.
+    DiskFreeBelow { bytes: u64 },
+    /// A fidelity bond unlocks within `blocks`.
+    FidelityExpiring { blocks: u32 },
+}
+
+/// A configured alert rule.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AlertRule {
+    pub id: u64,
+    pub name: String,
+    pub condition: AlertCondition,
+    /// Only this maker; every maker when unset
+    pub maker: Option<MakerId>,
+    pub enabled: bool,
+}
+
+impl AlertRule {
+    pub fn applies_to(&self, maker: &MakerId) -> bool {
+        self.enabled && self.maker.as_ref().is_none_or(|m| m == maker)
+    }
+}
+
+/// The configured rules, as stored in the config dir.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct RuleSet {
+    next_id: u64,
+    rules: Vec<AlertRule>,
+}
+
+impl RuleSet {
+    pub fn rules(&self) -> &[AlertRule] {
+        &self.rules
+    }
+
+    pub fn get(&self, id: u64) -> Option<&AlertRule> {
+        self.rules.iter().find(|r| r.id == id)
+    }
+
+    /// Adds a rule, assigning its ID.
+    pub fn add(&mut self, mut rule: AlertRule) -> AlertRule {
+        self.next_id = self.next_id.max(1);
+        rule.id = self.next_id;
+        self.next_id += 1;
+        self.rules.push(rule.clone());
+        rule
+    }
+
+    /// Replaces the rule with the same ID. Returns `false` if there is none.
+    pub fn update(&mut self, rule: AlertRule) -> bool {
+        match self.rules.iter_mut().find(|r| r.id == rule.id) {
+            Some(existing) => {
+                *existing = rule;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes a rule. Returns `false` if there was no such rule.
+    pub fn remove(&mut self, id: u64) -> bool {
+        let before = self.rules.len();
+        self.rules.retain(|r| r.id != id);
+        self.rules.len() != before
+    }
+}
+
+/// What the evaluator knows about a maker; `None` where it could not be read.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Observation {
+    /// The maker's server was started and not stopped since, whether or not it crashed
+    pub should_run: bool,
+    pub alive: bool,
+    /// When the maker was first seen not alive in its current outage
+    pub down_since: Option<u64>,
+    pub spendable: Option<u64>,
+    /// End time of the latest successful swap, or when the dashboard started watching the
+    /// maker if it has none
+    pub last_success: Option<u64>,
+    /// Verification progress of the maker's node, from 0 to 1
+    pub sync_progress: Option<f64>,
+    pub disk_free: Option<u64>,
+    pub tip: Option<u32>,
+    /// Unlock heights of the maker's unspent fidelity bonds
+    pub bond_locktimes: Option<Vec<u32>>,
+}
+
+/// Outcome of checking a condition.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Check {
+    /// The condition holds, with a description
+    Firing(String),
+    Clear,
+    /// The data the condition needs is unavailable; the alert state is left as is
+    Unknown,
+}
+
+impl AlertCondition {
+    pub fn check(&self, obs: &Observation, now: u64) -> Check {
+        let firing = |holds: bool, message: String| {
+            if holds {
+                Check::Firing(message)
+            } else {
+                Check::Clear
+            }
+        };
+        match *self {
+            Self::LowBalance { sats } => obs.spendable.map_or(Check::Unknown, |spendable| {
+                firing(
+                    spendable < sats,
+                    format!("Spendable balance of {spendable} sats is below {sats} sats"),
+                )
+            }),
+            Self::NoSuccessfulSwap { hours } => obs.last_success.map_or(Check::Unknown, |at| {
+                let idle = now.saturating_sub(at) / 3_600;
+                firing(
+                    idle >= hours,
+                    format!("No successful swap for {idle} hours"),
+                )
+            }),
+            // A maker that was stopped, or never started, isn't down
+            Self::MakerDown { minutes } => match (obs.should_run && !obs.alive, obs.down_since) {
+                (false, _) => Check::Clear,
+                (true, None) => Check::Unknown,
+                (true, Some(since)) => {
+                    let down = now.saturating_sub(since) / 60;
+                    firing(
+                        down >= minutes,
+                        format!("Maker has not answered for {down} minutes"),
+                    )
+                }
+            },
+            Self::NodeSyncBelow { progress } => {
+                obs.sync_progress.map_or(Check::Unknown, |synced| {
+                    firing(
+                        synced < progress,
+                        format!("Node is {:.2}% synced", synced * 100.0),
+                    )
+                })
+            }
+            Self::DiskFreeBelow { bytes } => obs.disk_free.map_or(Check::Unknown, |free| {
+                firing(
+                    free < bytes,
+                    format!("{free} bytes free on the data directory's disk, below {bytes}"),
+                )
+            }),
+            Self::FidelityExpiring { blocks } => match (obs.tip, &obs.bond_locktimes) {
+                (Some(tip), Some(locktimes)) => {
+                    let soonest = locktimes
+                        .iter()
+                        .filter(|&&locktime| locktime > tip)
+                        .map(|locktime| locktime - tip)
+                        .min();
+                    match soonest {
+                        Some(remaining) => firing(
+                            remaining <= blocks,
+                            format!("Fidelity bond unlocks in {remaining} blocks"),
+                        ),
+                        None => Check::Clear,
+                    }
+                }
+                _ => Check::Unknown,
+            },
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertState {
+    Firing,
+    Resolved,
+}
+
+/// An alert raised by a rule for a maker.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Alert {
+    pub id: u64,
+    pub rule_id: u64,
+    pub rule_name: String,
+    pub maker: MakerId,
+    pub state: AlertState,
+    /// Latest description of the condition
+    pub message: String,
+    /// Unix timestamp the alert was raised at
+    pub fired_at: u64,
+    pub resolved_at: Option<u64>,
+    /// Unix timestamp of the latest evaluation that found the condition holding
+    pub updated_at: u64,
+}
+
+/// Receives alerts as they are raised and resolved.
+pub trait Notifier: Send + Sync {
+    fn notify(&self, alert: &Alert);
+}
+
+/// Logs alerts.
+pub struct LogNotifier;
+
+impl Notifier for LogNotifier {
+    fn notify(&self, alert: &Alert) {
+        match alert.state {
+            AlertState::Firing => tracing::warn!(
+                "Alert '{}' firing for maker '{}': {}",
+                alert.rule_name,
+                alert.maker,
+                alert.message
+            ),
+            AlertState::Resolved => tracing::info!(
+                "Alert '{}' resolved for maker '{}'",
+                alert.rule_name,
+                alert.maker
+            ),
+        }
+    }
+}
+
+/// Publishes alerts on the event bus, which also hands them to subscribed webhooks.
+pub struct EventNotifier(pub Arc<EventBus>);
+
+impl Notifier for EventNotifier {
+    fn notify(&self, alert: &Alert) {
+        let kind = match alert.state {
+            AlertState::Firing => EventKind::AlertFiring {
+                alert_id: alert.id,
+                rule: alert.rule_name.clone(),
+                message: alert.message.clone(),
+            },
+            AlertState::Resolved => EventKind::AlertResolved {
+                alert_id: alert.id,
+                rule: alert.rule_name.clone(),
+            },
+        };
+        self.0.publish(Some(&alert.maker), kind);
+    }
+}
+
+/// Firing and recently resolved alerts. Not persisted: after a restart, conditions that
+/// still hold are raised again.
+#[derive(Default)]
+pub struct AlertBook {
+    firing: HashMap<(u64, MakerId), Alert>,
+    /// Newest last
+    resolved: VecDeque<Alert>,
+    next_id: u64,
+}
+
+impl AlertBook {
+    /// Updates the alert of `rule` for `maker` with the outcome of a check. Returns the
+    /// alert if it was raised or resolved, i.e. if notifiers should hear about it.
+    pub fn apply(
+        &mut self,
+        rule: &AlertRule,
+        maker: &MakerId,
+        check: Check,
+        now: u64,
+    ) -> Option<Alert> {
+        let key = (rule.id, maker.clone());
+        match check {
+            Check::Unknown => None,
+            Check::Firing(message) => {
+                if let Some(alert) = self.firing.get_mut(&key) {
+                    alert.message = message;
+                    alert.updated_at = now;
+                    return None;
+                }
+                self.next_id += 1;
+                let alert = Alert {
+                    id: self.next_id,
+                    rule_id: rule.id,
+                    rule_name: rule.name.clone(),
+                    maker: maker.clone(),
+                    state: AlertState::Firing,
+                    message,
+                    fired_at: now,
+                    resolved_at: None,
+                    updated_at: now,
+                };
+                self.firing.insert(key, alert.clone());
+                Some(alert)
+            }
+            Check::Clear => self.resolve(&key, now),
+        }
+    }
+
+    fn resolve(&mut self, key: &(u64, MakerId), now: u64) -> Option<Alert> {
+        let mut alert = self.firing.remove(key)?;
+        alert.state = AlertState::Resolved;
+        alert.resolved_at = Some(now);
+        self.resolved.push_back(alert.clone());
+        if self.resolved.len() > RESOLVED_LIMIT {
+            self.resolved.pop_front();
+        }
+        Some(alert)
+    }
+
+    /// Resolves the firing alerts `keep` rejects, e.g. those of deleted rules or makers.
+    /// Returns them.
+    pub fn resolve_where(&mut self, now: u64, mut keep: impl FnMut(&Alert) -> bool) -> Vec<Alert> {
+        let stale: Vec<_> = self
+            .firing
+            .iter()
+            .filter(|(_, alert)| !keep(alert))
+            .map(|(key, _)| key.clone())
+            .collect();
+        stale
+            .iter()
+            .filter_map(|key| self.resolve(key, now))
+            .collect()
+    }
+
+    /// Firing alerts, newest first, followed by resolved ones, most recently resolved first.
+    pub fn list(&self) -> Vec<Alert> {
+        let mut firing: Vec<_> = self.firing.values().cloned().collect();
+        firing.sort_by(|a, b| b.fired_at.cmp(&a.fired_at).then(b.id.cmp(&a.id)));
+        firing.extend(self.resolved.iter().rev().cloned());
+        firing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AlertBook, AlertCondition, AlertRule, AlertState, Check, Observation, RuleSet};
+
+    const NOW: u64 = 1_700_000_000;
+
+    fn rule(condition: AlertCondition) -> AlertRule {
+        AlertRule {
+            id: 1,
+            name: "test".into(),
+            condition,
+            maker: None,
+            enabled: true,
+        }
+    }
+
+    fn is_firing(condition: AlertCondition, obs: &Observation) -> Option<bool> {
+        match condition.check(obs, NOW) {
+            Check::Firing(_) => Some(true),
+            Check::Clear => Some(false),
+            Check::Unknown => None,
+        }
+    }
+
+    #[test]
+    fn conditions() {
+        let obs = Observation {
+            should_run: true,
+            alive: false,
+            down_since: Some(NOW - 10 * 60),
+            spendable: Some(50_000),
+            last_success: Some(NOW - 5 * 3_600),
+            sync_progress: Some(0.9995),
+            disk_free: Some(2_000_000_000),
+            tip: Some(1_000),
+            bond_locktimes: Some(vec![900, 1_100]),
+        };
+        let low = |sats| AlertCondition::LowBalance { sats };
+        assert_eq!(is_firing(low(100_000), &obs), Some(true));
+        assert_eq!(is_firing(low(50_000), &obs), Some(false));
+        let idle = |hours| AlertCondition::NoSuccessfulSwap { hours };
+        assert_eq!(is_firing(idle(4), &obs), Some(true));
+        assert_eq!(is_firing(idle(6), &obs), Some(false));
+        let down = |minutes| AlertCondition::MakerDown { minutes };
+        assert_eq!(is_firing(down(10), &obs), Some(true));
+        assert_eq!(is_firing(down(15), &obs), Some(false));
+        let sync = |progress| AlertCondition::NodeSyncBelow { progress };
+        assert_eq!(is_firing(sync(0.999), &obs), Some(false));
+        assert_eq!(is_firing(sync(0.9999), &obs), Some(true));
+        let disk = |bytes| AlertCondition::DiskFreeBelow { bytes };
+        assert_eq!(is_firing(disk(5_000_000_000), &obs), Some(true));
+        // The expired bond at 900 doesn't count
+        let bonds = |blocks| AlertCondition::FidelityExpiring { blocks };
+        assert_eq!(is_firing(bonds(100), &obs), Some(true));
+        assert_eq!(is_firing(bonds(99), &obs), Some(false));
+
+        let unknown = Observation {
+            should_run: true,
+            ..Observation::default()
+        };
+        assert_eq!(is_firing(low(1), &unknown), None);
+        assert_eq!(is_firing(down(1), &unknown), None);
+        let alive = Observation {
+            alive: true,
+            ..unknown.clone()
+        };
+        assert_eq!(is_firing(down(1), &alive), Some(false));
+        let stopped = Observation {
+            should_run: false,
+            ..obs
+        };
+        assert_eq!(is_firing(down(1), &stopped), Some(false));
+    }
+
+    #[test]
+    fn alerts_fire_once_and_resolve() {
+        let rule = rule(AlertCondition::LowBalance { sats: 10 });
+        let maker = "m1".to_string();
+        let mut book = AlertBook::default();
+        let fired = book
+            .apply(&rule, &maker, Check::Firing("low".into()), NOW)
+            .unwrap();
+        assert_eq!(fired.state, AlertState::Firing);
+        // Deduplicated while firing, but the message follows the latest check
+        assert!(book
+            .apply(&rule, &maker, Check::Firing("lower".into()), NOW + 60)
+            .is_none());
+        assert!(book
+            .apply(&rule, &maker, Check::Unknown, NOW + 120)
+            .is_none());
+        assert_eq!(book.list()[0].message, "lower");
+
+        let resolved = book.apply(&rule, &maker, Check::Clear, NOW + 180).unwrap();
+        assert_eq!(resolved.id, fired.id);
+        assert_eq!(resolved.state, AlertState::Resolved);
+        assert_eq!(resolved.resolved_at, Some(NOW + 180));
+        assert!(book.apply(&rule, &maker, Check::Clear, NOW + 240).is_none());
+
+        let again = book
+            .apply(&rule, &maker, Check::Firing("low".into()), NOW + 300)
+            .unwrap();
+        assert_ne!(again.id, fired.id);
+        let listed = book.list();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[0].state, AlertState::Firing);
+
+        assert_eq!(book.resolve_where(NOW + 360, |a| a.maker != maker).len(), 1);
+        assert!(book.list().iter().all(|a| a.state == AlertState::Resolved));
+    }
+
+    #[test]
+    fn rule_ids_are_not_reused() {
+        let mut rules = RuleSet::default();
+        let first = rules.add(rule(AlertCondition::MakerDown { minutes: 5 }));
+        let second = rules.add(rule(AlertCondition::MakerDown { minutes: 10 }));
+        assert_eq!((first.id, second.id), (1, 2));
+        assert!(rules.remove(second.id));
+        assert!(!rules.remove(second.id));
+        assert_eq!(rules.add(second.clone()).id, 3);
+        assert!(!rules.update(second));
+        assert_eq!(rules.rules().len(), 2);
+    }
+}
@@ -0,0 +1,114 @@
+//! Capital committed by a maker over time.
+//!
+//! Swap earnings only tell half the story: liquidity sitting in the wallet and coins locked
+//! in fidelity bonds are capital the operator could deploy elsewhere. Returns are measured
+//! against the average of the committed balances recorded in the maker's history.
+
+use super::history::HistoryPoint;
+
+/// Committed balances of a maker at one point in time, in sats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapitalSample {
+    /// Unix timestamp of the reading
+    pub at: u64,
+    pub regular: u64,
+    pub swap: u64,
+    pub fidelity: u64,
+}
+
+impl CapitalSample {
+    /// The committed balances of a history point, if its wallet was read.
+    pub fn from_history(point: &HistoryPoint) -> Option<Self> {
+        point.wallet.map(|wallet| Self {
+            at: point.at,
+            regular: wallet.regular,
+            swap: wallet.swap,
+            fidelity: wallet.fidelity,
+        })
+    }
+}
+
+/// Time-weighted average of the committed balances over a period, in sats.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CapitalAverage {
+    pub regular: f64,
+    pub swap: f64,
+    pub fidelity: f64,
+    /// Seconds of the period covered by samples
+    pub covered_secs: u64,
+}
+
+impl CapitalAverage {
+    /// Regular and swap liquidity, i.e. capital available to swaps.
+    pub fn liquid(&self) -> f64 {
+        self.regular + self.swap
+    }
+
+    pub fn total(&self) -> f64 {
+        self.liquid() + self.fidelity
+    }
+}
+
+/// Averages `samples` (oldest first) over `from..to`, holding each reading until the next.
+///
+/// The period starts at the first sample if none precedes `from`. Returns `None` when no
+/// sample covers any part of the period.
+pub fn average(samples: &[CapitalSample], from: u64, to: u64) -> Option<CapitalAverage> {
+    let mut sums = [0f64; 3];
+    let mut covered = 0u64;
+    for (i, sample) in samples.iter().enumerate() {
+        let until = samples.get(i + 1).map_or(to, |next| next.at.min(to));
+        let since = sample.at.max(from);
+        if until <= since {
+            continue;
+        }
+        let secs = until - since;
+        sums[0] += sample.regular as f64 * secs as f64;
+        sums[1] += sample.swap as f64 * secs as f64;
+        sums[2] += sample.fidelity as f64 * secs as f64;
+        covered += secs;
+    }
+    (covered > 0).then(|| CapitalAverage {
+        regular: sums[0] / covered as f64,
+        swap: sums[1] / covered as f64,
+        fidelity: sums[2] / covered as f64,
+        covered_secs: covered,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{average, CapitalSample};
+
+    fn sample(at: u64, regular: u64, fidelity: u64) -> CapitalSample {
+        CapitalSample {
+            at,
+            regular,
+            swap: 0,
+            fidelity,
+        }
+    }
+
+    #[test]
+    fn readings_hold_until_the_next_sample() {
+        let samples = [sample(0, 100, 0), sample(10, 300, 50), sample(40, 0, 0)];
+        // 0..10 at 100, 10..30 at 300
+        let avg = average(&samples, 0, 30).unwrap();
+        assert_eq!(avg.covered_secs, 30);
+        assert!((avg.regular - 233.333).abs() < 0.001);
+        assert!((avg.fidelity - 33.333).abs() < 0.001);
+        // A sample before the period sets its opening value
+        let avg = average(&samples, 20, 50).unwrap();
+        assert_eq!(avg.regular, 200.0);
+    }
+
+    #[test]
+    fn uncovered_periods_have_no_average() {
+        let samples = [sample(100, 100, 0)];
+        assert!(average(&samples, 0, 100).is_none());
+        assert!(average(&[], 0, 100).is_none());
+        // Only the covered part counts
+        let avg = average(&samples, 0, 200).unwrap();
+        assert_eq!((avg.covered_secs, avg.regular), (100, 100.0));
+    }
+}
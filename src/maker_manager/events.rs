@@ -0,0 +1,290 @@
+//! In-process bus for dashboard events.
+//!
+//! State changes made through the [`MakerManager`](super::MakerManager) and changes observed
+//! by the event watcher are published here. Subscribers receive every event published after
+//! they subscribed; a bounded backlog lets reconnecting clients resume from the last event
+//! they saw.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+use super::maker_pool::MakerId;
+use super::unix_now;
+
+/// Events kept for clients resuming from an earlier event.
+pub const BACKLOG_LEN: usize = 1_000;
+
+/// What happened.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EventKind {
+    MakerCreated,
+    MakerDeleted,
+    MakerStarted,
+    MakerStopped,
+    /// The coinswap server exited without being stopped.
+    MakerCrashed,
+    ConfigUpdated,
+    /// Wallet balances changed, in sats.
+    BalanceChanged {
+        regular: u64,
+        swap: u64,
+        contract: u64,
+        fidelity: u64,
+        spendable: u64,
+    },
+    NewUtxo {
+        outpoint: String,
+        amount: u64,
+        /// Wallet pool, e.g. `regular` or `fidelity`
+        pool: String,
+    },
+    /// A coin appeared in the wallet's swap pool. Only funded swaps produce one, so this
+    /// is not a record of every swap attempt.
+    SwapCoinReceived {
+        outpoint: String,
+        amount: u64,
+    },
+    SwapCompleted {
+        swap_id: String,
+        /// Fee earned (or, if negative, paid) in sats
+        fee: i64,
+    },
+    SwapFailed {
+        swap_id: String,
+        error: Option<String>,
+    },
+    /// The spendable balance fell below the maker's minimum swap amount.
+    LowBalance {
+        spendable: u64,
+        threshold: u64,
+    },
+    /// A fidelity bond entered the renewal window of the maker's renewal policy.
+    FidelityExpiring {
+        index: u32,
+        locktime: u32,
+        blocks_remaining: u32,
+    },
+    /// The node behind `rpc` reached a new tip.
+    NewBlock {
+        rpc: String,
+        height: u64,
+    },
+    BitcoindUp {
+        rpc: String,
+    },
+    BitcoindDown {
+        rpc: String,
+    },
+    /// An alert rule's condition started holding for the maker.
+    AlertFiring {
+        alert_id: u64,
+        rule: String,
+        message: String,
+    },
+    AlertResolved {
+        alert_id: u64,
+        rule: String,
+    },
+}
+
+impl EventKind {
+    /// Every event type.
+    pub const NAMES: [&'static str; 18] = [
+        "maker_created",
+        "maker_deleted",
+        "maker_started",
+        "maker_stopped",
+        "maker_crashed",
+        "config_updated",
+        "balance_changed",
+        "new_utxo",
+        "swap_coin_received",
+        "swap_completed",
+        "swap_failed",
+        "low_balance",
+        "fidelity_expiring",
+        "new_block",
+        "bitcoind_up",
+        "bitcoind_down",
+        "alert_firing",
+        "alert_resolved",
+    ];
+
+    /// The `type` tag of the event, e.g. `maker_crashed`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::MakerCreated => "maker_created",
+            Self::MakerDeleted => "maker_deleted",
+            Self::MakerStarted => "maker_started",
+            Self::MakerStopped => "maker_stopped",
+            Self::MakerCrashed => "maker_crashed",
+            Self::ConfigUpdated => "config_updated",
+            Self::BalanceChanged { .. } => "balance_changed",
+            Self::NewUtxo { .. } => "new_utxo",
+            Self::SwapCoinReceived { .. } => "swap_coin_received",
+            Self::SwapCompleted { .. } => "swap_completed",
+            Self::SwapFailed { .. } => "swap_failed",
+            Self::LowBalance { .. } => "low_balance",
+            Self::FidelityExpiring { .. } => "fidelity_expiring",
+            Self::NewBlock { .. } => "new_block",
+            Self::BitcoindUp { .. } => "bitcoind_up",
+            Self::BitcoindDown { .. } => "bitcoind_down",
+            Self::AlertFiring { .. } => "alert_firing",
+            Self::AlertResolved { .. } => "alert_resolved",
+        }
+    }
+}
+
+/// A published event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+    /// Increases with every event. Seeded from the clock at startup, so IDs keep increasing
+    /// across dashboard restarts.
+    pub id: u64,
+    /// Unix timestamp the event was published at
+    pub at: u64,
+    /// Maker the event concerns; `None` for node events
+    pub maker: Option<MakerId>,
+    #[serde(flatten)]
+    pub kind: EventKind,
+}
+
+/// A subscriber's view of the bus.
+pub struct Subscription {
+    /// Retained events after the requested ID, oldest first
+    pub replay: Vec<Event>,
+    /// Events published after subscribing
+    pub receiver: broadcast::Receiver<Event>,
+    /// ID of the last event published before subscribing
+    pub last_id: u64,
+}
+
+struct Backlog {
+    next_id: u64,
+    events: VecDeque<Event>,
+}
+
+/// Publishes events to live subscribers and keeps the latest [`BACKLOG_LEN`] for replay.
+pub struct EventBus {
+    backlog: Mutex<Backlog>,
+    sender: broadcast::Sender<Event>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(BACKLOG_LEN);
+        Self {
+            backlog: Mutex::new(Backlog {
+                // Keeps IDs increasing across restarts
+                next_id: unix_now() * 1_000,
+                events: VecDeque::with_capacity(BACKLOG_LEN),
+            }),
+            sender,
+        }
+    }
+
+    /// Publishes an event and returns it.
+    pub fn publish(&self, maker: Option<&MakerId>, kind: EventKind) -> Event {
+        let mut backlog = self.backlog.lock().unwrap_or_else(|e| e.into_inner());
+        let event = Event {
+            id: backlog.next_id,
+            at: unix_now(),
+            maker: maker.cloned(),
+            kind,
+        };
+        backlog.next_id += 1;
+        if backlog.events.len() == BACKLOG_LEN {
+            backlog.events.pop_front();
+        }
+        backlog.events.push_back(event.clone());
+        // Sent under the lock so subscribers never see an event twice or miss one
+        let _ = self.sender.send(event.clone());
+        event
+    }
+
+    /// Subscribes to the events published from now on, first replaying the retained events
+    /// after `after`, if given.
+    pub fn subscribe(&self, after: Option<u64>) -> Subscription {
+        let backlog = self.backlog.lock().unwrap_or_else(|e| e.into_inner());
+        Subscription {
+            replay: after.map_or_else(Vec::new, |after| Self::after(&backlog.events, after)),
+            receiver: self.sender.subscribe(),
+            last_id: backlog.next_id - 1,
+        }
+    }
+
+    /// Retained events after `after`, oldest first.
+    pub fn since(&self, after: u64) -> Vec<Event> {
+        let backlog = self.backlog.lock().unwrap_or_else(|e| e.into_inner());
+        Self::after(&backlog.events, after)
+    }
+
+    fn after(events: &VecDeque<Event>, after: u64) -> Vec<Event> {
+        let start = events.partition_point(|event| event.id <= after);
+        events.range(start..).cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EventBus, EventKind, BACKLOG_LEN};
+
+    #[test]
+    fn subscribers_resume_after_the_last_seen_event() {
+        let bus = EventBus::new();
+        let maker = "m1".to_string();
+        let first = bus.publish(Some(&maker), EventKind::MakerCreated);
+        let second = bus.publish(Some(&maker), EventKind::MakerStarted);
+        assert_eq!(second.id, first.id + 1);
+
+        let mut sub = bus.subscribe(Some(first.id));
+        assert_eq!(sub.replay, [second.clone()]);
+        assert_eq!(sub.last_id, second.id);
+        let third = bus.publish(None, EventKind::BitcoindDown { rpc: "node".into() });
+        assert_eq!(sub.receiver.try_recv().unwrap(), third);
+
+        let sub = bus.subscribe(None);
+        assert!(sub.replay.is_empty());
+        assert_eq!(sub.last_id, third.id);
+        assert_eq!(bus.since(0).len(), 3);
+    }
+
+    #[test]
+    fn backlog_is_bounded() {
+        let bus = EventBus::new();
+        let first = bus.publish(None, EventKind::MakerCreated);
+        for _ in 0..BACKLOG_LEN {
+            bus.publish(None, EventKind::MakerStopped);
+        }
+        let retained = bus.since(0);
+        assert_eq!(retained.len(), BACKLOG_LEN);
+        assert_eq!(retained[0].id, first.id + 1);
+    }
+
+    #[test]
+    fn events_serialize_with_a_type_tag() {
+        let bus = EventBus::new();
+        let event = bus.publish(
+            Some(&"m1".to_string()),
+            EventKind::SwapFailed {
+                swap_id: "s1".into(),
+                error: None,
+            },
+        );
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "swap_failed");
+        assert_eq!(json["maker"], "m1");
+        assert_eq!(json["swap_id"], "s1");
+        assert_eq!(event.kind.name(), "swap_failed");
+    }
+}
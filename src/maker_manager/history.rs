@@ -0,0 +1,452 @@
+//! On-disk time series of maker balances and status.
+//!
+//! Samples are appended to JSON-lines files under `makers/<id>/history/` in the config dir,
+//! next to the rest of the maker's dashboard state. Raw samples are rolled up into hourly
+//! and daily points as their intervals complete, and each tier is pruned to its retention,
+//! so long-running makers keep a bounded history at decreasing resolution.
+
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::maker_pool::MakerId;
+
+const HOUR: u64 = 3_600;
+const DAY: u64 = 24 * HOUR;
+
+/// A resolution the history is kept at.
+struct Tier {
+    file: &'static str,
+    /// Width of the points in seconds; raw samples have none
+    resolution: u64,
+    /// How long points are kept, in seconds
+    retention: u64,
+}
+
+const TIERS: [Tier; 3] = [
+    Tier {
+        file: "raw.jsonl",
+        resolution: 0,
+        retention: 2 * DAY,
+    },
+    Tier {
+        file: "hourly.jsonl",
+        resolution: HOUR,
+        retention: 60 * DAY,
+    },
+    Tier {
+        file: "daily.jsonl",
+        resolution: DAY,
+        retention: 5 * 365 * DAY,
+    },
+];
+
+/// UTXO counts per wallet pool.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct UtxoCounts {
+    pub regular: u32,
+    pub swept: u32,
+    pub swap: u32,
+    pub contract: u32,
+    pub fidelity: u32,
+}
+
+/// Wallet figures of a history point, averaged over the samples that had them.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WalletPoint {
+    /// Samples the wallet answered in
+    pub samples: u32,
+    pub regular: u64,
+    pub swap: u64,
+    pub contract: u64,
+    pub fidelity: u64,
+    pub spendable: u64,
+    pub utxos: UtxoCounts,
+}
+
+/// A raw sample, or the merge of the samples of an interval starting at `at`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct HistoryPoint {
+    pub at: u64,
+    pub samples: u32,
+    /// Share of samples the maker answered in, from 0 to 1
+    pub alive: f64,
+    /// Share of samples the coinswap server was running in, from 0 to 1
+    pub running: f64,
+    /// Highest block height of the maker's node
+    pub height: Option<u64>,
+    /// Absent when the wallet could not be read
+    pub wallet: Option<WalletPoint>,
+}
+
+impl HistoryPoint {
+    /// A single reading taken at `at`.
+    pub fn sample(at: u64, alive: bool, running: bool, height: Option<u64>) -> Self {
+        Self {
+            at,
+            samples: 1,
+            alive: if alive { 1.0 } else { 0.0 },
+            running: if running { 1.0 } else { 0.0 },
+            height,
+            wallet: None,
+        }
+    }
+}
+
+/// Figures of a bitcoind node from one sampling round.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct NodeSample {
+    pub up: bool,
+    pub height: Option<u64>,
+    /// Initial block download progress from 0 to 1
+    pub progress: Option<f64>,
+}
+
+/// Latest readings of the history sampler, kept in memory so `/metrics` and the alert
+/// evaluator don't poll the makers and nodes themselves.
+#[derive(Debug, Clone, Default)]
+pub struct Snapshot {
+    /// Latest raw sample of each maker
+    pub makers: HashMap<MakerId, HistoryPoint>,
+    /// Latest figures of each node, keyed by RPC address since makers often share one
+    pub nodes: HashMap<String, NodeSample>,
+    /// Unlock heights of each maker's unspent fidelity bonds, as last listed
+    pub bond_locktimes: HashMap<MakerId, Vec<u32>>,
+}
+
+/// Merges `points` into one point starting at `at`, weighting averages by sample count.
+pub fn merge(at: u64, points: &[HistoryPoint]) -> HistoryPoint {
+    let samples: u32 = points.iter().map(|p| p.samples).sum();
+    let weighted = |f: fn(&HistoryPoint) -> f64| {
+        let sum: f64 = points.iter().map(|p| f(p) * f64::from(p.samples)).sum();
+        if samples == 0 {
+            0.0
+        } else {
+            sum / f64::from(samples)
+        }
+    };
+    let wallets: Vec<WalletPoint> = points.iter().filter_map(|p| p.wallet).collect();
+    let wallet_samples: u32 = wallets.iter().map(|w| w.samples).sum();
+    let avg_u64 = |f: fn(&WalletPoint) -> u64| wallet_average(&wallets, f);
+    let avg_u32 = |f: fn(&WalletPoint) -> u32| wallet_average(&wallets, |w| u64::from(f(w))) as u32;
+    HistoryPoint {
+        at,
+        samples,
+        alive: weighted(|p| p.alive),
+        running: weighted(|p| p.running),
+        height: points.iter().filter_map(|p| p.height).max(),
+        wallet: (wallet_samples > 0).then(|| WalletPoint {
+            samples: wallet_samples,
+            regular: avg_u64(|w| w.regular),
+            swap: avg_u64(|w| w.swap),
+            contract: avg_u64(|w| w.contract),
+            fidelity: avg_u64(|w| w.fidelity),
+            spendable: avg_u64(|w| w.spendable),
+            utxos: UtxoCounts {
+                regular: avg_u32(|w| w.utxos.regular),
+                swept: avg_u32(|w| w.utxos.swept),
+                swap: avg_u32(|w| w.utxos.swap),
+                contract: avg_u32(|w| w.utxos.contract),
+                fidelity: avg_u32(|w| w.utxos.fidelity),
+            },
+        }),
+    }
+}
+
+/// Average of `f` over `wallets`, weighted by their sample counts.
+fn wallet_average(wallets: &[WalletPoint], f: impl Fn(&WalletPoint) -> u64) -> u64 {
+    let samples: u64 = wallets.iter().map(|w| u64::from(w.samples)).sum();
+    if samples == 0 {
+        return 0;
+    }
+    let sum: u128 = wallets
+        .iter()
+        .map(|w| u128::from(f(w)) * u128::from(w.samples))
+        .sum();
+    (sum as f64 / samples as f64).round() as u64
+}
+
+/// Groups `points` (oldest first) into `step`-second buckets aligned to `from` and merges
+/// each non-empty bucket.
+pub fn downsample(points: &[HistoryPoint], from: u64, step: u64) -> Vec<HistoryPoint> {
+    let mut out = Vec::new();
+    let mut start = 0;
+    while start < points.len() {
+        let bucket = points[start].at.saturating_sub(from) / step;
+        let end = start
+            + points[start..]
+                .iter()
+                .take_while(|p| p.at.saturating_sub(from) / step == bucket)
+                .count();
+        out.push(merge(from + bucket * step, &points[start..end]));
+        start = end;
+    }
+    out
+}
+
+/// Rolls the points of `finer` that fall in completed `resolution` intervals before
+/// `until` and after the last point of `coarser` into new `coarser` points.
+fn roll_up(
+    finer: &[HistoryPoint],
+    coarser: &[HistoryPoint],
+    resolution: u64,
+    until: u64,
+) -> Vec<HistoryPoint> {
+    let rolled_until = coarser.last().map_or(0, |last| last.at + resolution);
+    let boundary = until / resolution * resolution;
+    let pending: Vec<HistoryPoint> = finer
+        .iter()
+        .filter(|p| p.at >= rolled_until && p.at < boundary)
+        .copied()
+        .collect();
+    downsample(&pending, 0, resolution)
+}
+
+/// The tiers of one maker, oldest point first.
+#[derive(Default)]
+struct Series {
+    tiers: [Vec<HistoryPoint>; 3],
+}
+
+/// Per-maker balance and status history.
+pub struct HistoryStore {
+    /// The config dir's `makers/` directory, so removing a maker's state removes its history
+    root: PathBuf,
+    series: HashMap<MakerId, Series>,
+}
+
+impl HistoryStore {
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            series: HashMap::new(),
+        }
+    }
+
+    fn dir(&self, id: &str) -> PathBuf {
+        self.root.join(id).join("history")
+    }
+
+    fn series(&mut self, id: &MakerId) -> &mut Series {
+        if !self.series.contains_key(id) {
+            let dir = self.dir(id);
+            let mut series = Series::default();
+            for (points, tier) in series.tiers.iter_mut().zip(&TIERS) {
+                *points = fs::read_to_string(dir.join(tier.file))
+                    .map(|raw| {
+                        raw.lines()
+                            .filter_map(|line| serde_json::from_str(line).ok())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+            }
+            self.series.insert(id.clone(), series);
+        }
+        self.series.get_mut(id).expect("just inserted")
+    }
+
+    fn append_lines(&self, id: &str, tier: &Tier, points: &[HistoryPoint]) -> Result<()> {
+        let dir = self.dir(id);
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create history dir: {}", dir.display()))?;
+        let path = dir.join(tier.file);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open history file: {}", path.display()))?;
+        for point in points {
+            writeln!(file, "{}", serde_json::to_string(point)?)
+                .with_context(|| format!("Failed to write history file: {}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    fn rewrite(&self, id: &str, tier: &Tier, points: &[HistoryPoint]) -> Result<()> {
+        let path = self.dir(id).join(tier.file);
+        let tmp = path.with_extension("jsonl.tmp");
+        let mut contents = String::new();
+        for point in points {
+            contents.push_str(&serde_json::to_string(point)?);
+            contents.push('\n');
+        }
+        fs::write(&tmp, contents)
+            .with_context(|| format!("Failed to write history file: {}", tmp.display()))?;
+        fs::rename(&tmp, &path)
+            .with_context(|| format!("Failed to replace history file: {}", path.display()))
+    }
+
+    /// Records a raw sample, rolling up completed intervals into the coarser tiers and
+    /// pruning every tier to its retention once a day completes.
+    pub fn append(&mut self, id: &MakerId, point: HistoryPoint) -> Result<()> {
+        let series = self.series(id);
+        series.tiers[0].push(point);
+        let mut added = vec![vec![point], Vec::new(), Vec::new()];
+        for i in 1..TIERS.len() {
+            let rolled = roll_up(
+                &series.tiers[i - 1],
+                &series.tiers[i],
+                TIERS[i].resolution,
+                point.at,
+            );
+            series.tiers[i].extend(&rolled);
+            added[i] = rolled;
+        }
+        let mut pruned: [Option<Vec<HistoryPoint>>; 3] = Default::default();
+        if !added[2].is_empty() {
+            for (i, tier) in TIERS.iter().enumerate() {
+                let cutoff = point.at.saturating_sub(tier.retention);
+                let before = series.tiers[i].len();
+                series.tiers[i].retain(|p| p.at >= cutoff);
+                if series.tiers[i].len() != before {
+                    pruned[i] = Some(series.tiers[i].clone());
+                }
+            }
+        }
+
+        for (i, tier) in TIERS.iter().enumerate() {
+            match &pruned[i] {
+                Some(points) => self.rewrite(id, tier, points)?,
+                None if !added[i].is_empty() => self.append_lines(id, tier, &added[i])?,
+                None => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Points of the finest tier reaching back to `from`, covering `from..to`, plus the last
+    /// point before `from` so values can be carried into the period.
+    pub fn points(&mut self, id: &MakerId, from: u64, to: u64) -> Vec<HistoryPoint> {
+        let series = self.series(id);
+        let tier = pick_tier(&series.tiers, from, 0);
+        let points = &series.tiers[tier];
+        let start = points.partition_point(|p| p.at < from).saturating_sub(1);
+        points[start..]
+            .iter()
+            .take_while(|p| p.at < to)
+            .copied()
+            .collect()
+    }
+
+    /// History of `from..to` in `step`-second buckets, read from the finest tier that reaches
+    /// back to `from` at a resolution of at most `step`.
+    pub fn range(&mut self, id: &MakerId, from: u64, to: u64, step: u64) -> Vec<HistoryPoint> {
+        let series = self.series(id);
+        let tier = pick_tier(&series.tiers, from, step);
+        let points: Vec<HistoryPoint> = series.tiers[tier]
+            .iter()
+            .filter(|p| p.at >= from && p.at < to)
+            .copied()
+            .collect();
+        downsample(&points, from, step)
+    }
+
+    /// Forgets the cached history of a removed maker.
+    pub fn remove(&mut self, id: &MakerId) {
+        self.series.remove(id);
+    }
+}
+
+/// Index of the finest tier with a resolution of at most `step` whose points reach back to
+/// `from`; the tier reaching furthest back if none does.
+fn pick_tier(tiers: &[Vec<HistoryPoint>; 3], from: u64, step: u64) -> usize {
+    let reaches = |i: usize| tiers[i].first().is_some_and(|p| p.at <= from);
+    (0..TIERS.len())
+        .filter(|&i| TIERS[i].resolution <= step)
+        .find(|&i| reaches(i))
+        .or_else(|| (0..TIERS.len()).find(|&i| reaches(i)))
+        .unwrap_or_else(|| {
+            (0..TIERS.len())
+                .filter(|&i| !tiers[i].is_empty())
+                .min_by_key(|&i| tiers[i][0].at)
+                .unwrap_or(0)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{downsample, merge, HistoryPoint, HistoryStore, WalletPoint, DAY, HOUR};
+
+    fn point(at: u64, alive: bool, regular: Option<u64>) -> HistoryPoint {
+        HistoryPoint {
+            wallet: regular.map(|regular| WalletPoint {
+                samples: 1,
+                regular,
+                ..WalletPoint::default()
+            }),
+            ..HistoryPoint::sample(at, alive, alive, Some(at / 600))
+        }
+    }
+
+    #[test]
+    fn merging_weights_by_samples() {
+        let merged = merge(0, &[point(0, true, Some(100)), point(60, false, None)]);
+        assert_eq!(merged.samples, 2);
+        assert_eq!(merged.alive, 0.5);
+        assert_eq!(merged.height, Some(0));
+        // Only the sample with a wallet reading counts towards balances
+        assert_eq!(
+            merged.wallet.map(|w| (w.samples, w.regular)),
+            Some((1, 100))
+        );
+
+        let again = merge(0, &[merged, point(120, true, Some(400))]);
+        assert_eq!(again.samples, 3);
+        assert!((again.alive - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(again.wallet.map(|w| w.regular), Some(250));
+    }
+
+    #[test]
+    fn downsampling_aligns_buckets_to_from() {
+        let points: Vec<_> = (0..6).map(|i| point(100 + i * 50, true, Some(i))).collect();
+        let buckets = downsample(&points, 100, 100);
+        assert_eq!(
+            buckets
+                .iter()
+                .map(|p| (p.at, p.samples))
+                .collect::<Vec<_>>(),
+            vec![(100, 2), (200, 2), (300, 2)]
+        );
+    }
+
+    #[test]
+    fn samples_roll_up_prune_and_persist() {
+        let dir = std::env::temp_dir().join(format!("maker-history-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let id = "maker1".to_string();
+        let mut store = HistoryStore::new(dir.clone());
+        // One sample every 10 minutes for four days
+        let start = 100 * DAY;
+        for i in 0..(4 * 24 * 6) {
+            store
+                .append(&id, point(start + i * 600, true, Some(i)))
+                .unwrap();
+        }
+
+        // A fresh store reads the same tiers back from disk
+        let mut reloaded = HistoryStore::new(dir.clone());
+        let series = reloaded.series(&id);
+        assert_eq!(series.tiers[1].len(), 4 * 24 - 1);
+        assert_eq!(series.tiers[1][0].samples, 6);
+        assert_eq!(series.tiers[2].len(), 3);
+        // Raw samples older than two days were pruned when the third day completed
+        assert_eq!(series.tiers[0][0].at, start + DAY);
+        assert_eq!(series.tiers[0].len(), 3 * 24 * 6);
+
+        // Old periods fall back to the hourly tier
+        let hourly = reloaded.range(&id, start, start + DAY, HOUR);
+        assert_eq!(hourly.len(), 24);
+        assert!(hourly.iter().all(|p| p.samples == 6));
+        let daily = reloaded.range(&id, start, start + 2 * DAY, DAY);
+        assert_eq!(
+            daily.iter().map(|p| p.samples).collect::<Vec<_>>(),
+            vec![144, 144]
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,191 @@
+//! Per-maker wallet labels in the BIP-329 format.
+//!
+//! Labels are kept by the dashboard rather than the coinswap wallet, one JSON object per
+//! line, so they can be exchanged with any BIP-329 aware wallet.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use coinswap::bitcoin::{Address, OutPoint, Txid};
+use serde::{Deserialize, Serialize};
+
+/// What a label refers to, as defined by BIP-329.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelType {
+    Tx,
+    Addr,
+    Pubkey,
+    Input,
+    Output,
+    Xpub,
+}
+
+impl LabelType {
+    /// The BIP-329 `type` string.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Tx => "tx",
+            Self::Addr => "addr",
+            Self::Pubkey => "pubkey",
+            Self::Input => "input",
+            Self::Output => "output",
+            Self::Xpub => "xpub",
+        }
+    }
+}
+
+/// A single BIP-329 label record.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Label {
+    #[serde(rename = "type")]
+    pub label_type: LabelType,
+    /// Txid, address, pubkey, `txid:vout` outpoint or xpub, depending on `label_type`.
+    #[serde(rename = "ref")]
+    pub reference: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Key origin of the descriptor the reference belongs to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>,
+    /// Only meaningful for outputs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spendable: Option<bool>,
+}
+
+impl Label {
+    /// Checks that the reference is well-formed for the label type.
+    pub fn validate(&self) -> Result<(), String> {
+        let reference = self.reference.as_str();
+        let valid = match self.label_type {
+            LabelType::Tx => Txid::from_str(reference).is_ok(),
+            LabelType::Addr => Address::from_str(reference).is_ok(),
+            LabelType::Input | LabelType::Output => OutPoint::from_str(reference).is_ok(),
+            LabelType::Pubkey | LabelType::Xpub => !reference.is_empty(),
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(format!(
+                "Invalid {} reference '{reference}'",
+                self.label_type.as_str()
+            ))
+        }
+    }
+}
+
+/// All labels of one maker, keyed by type and reference.
+#[derive(Debug, Clone, Default)]
+pub struct LabelStore {
+    labels: BTreeMap<(LabelType, String), Label>,
+}
+
+impl LabelStore {
+    /// Parses and validates BIP-329 JSON lines. Blank lines are skipped; any invalid
+    /// record rejects the whole input.
+    pub fn parse_jsonl(input: &str) -> Result<Vec<Label>, String> {
+        input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(n, line)| {
+                let label: Label = serde_json::from_str(line)
+                    .map_err(|e| format!("Line {}: invalid label record: {e}", n + 1))?;
+                label
+                    .validate()
+                    .map_err(|e| format!("Line {}: {e}", n + 1))?;
+                Ok(label)
+            })
+            .collect()
+    }
+
+    /// Builds a store from previously exported JSON lines.
+    pub fn from_jsonl(input: &str) -> Result<Self, String> {
+        let mut store = Self::default();
+        for label in Self::parse_jsonl(input)? {
+            store.set(label);
+        }
+        Ok(store)
+    }
+
+    /// Serializes every label as BIP-329 JSON lines.
+    pub fn to_jsonl(&self) -> String {
+        self.labels
+            .values()
+            .filter_map(|label| serde_json::to_string(label).ok())
+            .map(|line| line + "\n")
+            .collect()
+    }
+
+    /// Inserts or replaces the label for its type and reference.
+    pub fn set(&mut self, label: Label) {
+        self.labels
+            .insert((label.label_type, label.reference.clone()), label);
+    }
+
+    pub fn remove(&mut self, label_type: LabelType, reference: &str) -> Option<Label> {
+        self.labels.remove(&(label_type, reference.to_string()))
+    }
+
+    pub fn get(&self, label_type: LabelType, reference: &str) -> Option<&Label> {
+        self.labels.get(&(label_type, reference.to_string()))
+    }
+
+    /// Label text for a reference, if any.
+    pub fn label_of(&self, label_type: LabelType, reference: &str) -> Option<String> {
+        self.get(label_type, reference)
+            .and_then(|label| label.label.clone())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Label> {
+        self.labels.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LabelStore, LabelType};
+
+    const TXID: &str = "f91d0a8a78462bc59398f2c5d7a84fcff491c26ba54c4833478b202796c8aafd";
+
+    #[test]
+    fn jsonl_round_trip() {
+        let input = format!(
+            "{{\"type\":\"tx\",\"ref\":\"{TXID}\",\"label\":\"Deposit from exchange\"}}\n\n\
+             {{\"type\":\"output\",\"ref\":\"{TXID}:1\",\"label\":\"cold storage\",\"spendable\":false}}\n"
+        );
+        let store = LabelStore::from_jsonl(&input).unwrap();
+        assert_eq!(store.len(), 2);
+        assert_eq!(
+            store.label_of(LabelType::Tx, TXID).as_deref(),
+            Some("Deposit from exchange")
+        );
+        assert_eq!(
+            store
+                .get(LabelType::Output, &format!("{TXID}:1"))
+                .unwrap()
+                .spendable,
+            Some(false)
+        );
+
+        let reparsed = LabelStore::from_jsonl(&store.to_jsonl()).unwrap();
+        assert_eq!(reparsed.to_jsonl(), store.to_jsonl());
+    }
+
+    #[test]
+    fn parse_rejects_malformed_records() {
+        let err = LabelStore::parse_jsonl("{\"type\":\"tx\",\"ref\":\"nope\",\"label\":\"x\"}")
+            .unwrap_err();
+        assert!(err.starts_with("Line 1:"), "{err}");
+        assert!(LabelStore::parse_jsonl("{\"type\":\"bogus\",\"ref\":\"x\"}").is_err());
+        assert!(LabelStore::parse_jsonl("not json").is_err());
+    }
+}
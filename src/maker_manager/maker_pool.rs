@@ -6,18 +6,33 @@ use std::sync::{Arc, RwLock};
 use std::thread::{self, JoinHandle};
 
 use anyhow::{anyhow, Result};
-use coinswap::bitcoin::{Address, Amount};
+use coinswap::bitcoin::opcodes::all::OP_CSV;
+use coinswap::bitcoin::script::{Instruction, Script};
+use coinswap::bitcoin::{absolute::LockTime, Address, Amount, OutPoint, Transaction, Txid};
+use coinswap::bitcoind::bitcoincore_rpc::json::ListUnspentResultEntry;
 use coinswap::maker::{start_server, MakerServer};
-use coinswap::utill::UTXO;
-use coinswap::wallet::{AddressType, Destination, Wallet};
+use coinswap::wallet::{AddressType, Destination, UTXOSpendInfo, Wallet};
 use tokio::{runtime::Runtime, sync::Mutex};
 
-use super::message::{MessageRequest, MessageResponse};
+use super::message::{
+    BumpMethod, FidelityBondSummary, FidelityFunding, FidelityRedeemError, MessageRequest,
+    MessageResponse, OutgoingTx, UtxoPool, WalletTxHints, WalletUtxo,
+};
+use super::unix_now;
 use crate::utils::bidirectional_channel::{channel, Requester, Responder};
 
 /// Unique identifier for each maker in the pool
 pub type MakerId = String;
 
+/// Whether `id` can name a maker: ASCII letters, digits, `-` and `_`. Maker IDs name
+/// directories and wallets, so anything else could reach outside the dashboard's own.
+pub fn is_valid_maker_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
 /// Trait abstracting wallet access across maker types
 pub trait MakerWalletAccess: Send + Sync + 'static {
     fn wallet(&self) -> &RwLock<Wallet>;
@@ -55,47 +70,29 @@ fn handle_request(
     network_port: u16,
     request: MessageRequest,
 ) -> Result<MessageResponse> {
-    let addr_type = maker.default_address_type();
-
     Ok(match request {
         MessageRequest::Ping => MessageResponse::Pong,
         MessageRequest::Utxo => match maker.wallet().read() {
             Ok(wallet) => MessageResponse::UtxoResp {
-                utxos: wallet
-                    .list_all_utxo_spend_info()
-                    .into_iter()
-                    .map(UTXO::from_utxo_data)
-                    .collect(),
+                utxos: wallet_utxos(&wallet, wallet.list_all_utxo_spend_info()),
             },
             Err(e) => MessageResponse::ServerError(e.to_string()),
         },
         MessageRequest::SwapUtxo => match maker.wallet().read() {
             Ok(wallet) => MessageResponse::SwapUtxoResp {
-                utxos: wallet
-                    .list_incoming_swap_coin_utxo_spend_info()
-                    .into_iter()
-                    .map(UTXO::from_utxo_data)
-                    .collect(),
+                utxos: wallet_utxos(&wallet, wallet.list_incoming_swap_coin_utxo_spend_info()),
             },
             Err(e) => MessageResponse::ServerError(e.to_string()),
         },
         MessageRequest::ContractUtxo => match maker.wallet().read() {
             Ok(wallet) => MessageResponse::ContractUtxoResp {
-                utxos: wallet
-                    .list_live_timelock_contract_spend_info()
-                    .into_iter()
-                    .map(UTXO::from_utxo_data)
-                    .collect(),
+                utxos: wallet_utxos(&wallet, wallet.list_live_timelock_contract_spend_info()),
             },
             Err(e) => MessageResponse::ServerError(e.to_string()),
         },
         MessageRequest::FidelityUtxo => match maker.wallet().read() {
             Ok(wallet) => MessageResponse::FidelityUtxoResp {
-                utxos: wallet
-                    .list_fidelity_spend_info()
-                    .into_iter()
-                    .map(UTXO::from_utxo_data)
-                    .collect(),
+                utxos: wallet_utxos(&wallet, wallet.list_fidelity_spend_info()),
             },
             Err(e) => MessageResponse::ServerError(e.to_string()),
         },
@@ -106,8 +103,8 @@ fn handle_request(
             },
             Err(e) => MessageResponse::ServerError(e.to_string()),
         },
-        MessageRequest::NewAddress => match maker.wallet().write() {
-            Ok(mut wallet) => match wallet.get_next_external_address(addr_type) {
+        MessageRequest::NewAddress { address_type } => match maker.wallet().write() {
+            Ok(mut wallet) => match wallet.get_next_external_address(address_type.into()) {
                 Ok(addr) => MessageResponse::NewAddressResp(addr.to_string()),
                 Err(e) => MessageResponse::ServerError(format!("{e:?}")),
             },
@@ -117,82 +114,8 @@ fn handle_request(
             address,
             amount,
             feerate,
-        } => {
-            let amount = Amount::from_sat(amount);
-            let addr = match Address::from_str(&address) {
-                Ok(a) => a.assume_checked(),
-                Err(e) => {
-                    return Ok(MessageResponse::ServerError(format!(
-                        "Invalid address: {e}"
-                    )))
-                }
-            };
-            let destination = Destination::Multi {
-                outputs: vec![(addr, amount)],
-                op_return_data: None,
-                change_address_type: addr_type,
-            };
-            let coins_to_send = match maker.wallet().read() {
-                Ok(wallet) => match wallet.coin_select(amount, feerate, None, None) {
-                    Ok(coins) => coins,
-                    Err(e) => {
-                        return Ok(MessageResponse::ServerError(format!(
-                            "Coin selection failed: {e:?}"
-                        )))
-                    }
-                },
-                Err(e) => {
-                    return Ok(MessageResponse::ServerError(format!(
-                        "Wallet lock failed: {e}"
-                    )))
-                }
-            };
-            let tx = match maker.wallet().write() {
-                Ok(mut wallet) => {
-                    match wallet.spend_from_wallet(feerate, destination, &coins_to_send) {
-                        Ok(tx) => tx,
-                        Err(e) => {
-                            return Ok(MessageResponse::ServerError(format!(
-                                "Transaction building failed: {e:?}"
-                            )))
-                        }
-                    }
-                }
-                Err(e) => {
-                    return Ok(MessageResponse::ServerError(format!(
-                        "Wallet lock failed: {e}"
-                    )))
-                }
-            };
-            let txid = match maker.wallet().read() {
-                Ok(wallet) => match wallet.send_tx(&tx) {
-                    Ok(txid) => txid,
-                    Err(e) => {
-                        return Ok(MessageResponse::ServerError(format!(
-                            "Broadcast failed: {e:?}"
-                        )))
-                    }
-                },
-                Err(e) => {
-                    return Ok(MessageResponse::ServerError(format!(
-                        "Wallet lock failed: {e}"
-                    )))
-                }
-            };
-            match maker.wallet().write() {
-                Ok(mut wallet) => {
-                    if let Err(e) = wallet.sync_and_save() {
-                        return Ok(MessageResponse::ServerError(format!("Sync failed: {e:?}")));
-                    }
-                }
-                Err(e) => {
-                    return Ok(MessageResponse::ServerError(format!(
-                        "Wallet lock failed: {e}"
-                    )));
-                }
-            }
-            MessageResponse::SendToAddressResp(txid.to_string())
-        }
+            exclude,
+        } => send_to_address(maker, &address, amount, feerate, exclude),
         MessageRequest::GetTorAddress => match read_tor_address(maker.data_dir(), network_port) {
             Ok(address) => MessageResponse::GetTorAddressResp(address),
             Err(e) => MessageResponse::ServerError(e.to_string()),
@@ -201,10 +124,7 @@ fn handle_request(
             MessageResponse::GetDataDirResp(maker.data_dir().to_path_buf())
         }
         MessageRequest::ListFidelity => match maker.wallet().read() {
-            Ok(wallet) => match wallet.display_fidelity_bonds() {
-                Ok(bonds) => MessageResponse::ListBonds(bonds),
-                Err(e) => MessageResponse::ServerError(e.to_string()),
-            },
+            Ok(wallet) => MessageResponse::ListBonds(fidelity_bonds(&wallet)),
             Err(e) => MessageResponse::ServerError(e.to_string()),
         },
         MessageRequest::SyncWallet => match maker.wallet().write() {
@@ -216,17 +136,539 @@ fn handle_request(
         },
         MessageRequest::SweptSwapUtxo => match maker.wallet().read() {
             Ok(wallet) => MessageResponse::SweptSwapUtxoResp {
-                utxos: wallet
+                utxos: wallet_utxos(&wallet, wallet.list_swept_incoming_swap_utxos()),
+            },
+            Err(e) => MessageResponse::ServerError(e.to_string()),
+        },
+        MessageRequest::TxHints => match maker.wallet().read() {
+            Ok(wallet) => MessageResponse::TxHintsResp(WalletTxHints {
+                fidelity_outpoints: wallet
+                    .get_fidelity_bonds()
+                    .values()
+                    .map(|bond| bond.outpoint)
+                    .collect(),
+                swap_in_txids: wallet
+                    .list_incoming_swap_coin_utxo_spend_info()
+                    .into_iter()
+                    .map(|(entry, _)| entry.txid)
+                    .collect(),
+                sweep_txids: wallet
                     .list_swept_incoming_swap_utxos()
                     .into_iter()
-                    .map(UTXO::from_utxo_data)
+                    .map(|(entry, _)| entry.txid)
                     .collect(),
-            },
+            }),
             Err(e) => MessageResponse::ServerError(e.to_string()),
         },
+        MessageRequest::BumpFee {
+            tx,
+            feerate,
+            method: BumpMethod::Rbf,
+        } => bump_rbf(maker, tx, feerate),
+        MessageRequest::BumpFee {
+            tx,
+            feerate,
+            method: BumpMethod::Cpfp,
+        } => bump_cpfp(maker, tx, feerate),
+        MessageRequest::WalletBackup { passphrase } => wallet_backup(maker, passphrase),
+        MessageRequest::CreateFidelity {
+            amount,
+            locktime,
+            feerate,
+            exclude,
+            dry_run,
+        } => create_fidelity(
+            maker,
+            network_port,
+            amount,
+            locktime,
+            feerate,
+            exclude,
+            dry_run,
+        ),
+        MessageRequest::RedeemFidelity {
+            index,
+            feerate,
+            tip,
+        } => redeem_fidelity(maker, index, feerate, tip),
     })
 }
 
+/// Spends an expired fidelity bond back into the wallet.
+fn redeem_fidelity(
+    maker: &dyn MakerWalletAccess,
+    index: u32,
+    feerate: f64,
+    tip: u32,
+) -> MessageResponse {
+    let rejected = |reason| MessageResponse::FidelityRedeemRejected { index, reason };
+    let txid = match maker.wallet().write() {
+        Ok(mut wallet) => {
+            let Some(bond) = wallet.get_fidelity_bonds().get(&index) else {
+                return rejected(FidelityRedeemError::NotFound);
+            };
+            if bond.is_spent {
+                return rejected(FidelityRedeemError::Spent);
+            }
+            // A transaction with this locktime is valid in the block after it
+            let locktime = bond.lock_time.to_consensus_u32();
+            if tip < locktime {
+                return rejected(FidelityRedeemError::NotExpired {
+                    remaining_blocks: locktime - tip,
+                });
+            }
+            match wallet.redeem_fidelity(index, feerate) {
+                Ok(txid) => txid,
+                Err(e) => {
+                    return MessageResponse::ServerError(format!(
+                        "Fidelity bond redemption failed: {e:?}"
+                    ))
+                }
+            }
+        }
+        Err(e) => return MessageResponse::ServerError(format!("Wallet lock failed: {e}")),
+    };
+    if let Ok(mut wallet) = maker.wallet().write() {
+        if let Err(e) = wallet.sync_and_save() {
+            tracing::warn!("Sync after redeeming fidelity bond {index} failed: {e:?}");
+        }
+    }
+    MessageResponse::FidelitySpend(txid)
+}
+
+/// Lists the wallet's fidelity bonds by index, marking the one the maker advertises.
+fn fidelity_bonds(wallet: &Wallet) -> Vec<FidelityBondSummary> {
+    // The maker always advertises its highest-value bond
+    let advertised = wallet.get_highest_fidelity_index().ok().flatten();
+    let mut bonds: Vec<_> = wallet
+        .get_fidelity_bonds()
+        .iter()
+        .map(|(&index, bond)| FidelityBondSummary {
+            index,
+            outpoint: bond.outpoint,
+            amount: bond.amount,
+            locktime: bond.lock_time.to_consensus_u32(),
+            conf_height: bond.conf_height,
+            cert_expiry: bond.cert_expiry,
+            spent: bond.is_spent,
+            value: wallet.calculate_bond_value(bond).ok(),
+            advertised: advertised == Some(index),
+        })
+        .collect();
+    bonds.sort_by_key(|bond| bond.index);
+    bonds
+}
+
+/// Virtual size of a transaction's fixed parts: version, locktime, counts and segwit marker.
+const TX_OVERHEAD_VBYTES: f64 = 10.5;
+/// Virtual size of a P2WSH or P2TR output; covers both the bond and the change output.
+const OUTPUT_VBYTES: f64 = 43.0;
+const P2WPKH_INPUT_VBYTES: f64 = 68.0;
+const P2TR_INPUT_VBYTES: f64 = 57.5;
+
+/// Prices a fidelity bond funding transaction and, unless `dry_run` is set or funds fall
+/// short, broadcasts it.
+fn create_fidelity(
+    maker: &dyn MakerWalletAccess,
+    network_port: u16,
+    amount: u64,
+    locktime: u32,
+    feerate: f64,
+    exclude: Vec<OutPoint>,
+    dry_run: bool,
+) -> MessageResponse {
+    let mut funding = {
+        let wallet = match maker.wallet().read() {
+            Ok(wallet) => wallet,
+            Err(e) => return MessageResponse::ServerError(format!("Wallet lock failed: {e}")),
+        };
+        // Frozen coins locked in Core aren't listed at all, but a lock lost with a bitcoind
+        // restart leaves the coin listed until it is re-applied. `Wallet::create_fidelity`
+        // selects the bond's inputs itself and can't be told to skip any, so refuse rather
+        // than risk spending a frozen coin
+        let frozen: Vec<String> = wallet
+            .list_all_utxo_spend_info()
+            .into_iter()
+            .filter(|(_, info)| {
+                matches!(
+                    info,
+                    UTXOSpendInfo::SeedCoin { .. } | UTXOSpendInfo::SweptCoin { .. }
+                )
+            })
+            .map(|(entry, _)| OutPoint::new(entry.txid, entry.vout))
+            .filter(|outpoint| exclude.contains(outpoint))
+            .map(|outpoint| outpoint.to_string())
+            .collect();
+        if !frozen.is_empty() {
+            return MessageResponse::ServerError(format!(
+                "Cannot fund a fidelity bond while spendable coins are frozen ({}); unfreeze them first",
+                frozen.join(", ")
+            ));
+        }
+        let spendable = match wallet.get_balances() {
+            Ok(balances) => balances.spendable.to_sat(),
+            Err(e) => return MessageResponse::ServerError(format!("{e:?}")),
+        };
+        let inputs: f64 =
+            match wallet.coin_select(Amount::from_sat(amount), feerate, None, Some(exclude)) {
+                Ok(coins) => coins
+                    .iter()
+                    .map(|(entry, _)| {
+                        if entry.script_pub_key.is_p2tr() {
+                            P2TR_INPUT_VBYTES
+                        } else {
+                            P2WPKH_INPUT_VBYTES
+                        }
+                    })
+                    .sum(),
+                Err(e) => {
+                    return MessageResponse::ServerError(format!("Coin selection failed: {e:?}"))
+                }
+            };
+        let vsize = TX_OVERHEAD_VBYTES + inputs + 2.0 * OUTPUT_VBYTES;
+        let fee = (vsize * feerate).ceil() as u64;
+        FidelityFunding {
+            amount,
+            locktime,
+            feerate,
+            fee,
+            spendable,
+            sufficient: spendable >= amount + fee,
+            index: None,
+            txid: None,
+        }
+    };
+    if dry_run || !funding.sufficient {
+        return MessageResponse::FidelityCreateResp(funding);
+    }
+
+    let locktime = match LockTime::from_height(locktime) {
+        Ok(locktime) => locktime,
+        Err(e) => return MessageResponse::ServerError(format!("Invalid locktime: {e}")),
+    };
+    let maker_address = read_tor_address(maker.data_dir(), network_port).ok();
+    let index = match maker.wallet().write() {
+        Ok(mut wallet) => match wallet.create_fidelity(
+            Amount::from_sat(amount),
+            locktime,
+            maker_address.as_deref(),
+            feerate,
+        ) {
+            Ok(index) => index,
+            Err(e) => {
+                return MessageResponse::ServerError(format!(
+                    "Fidelity bond creation failed: {e:?}"
+                ))
+            }
+        },
+        Err(e) => return MessageResponse::ServerError(format!("Wallet lock failed: {e}")),
+    };
+    funding.index = Some(index);
+    if let Ok(mut wallet) = maker.wallet().write() {
+        if let Err(e) = wallet.sync_and_save() {
+            tracing::warn!("Sync after creating fidelity bond {index} failed: {e:?}");
+        }
+        funding.txid = wallet
+            .get_fidelity_bonds()
+            .get(&index)
+            .map(|bond| bond.outpoint.txid);
+    }
+    MessageResponse::FidelityCreateResp(funding)
+}
+
+/// Writes an encrypted backup next to the wallet and hands back its contents. The file
+/// is removed again so the backup only ever leaves through the API response.
+fn wallet_backup(maker: &dyn MakerWalletAccess, passphrase: String) -> MessageResponse {
+    let path = maker
+        .data_dir()
+        .join(format!("wallet-backup-{}.json", unix_now()));
+    let result = match maker.wallet().read() {
+        Ok(wallet) => wallet
+            .backup(&path, Some(passphrase))
+            .map_err(|e| format!("Failed to back up wallet: {e:?}")),
+        Err(e) => Err(e.to_string()),
+    }
+    .and_then(|()| fs::read_to_string(&path).map_err(|e| e.to_string()));
+    let _ = fs::remove_file(&path);
+    match result {
+        Ok(backup) => MessageResponse::WalletBackupResp(backup),
+        Err(e) => MessageResponse::ServerError(e),
+    }
+}
+
+/// Relative timelock of a coinswap contract script: the number pushed right before its
+/// `OP_CSV`.
+fn contract_timelock(script: &Script) -> Option<u16> {
+    let instructions: Vec<Instruction> = script.instructions().collect::<Result<_, _>>().ok()?;
+    instructions.windows(2).find_map(|pair| match pair {
+        [number, Instruction::Op(op)] if *op == OP_CSV => {
+            number.script_num().and_then(|n| u16::try_from(n).ok())
+        }
+        _ => None,
+    })
+}
+
+fn wallet_utxos(
+    wallet: &Wallet,
+    coins: Vec<(ListUnspentResultEntry, UTXOSpendInfo)>,
+) -> Vec<WalletUtxo> {
+    coins
+        .into_iter()
+        .map(|(entry, info)| {
+            // Each side of a swap has its own contract, and so its own timelock
+            let contract_timelock = match &info {
+                UTXOSpendInfo::TimelockContract {
+                    swapcoin_multisig_redeemscript,
+                    ..
+                } => wallet
+                    .find_outgoing_swapcoin(swapcoin_multisig_redeemscript)
+                    .and_then(|coin| contract_timelock(&coin.contract_redeemscript)),
+                UTXOSpendInfo::HashlockContract {
+                    swapcoin_multisig_redeemscript,
+                    ..
+                } => wallet
+                    .find_incoming_swapcoin(swapcoin_multisig_redeemscript)
+                    .and_then(|coin| contract_timelock(&coin.contract_redeemscript)),
+                _ => None,
+            };
+            let (pool, fidelity_index) = match info {
+                UTXOSpendInfo::SeedCoin { .. } => (UtxoPool::Regular, None),
+                UTXOSpendInfo::SweptCoin { .. } => (UtxoPool::Swept, None),
+                UTXOSpendInfo::IncomingSwapCoin { .. } | UTXOSpendInfo::OutgoingSwapCoin { .. } => {
+                    (UtxoPool::Swap, None)
+                }
+                UTXOSpendInfo::TimelockContract { .. } | UTXOSpendInfo::HashlockContract { .. } => {
+                    (UtxoPool::Contract, None)
+                }
+                UTXOSpendInfo::FidelityBondCoin { index, .. } => (UtxoPool::Fidelity, Some(index)),
+            };
+            WalletUtxo {
+                outpoint: OutPoint::new(entry.txid, entry.vout),
+                address: entry.address.map(|addr| addr.assume_checked().to_string()),
+                script_pubkey: entry.script_pub_key,
+                amount: entry.amount,
+                confirmations: entry.confirmations,
+                descriptor: entry.descriptor,
+                pool,
+                fidelity_index,
+                contract_timelock,
+            }
+        })
+        .collect()
+}
+
+/// Records a freshly broadcast transaction so it can be fee-bumped later.
+fn outgoing_tx(
+    tx: &Transaction,
+    txid: Txid,
+    address: &Address,
+    amount: u64,
+    feerate: f64,
+    inputs: Vec<(ListUnspentResultEntry, UTXOSpendInfo)>,
+) -> OutgoingTx {
+    let input_total: u64 = inputs.iter().map(|(entry, _)| entry.amount.to_sat()).sum();
+    let output_total: u64 = tx.output.iter().map(|out| out.value.to_sat()).sum();
+    let recipient = address.script_pubkey();
+    let change = tx
+        .output
+        .iter()
+        .position(|out| out.script_pubkey != recipient)
+        .map(|vout| OutPoint::new(txid, vout as u32));
+
+    OutgoingTx {
+        txid,
+        address: address.to_string(),
+        amount,
+        feerate,
+        fee: input_total.saturating_sub(output_total),
+        vsize: tx.vsize() as u64,
+        inputs,
+        change,
+        created_at: unix_now(),
+        replaces: None,
+        replaced_by: None,
+        cpfp_parent: None,
+    }
+}
+
+/// Broadcasts a signed transaction and syncs the wallet so spent coins drop out of it.
+fn broadcast(maker: &dyn MakerWalletAccess, tx: &Transaction) -> Result<Txid, String> {
+    let txid = match maker.wallet().read() {
+        Ok(wallet) => wallet
+            .send_tx(tx)
+            .map_err(|e| format!("Broadcast failed: {e:?}"))?,
+        Err(e) => return Err(format!("Wallet lock failed: {e}")),
+    };
+    match maker.wallet().write() {
+        Ok(mut wallet) => wallet
+            .sync_and_save()
+            .map_err(|e| format!("Sync failed: {e:?}"))?,
+        Err(e) => return Err(format!("Wallet lock failed: {e}")),
+    }
+    Ok(txid)
+}
+
+/// Builds a transaction paying `amount` to `address` from the given coins.
+fn build_payment(
+    maker: &dyn MakerWalletAccess,
+    address: &Address,
+    amount: Amount,
+    feerate: f64,
+    coins: &[(ListUnspentResultEntry, UTXOSpendInfo)],
+) -> Result<Transaction, String> {
+    let destination = Destination::Multi {
+        outputs: vec![(address.clone(), amount)],
+        op_return_data: None,
+        change_address_type: maker.default_address_type(),
+    };
+    match maker.wallet().write() {
+        Ok(mut wallet) => wallet
+            .spend_from_wallet(feerate, destination, coins)
+            .map_err(|e| format!("Transaction building failed: {e:?}")),
+        Err(e) => Err(format!("Wallet lock failed: {e}")),
+    }
+}
+
+fn send_to_address(
+    maker: &dyn MakerWalletAccess,
+    address: &str,
+    amount: u64,
+    feerate: f64,
+    exclude: Vec<OutPoint>,
+) -> MessageResponse {
+    let addr = match Address::from_str(address) {
+        Ok(a) => a.assume_checked(),
+        Err(e) => return MessageResponse::ServerError(format!("Invalid address: {e}")),
+    };
+    let coins_to_send = match maker.wallet().read() {
+        Ok(wallet) => {
+            match wallet.coin_select(Amount::from_sat(amount), feerate, None, Some(exclude)) {
+                Ok(coins) => coins,
+                Err(e) => {
+                    return MessageResponse::ServerError(format!("Coin selection failed: {e:?}"))
+                }
+            }
+        }
+        Err(e) => return MessageResponse::ServerError(format!("Wallet lock failed: {e}")),
+    };
+    let tx = match build_payment(
+        maker,
+        &addr,
+        Amount::from_sat(amount),
+        feerate,
+        &coins_to_send,
+    ) {
+        Ok(tx) => tx,
+        Err(e) => return MessageResponse::ServerError(e),
+    };
+    match broadcast(maker, &tx) {
+        Ok(txid) => MessageResponse::SendToAddressResp(outgoing_tx(
+            &tx,
+            txid,
+            &addr,
+            amount,
+            feerate,
+            coins_to_send,
+        )),
+        Err(e) => MessageResponse::ServerError(e),
+    }
+}
+
+/// Replaces `original` with a transaction spending the same inputs to the same recipient
+/// at a higher feerate.
+fn bump_rbf(maker: &dyn MakerWalletAccess, original: OutgoingTx, feerate: f64) -> MessageResponse {
+    let addr = match Address::from_str(&original.address) {
+        Ok(a) => a.assume_checked(),
+        Err(e) => return MessageResponse::ServerError(format!("Invalid address: {e}")),
+    };
+    let tx = match build_payment(
+        maker,
+        &addr,
+        Amount::from_sat(original.amount),
+        feerate,
+        &original.inputs,
+    ) {
+        Ok(tx) => tx,
+        Err(e) => return MessageResponse::ServerError(e),
+    };
+    match broadcast(maker, &tx) {
+        Ok(txid) => {
+            let mut replacement =
+                outgoing_tx(&tx, txid, &addr, original.amount, feerate, original.inputs);
+            replacement.replaces = Some(original.txid);
+            MessageResponse::BumpFeeResp(replacement)
+        }
+        Err(e) => MessageResponse::ServerError(e),
+    }
+}
+
+/// Sweeps the change output of `parent` to a fresh change address with a fee high enough
+/// that parent and child together pay `feerate`.
+fn bump_cpfp(maker: &dyn MakerWalletAccess, parent: OutgoingTx, feerate: f64) -> MessageResponse {
+    let Some(change) = parent.change else {
+        return MessageResponse::ServerError(format!(
+            "Transaction {} has no change output to spend",
+            parent.txid
+        ));
+    };
+    let (coin, sweep_addr) = match maker.wallet().write() {
+        Ok(mut wallet) => {
+            let coin = wallet
+                .list_all_utxo_spend_info()
+                .into_iter()
+                .find(|(entry, _)| entry.txid == change.txid && entry.vout == change.vout);
+            let Some(coin) = coin else {
+                return MessageResponse::ServerError(format!(
+                    "Change output {change} is no longer unspent"
+                ));
+            };
+            // Change, not a receive address, so the child doesn't consume the external chain
+            match wallet.get_next_internal_addresses(1, maker.default_address_type()) {
+                Ok(mut addrs) if !addrs.is_empty() => (coin, addrs.remove(0)),
+                Ok(_) => {
+                    return MessageResponse::ServerError(
+                        "Wallet returned no change address".to_string(),
+                    )
+                }
+                Err(e) => return MessageResponse::ServerError(format!("{e:?}")),
+            }
+        }
+        Err(e) => return MessageResponse::ServerError(format!("Wallet lock failed: {e}")),
+    };
+
+    let build = |feerate: f64| match maker.wallet().write() {
+        Ok(mut wallet) => wallet
+            .spend_from_wallet(
+                feerate,
+                Destination::Sweep(sweep_addr.clone()),
+                std::slice::from_ref(&coin),
+            )
+            .map_err(|e| format!("Transaction building failed: {e:?}")),
+        Err(e) => Err(format!("Wallet lock failed: {e}")),
+    };
+
+    // Build once to learn the child's size, then again with the feerate that lifts the package.
+    let child_vsize = match build(feerate) {
+        Ok(trial) => trial.vsize() as f64,
+        Err(e) => return MessageResponse::ServerError(e),
+    };
+    let package_fee = feerate * (parent.vsize as f64 + child_vsize);
+    let child_feerate = ((package_fee - parent.fee as f64) / child_vsize).max(feerate);
+    let tx = match build(child_feerate) {
+        Ok(tx) => tx,
+        Err(e) => return MessageResponse::ServerError(e),
+    };
+    let amount = tx.output.iter().map(|out| out.value.to_sat()).sum();
+    match broadcast(maker, &tx) {
+        Ok(txid) => {
+            let mut child = outgoing_tx(&tx, txid, &sweep_addr, amount, child_feerate, vec![coin]);
+            child.cpfp_parent = Some(parent.txid);
+            MessageResponse::BumpFeeResp(child)
+        }
+        Err(e) => MessageResponse::ServerError(e),
+    }
+}
+
 /// Entry representing a single maker running in its own thread
 struct MakerEntry {
     inner: Arc<MakerServer>,
@@ -273,6 +715,8 @@ struct MakerPoolEntry {
     message_thread: Option<JoinHandle<()>>,
     /// Thread running start_server (only when "started")
     server_thread: Option<JoinHandle<()>>,
+    /// The server exited on its own and hasn't been started or stopped since
+    crashed: bool,
 }
 
 /// Pool managing multiple makers, each running in its own thread
@@ -314,6 +758,7 @@ impl MakerPool {
                 requester: Mutex::new(requester),
                 message_thread: Some(message_thread),
                 server_thread: None,
+                crashed: false,
             },
         );
 
@@ -348,6 +793,7 @@ impl MakerPool {
                 })?;
 
         entry.server_thread = Some(server_handle);
+        entry.crashed = false;
         Ok(())
     }
 
@@ -370,6 +816,7 @@ impl MakerPool {
             .shutdown
             .store(true, std::sync::atomic::Ordering::Relaxed);
 
+        entry.crashed = false;
         server_thread
             .join()
             .map_err(|_| anyhow!("Failed to join server thread for maker '{id}'"))?;
@@ -385,6 +832,38 @@ impl MakerPool {
             .unwrap_or(false)
     }
 
+    /// Returns true if the maker's coinswap server was started and not stopped since, even
+    /// if it has crashed
+    pub fn should_be_running(&self, id: &MakerId) -> bool {
+        self.makers
+            .get(id)
+            .is_some_and(|e| e.server_thread.is_some() || e.crashed)
+    }
+
+    /// Detaches the server threads that exited without being asked to stop and returns
+    /// their makers, which are then reported as stopped.
+    pub fn reap_crashed(&mut self) -> Vec<MakerId> {
+        let mut crashed = Vec::new();
+        for (id, entry) in &mut self.makers {
+            let exited = entry
+                .server_thread
+                .as_ref()
+                .is_some_and(|handle| handle.is_finished());
+            let stopping = entry
+                .maker_handle
+                .shutdown
+                .load(std::sync::atomic::Ordering::Relaxed);
+            if exited && !stopping {
+                if let Some(handle) = entry.server_thread.take() {
+                    let _ = handle.join();
+                }
+                entry.crashed = true;
+                crashed.push(id.clone());
+            }
+        }
+        crashed
+    }
+
     /// Sends a request to a specific maker and returns the response
     pub async fn request(&self, id: &MakerId, req: MessageRequest) -> Result<MessageResponse> {
         let handle = self
@@ -450,7 +929,31 @@ impl Default for MakerPool {
 
 #[cfg(test)]
 mod tests {
-    use super::read_tor_address;
+    use coinswap::bitcoin::opcodes::all::{OP_CHECKSIG, OP_CSV, OP_DROP, OP_ELSE, OP_ENDIF};
+    use coinswap::bitcoin::script::Builder;
+
+    use super::{contract_timelock, read_tor_address};
+
+    #[test]
+    fn reads_the_relative_timelock_of_a_contract() {
+        let contract = |timelock| {
+            Builder::new()
+                .push_opcode(OP_ELSE)
+                .push_int(timelock)
+                .push_opcode(OP_CSV)
+                .push_opcode(OP_DROP)
+                .push_opcode(OP_ENDIF)
+                .push_opcode(OP_CHECKSIG)
+                .into_script()
+        };
+        assert_eq!(contract_timelock(&contract(12)), Some(12));
+        assert_eq!(contract_timelock(&contract(300)), Some(300));
+        let no_csv = Builder::new()
+            .push_int(12)
+            .push_opcode(OP_DROP)
+            .into_script();
+        assert_eq!(contract_timelock(&no_csv), None);
+    }
 
     #[test]
     fn reads_tor_hostname_from_maker_data_dir() {
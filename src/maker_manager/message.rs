@@ -1,6 +1,10 @@
 use std::path::PathBuf;
 
-use coinswap::{bitcoin::Txid, utill::UTXO, wallet::Balances};
+use coinswap::{
+    bitcoin::{Amount, OutPoint, ScriptBuf, Txid},
+    bitcoind::bitcoincore_rpc::json::ListUnspentResultEntry,
+    wallet::{AddressType, Balances, UTXOSpendInfo},
+};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, to_string_pretty};
 
@@ -19,7 +23,10 @@ pub enum MessageRequest {
     /// Request to retrieve the total wallet balances of different categories.
     Balances,
     /// Request for generating a new wallet address.
-    NewAddress,
+    NewAddress {
+        /// Script type of the address to derive.
+        address_type: WalletAddressType,
+    },
     /// Request to send funds to a specific address.
     SendToAddress {
         /// The recipient's address.
@@ -28,6 +35,8 @@ pub enum MessageRequest {
         amount: u64,
         /// The transaction fee to include.
         feerate: f64,
+        /// Frozen coins coin selection must not spend.
+        exclude: Vec<OutPoint>,
     },
     /// Request to retrieve the Tor address of the Maker.
     GetTorAddress,
@@ -39,6 +48,203 @@ pub enum MessageRequest {
     SyncWallet,
     /// Request to fetch UTXOs for completed (swept) incoming swap coins.
     SweptSwapUtxo,
+    /// Request to collect wallet data used to classify transaction history.
+    TxHints,
+    /// Request to fee-bump a transaction previously broadcast by the dashboard.
+    BumpFee {
+        /// The tracked transaction to bump.
+        tx: OutgoingTx,
+        /// Target feerate in sat/vB (for CPFP, the effective feerate of the package).
+        feerate: f64,
+        /// Whether to replace the transaction or spend its change output.
+        method: BumpMethod,
+    },
+    /// Request to export the wallet in coinswap's backup format.
+    WalletBackup {
+        /// Passphrase the backup is encrypted with.
+        passphrase: String,
+    },
+    /// Request to lock funds in a new fidelity bond, or only price it when `dry_run` is set.
+    CreateFidelity {
+        /// Amount locked in the bond, in sats.
+        amount: u64,
+        /// Absolute block height the bond unlocks at.
+        locktime: u32,
+        /// Feerate of the funding transaction in sat/vB.
+        feerate: f64,
+        /// Frozen coins coin selection must not spend.
+        exclude: Vec<OutPoint>,
+        /// Only compute the funding requirements.
+        dry_run: bool,
+    },
+    /// Request to spend an expired fidelity bond back into the regular wallet.
+    RedeemFidelity {
+        /// Index of the bond in the wallet.
+        index: u32,
+        /// Feerate of the redemption transaction in sat/vB.
+        feerate: f64,
+        /// Current chain tip, used to check the bond has expired.
+        tip: u32,
+    },
+}
+
+/// Which part of the wallet a UTXO belongs to, derived from coinswap's [`UTXOSpendInfo`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UtxoPool {
+    /// Single-sig coins derived from the wallet seed.
+    Regular,
+    /// Single-sig coins swept from completed incoming swaps.
+    Swept,
+    /// Incoming or outgoing 2-of-2 swap multisig coins.
+    Swap,
+    /// Live hashlock or timelock contract outputs.
+    Contract,
+    /// Fidelity bond collateral.
+    Fidelity,
+}
+
+/// A wallet UTXO as reported by `listunspent`, plus the wallet's own classification.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WalletUtxo {
+    pub outpoint: OutPoint,
+    /// Address of the output, if Core could derive one from the script.
+    pub address: Option<String>,
+    pub script_pubkey: ScriptBuf,
+    pub amount: Amount,
+    pub confirmations: u32,
+    /// Output descriptor reported by Core, including the key origin when known.
+    pub descriptor: Option<String>,
+    pub pool: UtxoPool,
+    /// Index of the fidelity bond locked in this UTXO, for fidelity coins.
+    pub fidelity_index: Option<u32>,
+    /// Relative timelock of the contract, in blocks, for contract coins whose script is known.
+    pub contract_timelock: Option<u16>,
+}
+
+/// Receive address script type, mirroring coinswap's [`AddressType`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WalletAddressType {
+    /// Native segwit v0 (`bc1q...`).
+    P2wpkh,
+    /// Taproot (`bc1p...`), the coinswap default.
+    #[default]
+    P2tr,
+}
+
+impl From<WalletAddressType> for AddressType {
+    fn from(t: WalletAddressType) -> Self {
+        match t {
+            WalletAddressType::P2wpkh => Self::P2WPKH,
+            WalletAddressType::P2tr => Self::P2TR,
+        }
+    }
+}
+
+/// How a stuck transaction is fee-bumped.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BumpMethod {
+    /// Replace-by-fee: re-spend the same inputs at a higher feerate.
+    Rbf,
+    /// Child-pays-for-parent: spend our change output with a high enough fee for the package.
+    Cpfp,
+}
+
+/// A transaction the dashboard broadcast from a maker's wallet.
+///
+/// The spent coins are kept alongside the transaction so a replacement can be built after
+/// they have disappeared from the wallet's unspent set.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OutgoingTx {
+    pub txid: Txid,
+    /// Recipient address.
+    pub address: String,
+    /// Amount paid to the recipient, in sats.
+    pub amount: u64,
+    /// Feerate the transaction was built with, in sat/vB.
+    pub feerate: f64,
+    /// Absolute fee paid, in sats.
+    pub fee: u64,
+    pub vsize: u64,
+    /// Coins spent by the transaction.
+    pub inputs: Vec<(ListUnspentResultEntry, UTXOSpendInfo)>,
+    /// Our change output, if the transaction has one.
+    pub change: Option<OutPoint>,
+    /// Unix timestamp of the broadcast.
+    pub created_at: u64,
+    /// The transaction this one replaced via RBF.
+    #[serde(default)]
+    pub replaces: Option<Txid>,
+    /// The transaction that replaced this one via RBF.
+    #[serde(default)]
+    pub replaced_by: Option<Txid>,
+    /// The parent this transaction bumps via CPFP.
+    #[serde(default)]
+    pub cpfp_parent: Option<Txid>,
+}
+
+/// Funding requirements of a new fidelity bond, and the bond itself once created.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FidelityFunding {
+    /// Amount locked in the bond, in sats.
+    pub amount: u64,
+    /// Absolute block height the bond unlocks at.
+    pub locktime: u32,
+    pub feerate: f64,
+    /// Estimated fee of the funding transaction, in sats.
+    pub fee: u64,
+    /// Spendable wallet balance before funding, in sats.
+    pub spendable: u64,
+    /// Whether the spendable balance covers amount and fee.
+    pub sufficient: bool,
+    /// Index of the created bond; `None` for a dry run or when funds are insufficient.
+    pub index: Option<u32>,
+    /// Funding transaction of the created bond.
+    pub txid: Option<Txid>,
+}
+
+/// A fidelity bond the wallet has created, including redeemed ones.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FidelityBondSummary {
+    pub index: u32,
+    pub outpoint: OutPoint,
+    pub amount: Amount,
+    /// Absolute block height the bond unlocks at.
+    pub locktime: u32,
+    /// Height of the block that confirmed the bond.
+    pub conf_height: Option<u32>,
+    /// Block height the bond's certificate expires at.
+    pub cert_expiry: Option<u32>,
+    pub spent: bool,
+    /// Bond value as computed by the wallet; `None` while it cannot be computed.
+    pub value: Option<Amount>,
+    /// Whether this is the bond the maker advertises to takers.
+    pub advertised: bool,
+}
+
+/// Why a fidelity bond cannot be redeemed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum FidelityRedeemError {
+    /// The wallet has no bond with this index.
+    NotFound,
+    /// The bond was already redeemed.
+    Spent,
+    /// The bond's timelock has not expired yet.
+    NotExpired {
+        /// Blocks until the bond can be redeemed.
+        remaining_blocks: u32,
+    },
+}
+
+/// Wallet-side knowledge needed to tell swap, fidelity and sweep transactions apart
+/// from plain deposits and withdrawals.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct WalletTxHints {
+    /// Outpoints of every fidelity bond the wallet has created, including redeemed ones.
+    pub fidelity_outpoints: Vec<OutPoint>,
+    /// Transactions that created incoming swap coins.
+    pub swap_in_txids: Vec<Txid>,
+    /// Transactions that swept completed incoming swap coins into the wallet.
+    pub sweep_txids: Vec<Txid>,
 }
 
 /// Enum representing RPC message responses.
@@ -52,29 +258,29 @@ pub enum MessageResponse {
     /// Response containing all spendable UTXOs
     UtxoResp {
         /// List of spendable UTXOs in the wallet.
-        utxos: Vec<UTXO>,
+        utxos: Vec<WalletUtxo>,
     },
     /// Response containing UTXOs in the swap pool.
     SwapUtxoResp {
         /// List of UTXOs in the swap pool.
-        utxos: Vec<UTXO>,
+        utxos: Vec<WalletUtxo>,
     },
     /// Response containing UTXOs in the fidelity pool.
     FidelityUtxoResp {
         /// List of UTXOs in the fidelity pool.
-        utxos: Vec<UTXO>,
+        utxos: Vec<WalletUtxo>,
     },
     /// Response containing UTXOs in the contract pool.
     ContractUtxoResp {
         /// List of UTXOs in the contract pool.
-        utxos: Vec<UTXO>,
+        utxos: Vec<WalletUtxo>,
     },
     /// Response containing the total wallet balances of different categories.
     TotalBalanceResp(Balances),
     /// Response containing a newly generated wallet address.
     NewAddressResp(String),
-    /// Response to a send-to-address request.
-    SendToAddressResp(String),
+    /// Response to a send-to-address request, carrying the broadcast transaction.
+    SendToAddressResp(OutgoingTx),
     /// Response containing the Tor address of the Maker.
     GetTorAddressResp(String),
     /// Response containing the path to the data directory.
@@ -86,11 +292,26 @@ pub enum MessageResponse {
     /// Response with the internal server error.
     ServerError(String),
     /// Response listing all current and past fidelity bonds.
-    ListBonds(String),
+    ListBonds(Vec<FidelityBondSummary>),
     /// Response containing UTXOs for completed (swept) incoming swap coins.
     SweptSwapUtxoResp {
         /// List of UTXOs that were swept from completed incoming swaps.
-        utxos: Vec<UTXO>,
+        utxos: Vec<WalletUtxo>,
+    },
+    /// Response to a fee-bump request, carrying the replacement or child transaction.
+    BumpFeeResp(OutgoingTx),
+    /// Response containing transaction classification hints.
+    TxHintsResp(WalletTxHints),
+    /// Response containing the encrypted wallet backup file.
+    WalletBackupResp(String),
+    /// Response to a fidelity bond creation request.
+    FidelityCreateResp(FidelityFunding),
+    /// Response refusing to redeem a fidelity bond.
+    FidelityRedeemRejected {
+        /// Index of the bond.
+        index: u32,
+        /// Why the bond cannot be redeemed.
+        reason: FidelityRedeemError,
     },
 }
 
@@ -118,13 +339,40 @@ impl std::fmt::Display for MessageResponse {
                 let json = serde_json::to_string_pretty(utxos).map_err(|_| std::fmt::Error)?;
                 write!(f, "{json}")
             }
-            Self::SendToAddressResp(tx_hex) => write!(f, "{tx_hex}"),
+            Self::SendToAddressResp(tx) | Self::BumpFeeResp(tx) => write!(f, "{}", tx.txid),
+            Self::TxHintsResp(hints) => {
+                let json = serde_json::to_string_pretty(hints).map_err(|_| std::fmt::Error)?;
+                write!(f, "{json}")
+            }
+            Self::FidelityCreateResp(funding) => match (funding.index, funding.txid) {
+                (Some(index), Some(txid)) => write!(f, "Fidelity bond {index} created in {txid}"),
+                (Some(index), None) => write!(f, "Fidelity bond {index} created"),
+                _ => write!(
+                    f,
+                    "Fidelity bond of {} sats needs ~{} sats fee",
+                    funding.amount, funding.fee
+                ),
+            },
+            Self::FidelityRedeemRejected { index, reason } => match reason {
+                FidelityRedeemError::NotFound => write!(f, "Fidelity bond {index} not found"),
+                FidelityRedeemError::Spent => {
+                    write!(f, "Fidelity bond {index} was already redeemed")
+                }
+                FidelityRedeemError::NotExpired { remaining_blocks } => write!(
+                    f,
+                    "Fidelity bond {index} has not expired yet: {remaining_blocks} blocks remaining"
+                ),
+            },
+            Self::WalletBackupResp(backup) => write!(f, "Wallet backup ({} bytes)", backup.len()),
             Self::GetTorAddressResp(addr) => write!(f, "{addr}"),
             Self::GetDataDirResp(path) => write!(f, "{}", path.display()),
             Self::Shutdown => write!(f, "Shutdown Initiated"),
             Self::FidelitySpend(txid) => write!(f, "{txid}"),
             Self::ServerError(e) => write!(f, "{e}"),
-            Self::ListBonds(v) => write!(f, "{v}"),
+            Self::ListBonds(bonds) => {
+                let json = serde_json::to_string_pretty(bonds).map_err(|_| std::fmt::Error)?;
+                write!(f, "{json}")
+            }
         }
     }
 }
@@ -1,21 +1,35 @@
+pub mod alerts;
+pub mod capital;
+pub mod events;
+pub mod history;
+pub mod labels;
 pub mod maker_pool;
 pub mod message;
 pub mod persistence;
+pub mod renewal;
+pub mod webhooks;
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::net::TcpListener;
 use std::path::PathBuf;
 use std::sync::Arc;
 
 use crate::utils::log_writer::MakerLogWriter;
+use alerts::{Alert, AlertBook, AlertRule, Check, RuleSet};
 use anyhow::{anyhow, Result};
-use coinswap::bitcoin::Network;
-use coinswap::bitcoind::bitcoincore_rpc::Auth;
+use coinswap::bitcoin::{Network, OutPoint, Txid};
+use coinswap::bitcoind::bitcoincore_rpc::{Auth, Client};
 use coinswap::maker::{MakerServer, MakerServerConfig};
-use coinswap::wallet::RPCConfig;
-use maker_pool::{MakerId, MakerPool};
-use message::{MessageRequest, MessageResponse};
+use coinswap::wallet::{RPCConfig, Wallet};
+use events::{EventBus, EventKind};
+use history::{HistoryPoint, HistoryStore, NodeSample, Snapshot};
+use labels::{Label, LabelStore, LabelType};
+use maker_pool::{is_valid_maker_id, MakerId, MakerPool};
+use message::{BumpMethod, MessageRequest, MessageResponse, OutgoingTx, WalletAddressType};
 use persistence::PersistenceManager;
+use renewal::{RenewalPolicy, RenewalRecord, RENEWAL_LOG_LIMIT};
+use serde::{Deserialize, Serialize};
+use webhooks::{Delivery, Webhook, WebhookStore};
 
 /// Configuration for creating a new maker.
 #[derive(Debug, Clone)]
@@ -34,6 +48,9 @@ pub struct MakerConfig {
     pub wallet_name: Option<String>,
     /// Optional password for wallet encryption
     pub password: Option<String>,
+    /// Whether the wallet password is written to `makers.json`. When false the maker
+    /// comes up `Locked` after a dashboard restart until it is unlocked through the API.
+    pub persist_password: bool,
     pub network_port: u16,
     pub rpc_port: u16,
     pub socks_port: u16,
@@ -58,6 +75,7 @@ impl Default for MakerConfig {
             tor_auth: None,
             wallet_name: None,
             password: None,
+            persist_password: true,
             network_port: 6102,
             rpc_port: 6103,
             socks_port: 9050,
@@ -74,11 +92,34 @@ impl Default for MakerConfig {
     }
 }
 
+impl MakerConfig {
+    /// Builds a Bitcoin Core RPC client for the node this maker is configured against.
+    pub fn rpc_client(&self) -> Result<Client> {
+        self.client_for(format!("http://{}", self.rpc))
+    }
+
+    /// Builds an RPC client scoped to the Bitcoin Core wallet backing maker `id`.
+    pub fn wallet_rpc_client(&self, id: &str) -> Result<Client> {
+        let wallet_name = self.wallet_name.as_deref().unwrap_or(id);
+        self.client_for(format!("http://{}/wallet/{}", self.rpc, wallet_name))
+    }
+
+    fn client_for(&self, url: String) -> Result<Client> {
+        let auth = match &self.auth {
+            Some((user, pass)) => Auth::UserPass(user.clone(), pass.clone()),
+            None => Auth::None,
+        };
+        Client::new(&url, auth).map_err(|e| anyhow!("Failed to create RPC client for {url}: {e}"))
+    }
+}
+
 /// Operational state of a maker
 #[derive(Debug, Clone, PartialEq)]
 pub enum MakerState {
     Running,
     Stopped,
+    /// The wallet password is not persisted and the maker has not been unlocked yet.
+    Locked,
 }
 
 /// Full information about a registered maker
@@ -89,45 +130,153 @@ pub struct MakerInfo {
     pub config: MakerConfig,
 }
 
+/// A receive address handed out through the dashboard
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssuedAddress {
+    pub address: String,
+    pub address_type: WalletAddressType,
+    /// Unix timestamp the address was issued
+    pub issued_at: u64,
+}
+
+/// What a dashboard-triggered wallet repair does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RescanKind {
+    /// `rescanblockchain` over the wallet.
+    Rescan,
+    /// Re-import of the wallet's descriptors with a timestamp at the start height.
+    Reimport,
+}
+
+/// A rescan or descriptor re-import started through the dashboard
+#[derive(Debug, Clone)]
+pub struct RescanJob {
+    pub kind: RescanKind,
+    pub from_height: u64,
+    /// Unix timestamp the job started
+    pub started_at: u64,
+    /// Unix timestamp the job finished, `None` while it runs
+    pub finished_at: Option<u64>,
+    pub error: Option<String>,
+}
+
 /// High-level manager for creating and interacting with makers
 pub struct MakerManager {
     pool: MakerPool,
     /// Persisted configs keyed by maker ID
     configs: HashMap<MakerId, MakerConfig>,
+    /// Saved configs whose ID is invalid; never loaded, but kept in `makers.json`
+    ignored_configs: HashMap<MakerId, MakerConfig>,
     /// Handles saving/loading maker state to disk
     persistence: PersistenceManager,
     /// Running bitcoind child process spawned by the dashboard, if any
     bitcoind_process: Option<std::process::Child>,
     /// Network bitcoind was started on (e.g. "regtest", "signet")
     bitcoind_network: Option<String>,
+    /// Transactions broadcast through the dashboard, keyed by maker ID
+    outgoing_txs: HashMap<MakerId, Vec<OutgoingTx>>,
+    /// BIP-329 labels, keyed by maker ID
+    labels: HashMap<MakerId, LabelStore>,
+    /// Receive addresses issued through the dashboard, keyed by maker ID
+    issued_addresses: HashMap<MakerId, Vec<IssuedAddress>>,
+    /// UTXOs excluded from coin selection, keyed by maker ID
+    frozen_utxos: HashMap<MakerId, BTreeSet<OutPoint>>,
+    /// Latest rescan or re-import per maker; not persisted
+    rescans: HashMap<MakerId, RescanJob>,
+    /// Fidelity bond renewal policies, keyed by maker ID
+    renewal_policies: HashMap<MakerId, RenewalPolicy>,
+    /// Audit log of automatic renewal actions, oldest first, keyed by maker ID
+    renewal_log: HashMap<MakerId, Vec<RenewalRecord>>,
+    /// Makers whose renewal is being planned or executed; not persisted
+    renewals: HashSet<MakerId>,
+    /// Balance and status history of every maker
+    history: HistoryStore,
+    /// Latest maker and node readings of the history sampler; not persisted
+    snapshot: Snapshot,
+    /// Dashboard events; shared so subscribers don't hold the manager lock
+    events: Arc<EventBus>,
+    /// Outgoing webhooks and their delivery log
+    webhooks: WebhookStore,
+    /// Alert rules, evaluated by the alert evaluator
+    alert_rules: RuleSet,
+    /// Firing and recently resolved alerts; not persisted
+    alerts: AlertBook,
 }
 
 impl MakerManager {
     const DEFAULT_WALLET_NAME: &'static str = "maker-wallet";
     const LEGACY_RPC_WALLET_NAME: &'static str = "random";
+    const OUTGOING_TXS_FILE: &'static str = "outgoing_txs.json";
+    const LABELS_FILE: &'static str = "labels.jsonl";
+    const ADDRESSES_FILE: &'static str = "addresses.json";
+    const FROZEN_UTXOS_FILE: &'static str = "frozen_utxos.json";
+    const RENEWAL_POLICY_FILE: &'static str = "fidelity_renewal.json";
+    const RENEWAL_LOG_FILE: &'static str = "fidelity_renewal_log.json";
+    const ALERT_RULES_FILE: &'static str = "alert_rules.json";
 
     /// Creates a new MakerManager with persistence at the given config directory.
     /// Loads any previously saved maker configs and re-initializes them (but does NOT start servers).
     pub fn new(config_dir: PathBuf) -> Result<Self> {
         let persistence = PersistenceManager::new(config_dir.clone())?;
         let saved_configs = persistence.load()?;
+        let alert_rules = persistence
+            .load_state(Self::ALERT_RULES_FILE)
+            .unwrap_or_else(|e| {
+                tracing::warn!("Failed to load alert rules: {}", e);
+                RuleSet::default()
+            });
+        let webhooks = WebhookStore::load(&persistence);
 
         let mut mgr = Self {
             pool: MakerPool::new(),
             configs: HashMap::new(),
+            ignored_configs: HashMap::new(),
             persistence,
             bitcoind_process: None,
             bitcoind_network: None,
+            outgoing_txs: HashMap::new(),
+            labels: HashMap::new(),
+            issued_addresses: HashMap::new(),
+            frozen_utxos: HashMap::new(),
+            rescans: HashMap::new(),
+            renewal_policies: HashMap::new(),
+            renewal_log: HashMap::new(),
+            renewals: HashSet::new(),
+            history: HistoryStore::new(config_dir.join("makers")),
+            snapshot: Snapshot::default(),
+            events: Arc::new(EventBus::new()),
+            webhooks,
+            alert_rules,
+            alerts: AlertBook::default(),
         };
 
         // Restore previously registered makers (init only, not started)
         let mut normalized_any = false;
         for (id, config) in saved_configs {
+            if !is_valid_maker_id(&id) {
+                tracing::error!(
+                    "Ignoring maker '{}': IDs may only contain letters, digits, '-' and '_'. \
+                     Rename it in makers.json to load it.",
+                    id
+                );
+                mgr.ignored_configs.insert(id, config);
+                continue;
+            }
             tracing::info!("Restoring maker '{}'", id);
             let original_wallet_name = config.wallet_name.clone();
             let normalized_config = Self::normalize_config(&id, config);
             normalized_any |= normalized_config.wallet_name != original_wallet_name;
 
+            if Self::is_locked_config(&normalized_config) {
+                tracing::info!(
+                    "Maker '{}' is locked until its wallet password is provided",
+                    id
+                );
+                mgr.load_dashboard_state(&id);
+                mgr.configs.insert(id, normalized_config);
+                continue;
+            }
+
             match mgr.create_maker_internal(id.clone(), normalized_config.clone(), false) {
                 Ok(()) => tracing::info!("Maker '{}' restored successfully (stopped)", id),
                 Err(e) => {
@@ -205,23 +354,14 @@ impl MakerManager {
         config: MakerConfig,
         persist: bool,
     ) -> Result<()> {
-        let (user, pass) = config.auth.clone().ok_or_else(|| {
-            anyhow!("RPC authentication credentials must be provided in MakerConfig.auth")
-        })?;
-
         let mut config = Self::normalize_config(&id, config);
+        let rpc_config = Self::rpc_config(&id, &config)?;
         if config.data_directory.is_none() {
             let maker_dir = Self::default_maker_data_dir(&id);
             std::fs::create_dir_all(&maker_dir)?;
             config.data_directory = Some(maker_dir);
         }
 
-        let rpc_config = RPCConfig {
-            url: config.rpc.clone(),
-            auth: Auth::UserPass(user, pass),
-            wallet_name: config.wallet_name.clone().unwrap_or_else(|| id.clone()),
-        };
-
         let data_dir = config
             .data_directory
             .clone()
@@ -261,6 +401,8 @@ impl MakerManager {
         self.pool
             .spawn_maker(id.clone(), maker, config.network_port)?;
 
+        self.load_dashboard_state(&id);
+
         self.configs.insert(id, config);
         if persist {
             self.persist();
@@ -268,6 +410,17 @@ impl MakerManager {
         Ok(())
     }
 
+    fn rpc_config(id: &MakerId, config: &MakerConfig) -> Result<RPCConfig> {
+        let (user, pass) = config.auth.clone().ok_or_else(|| {
+            anyhow!("RPC authentication credentials must be provided in MakerConfig.auth")
+        })?;
+        Ok(RPCConfig {
+            url: config.rpc.clone(),
+            auth: Auth::UserPass(user, pass),
+            wallet_name: config.wallet_name.clone().unwrap_or_else(|| id.clone()),
+        })
+    }
+
     pub fn get_maker_config(&self, id: &str) -> Option<&MakerConfig> {
         self.configs.get(id)
     }
@@ -275,14 +428,235 @@ impl MakerManager {
     /// Creates and registers a new maker (init + message loop only, NOT started).
     /// Use `start_maker` to start the coinswap server.
     pub fn create_maker(&mut self, id: MakerId, config: MakerConfig) -> Result<()> {
+        Self::check_id(&id)?;
         let config = Self::normalize_config(&id, config);
-        self.create_maker_internal(id, config, true)
+        self.create_maker_internal(id.clone(), config, true)?;
+        self.events.publish(Some(&id), EventKind::MakerCreated);
+        Ok(())
     }
 
-    /// Saves current configs to disk.
-    fn persist(&self) {
-        if let Err(e) = self.persistence.save(&self.configs) {
-            tracing::error!("Failed to persist maker configs: {}", e);
+    /// Creates and registers a new maker whose wallet is restored from an encrypted
+    /// coinswap wallet backup instead of being generated from a fresh seed.
+    /// Refuses to overwrite an existing wallet file.
+    pub fn create_maker_from_backup(
+        &mut self,
+        id: MakerId,
+        config: MakerConfig,
+        backup: &str,
+        passphrase: String,
+    ) -> Result<()> {
+        Self::check_id(&id)?;
+        let mut config = Self::normalize_config(&id, config);
+        let data_dir = config
+            .data_directory
+            .clone()
+            .unwrap_or_else(|| Self::default_maker_data_dir(&id));
+        config.data_directory = Some(data_dir);
+        let wallet_path = Self::wallet_path(&id, &config);
+        if wallet_path.exists() {
+            return Err(anyhow!(
+                "Wallet file {} already exists",
+                wallet_path.display()
+            ));
+        }
+        let created = Self::restore_wallet(&id, &config, backup, passphrase)
+            .and_then(|()| self.create_maker_internal(id.clone(), config, true));
+        if let Err(e) = created {
+            // Don't leave a wallet behind that would block retrying with the same ID.
+            if wallet_path.exists() {
+                if let Err(remove_err) = std::fs::remove_file(&wallet_path) {
+                    tracing::warn!(
+                        "Failed to remove restored wallet {}: {remove_err}",
+                        wallet_path.display()
+                    );
+                }
+            }
+            return Err(e);
+        }
+        self.events.publish(Some(&id), EventKind::MakerCreated);
+        Ok(())
+    }
+
+    fn check_id(id: &MakerId) -> Result<()> {
+        if !is_valid_maker_id(id) {
+            return Err(anyhow!(
+                "Invalid maker ID '{id}': use only letters, digits, '-' and '_'"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Path of a maker's coinswap wallet file. `config.data_directory` must be set.
+    fn wallet_path(id: &MakerId, config: &MakerConfig) -> PathBuf {
+        let data_dir = config.data_directory.clone().unwrap_or_default();
+        data_dir
+            .join("wallets")
+            .join(config.wallet_name.clone().unwrap_or_else(|| id.clone()))
+    }
+
+    /// Writes a wallet file restored from `backup`, encrypted with `config.password`.
+    fn restore_wallet(
+        id: &MakerId,
+        config: &MakerConfig,
+        backup: &str,
+        passphrase: String,
+    ) -> Result<()> {
+        let rpc_config = Self::rpc_config(id, config)?;
+        let wallet_path = Self::wallet_path(id, config);
+        if let Some(wallets_dir) = wallet_path.parent() {
+            std::fs::create_dir_all(wallets_dir)?;
+        }
+        let backup_path = wallet_path.with_file_name(format!("{id}-restore.json"));
+        std::fs::write(&backup_path, backup)?;
+        let restored = Wallet::restore(
+            &backup_path,
+            Some(passphrase),
+            &rpc_config,
+            &wallet_path,
+            config.password.clone(),
+        );
+        let _ = std::fs::remove_file(&backup_path);
+        restored.map_err(|e| anyhow!("Failed to restore wallet from backup: {e:?}"))?;
+        Ok(())
+    }
+
+    fn is_locked_config(config: &MakerConfig) -> bool {
+        !config.persist_password && config.password.is_none()
+    }
+
+    /// Whether a maker is waiting for its wallet password (see [`MakerState::Locked`]).
+    pub fn is_locked(&self, id: &MakerId) -> bool {
+        self.configs.get(id).is_some_and(Self::is_locked_config)
+    }
+
+    /// Checks `given` against the maker's wallet password. Makers without a wallet
+    /// password accept anything.
+    pub fn check_wallet_password(
+        &self,
+        id: &MakerId,
+        given: Option<&str>,
+    ) -> Result<(), MakerManagerError> {
+        let config = self
+            .configs
+            .get(id)
+            .ok_or_else(|| MakerManagerError::NotFound(id.clone()))?;
+        if Self::is_locked_config(config) {
+            return Err(MakerManagerError::Locked(id.clone()));
+        }
+        match &config.password {
+            Some(expected)
+                if !constant_time_eq(expected.as_bytes(), given.unwrap_or_default().as_bytes()) =>
+            {
+                Err(MakerManagerError::WrongPassword(id.clone()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Initialises a locked maker with the given wallet password. The password is kept in
+    /// memory only.
+    pub fn unlock_maker(
+        &mut self,
+        id: &MakerId,
+        password: String,
+    ) -> Result<(), MakerManagerError> {
+        let config = self
+            .configs
+            .get(id)
+            .cloned()
+            .ok_or_else(|| MakerManagerError::NotFound(id.clone()))?;
+        if !Self::is_locked_config(&config) {
+            return Err(MakerManagerError::NotLocked(id.clone()));
+        }
+        let config = MakerConfig {
+            password: Some(password),
+            ..config
+        };
+        self.create_maker_internal(id.clone(), config, false)
+            .map_err(|e| anyhow!("Failed to unlock maker '{id}': {e}").into())
+    }
+
+    /// Re-encrypts a stopped maker's wallet with a new password.
+    ///
+    /// coinswap has no in-place re-keying, so the wallet is backed up, moved aside and
+    /// restored under the new password. On failure the original wallet file is put back.
+    pub async fn change_wallet_password(
+        &mut self,
+        id: &MakerId,
+        current_password: Option<&str>,
+        new_password: String,
+        persist_password: bool,
+    ) -> Result<(), MakerManagerError> {
+        self.check_wallet_password(id, current_password)?;
+        if self.pool.is_server_running(id) {
+            return Err(MakerManagerError::AlreadyRunning(id.clone()));
+        }
+        let previous = self.configs.get(id).cloned().expect("checked above");
+        if !self.pool.contains(id) {
+            self.create_maker_internal(id.clone(), previous.clone(), false)?;
+        }
+
+        let backup = match self.wallet_backup(id, new_password.clone()).await? {
+            MessageResponse::WalletBackupResp(backup) => backup,
+            MessageResponse::ServerError(e) => return Err(anyhow!(e).into()),
+            other => return Err(anyhow!("Unexpected response: {other}").into()),
+        };
+
+        let wallet_path = Self::wallet_path(id, &previous);
+        let mut aside = wallet_path.clone().into_os_string();
+        aside.push(".old");
+        let aside = PathBuf::from(aside);
+        self.pool.remove_maker(id);
+        if let Err(e) = std::fs::rename(&wallet_path, &aside) {
+            self.create_maker_internal(id.clone(), previous, false)?;
+            return Err(anyhow!("Failed to move the wallet aside: {e}").into());
+        }
+
+        let updated = MakerConfig {
+            password: Some(new_password.clone()),
+            persist_password,
+            ..previous.clone()
+        };
+        let result = Self::restore_wallet(id, &updated, &backup, new_password)
+            .and_then(|()| self.create_maker_internal(id.clone(), updated, true));
+        match result {
+            Ok(()) => {
+                if let Err(e) = std::fs::remove_file(&aside) {
+                    tracing::warn!("Failed to remove old wallet file for '{}': {}", id, e);
+                }
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Failed to re-encrypt wallet of maker '{}': {}. Rolling back.",
+                    id,
+                    e
+                );
+                let _ = std::fs::remove_file(&wallet_path);
+                std::fs::rename(&aside, &wallet_path)
+                    .map_err(|e| anyhow!("Failed to put the original wallet back: {e}"))?;
+                if let Err(restore_err) = self.create_maker_internal(id.clone(), previous, false) {
+                    return Err(anyhow!(
+                        "Failed to change wallet password: {e}; rollback also failed: {restore_err}"
+                    )
+                    .into());
+                }
+                Err(e.into())
+            }
+        }
+    }
+
+    /// Saves current configs to disk. Returns `false`, after logging why, if they could not
+    /// be written.
+    fn persist(&self) -> bool {
+        let mut configs = self.configs.clone();
+        configs.extend(self.ignored_configs.clone());
+        match self.persistence.save(&configs) {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::error!("Failed to persist maker configs: {}", e);
+                false
+            }
         }
     }
 
@@ -353,6 +727,9 @@ impl MakerManager {
         if !self.configs.contains_key(id) {
             return Err(MakerManagerError::NotFound(id.clone()));
         }
+        if self.is_locked(id) {
+            return Err(MakerManagerError::Locked(id.clone()));
+        }
         if self.pool.is_server_running(id) {
             return Err(MakerManagerError::AlreadyRunning(id.clone()));
         }
@@ -363,7 +740,11 @@ impl MakerManager {
             self.create_maker_internal(id.clone(), config, false)
                 .map_err(MakerManagerError::Other)?;
         }
-        self.pool.start_server(id).map_err(MakerManagerError::Other)
+        self.pool
+            .start_server(id)
+            .map_err(MakerManagerError::Other)?;
+        self.events.publish(Some(id), EventKind::MakerStarted);
+        Ok(())
     }
 
     /// Stops the coinswap server for a running maker.
@@ -375,7 +756,11 @@ impl MakerManager {
         if !self.pool.is_server_running(id) {
             return Err(MakerManagerError::AlreadyStopped(id.clone()));
         }
-        self.pool.stop_server(id).map_err(MakerManagerError::Other)
+        self.pool
+            .stop_server(id)
+            .map_err(MakerManagerError::Other)?;
+        self.events.publish(Some(id), EventKind::MakerStopped);
+        Ok(())
     }
 
     /// Returns full info (id, state, config) for a maker
@@ -384,6 +769,8 @@ impl MakerManager {
             id: id.clone(),
             state: if self.pool.is_server_running(id) {
                 MakerState::Running
+            } else if Self::is_locked_config(config) {
+                MakerState::Locked
             } else {
                 MakerState::Stopped
             },
@@ -430,29 +817,502 @@ impl MakerManager {
         self.request(id, MessageRequest::Balances).await
     }
 
-    /// Generates a new address from a maker's wallet
-    pub async fn get_new_address(&self, id: &MakerId) -> Result<MessageResponse> {
-        self.request(id, MessageRequest::NewAddress).await
+    /// Exports a maker's wallet in coinswap's backup format, encrypted with `passphrase`.
+    pub async fn wallet_backup(&self, id: &MakerId, passphrase: String) -> Result<MessageResponse> {
+        self.request(id, MessageRequest::WalletBackup { passphrase })
+            .await
     }
 
-    /// Sends funds to an address from a maker's wallet
+    /// Collects the wallet data used to classify a maker's transaction history
+    pub async fn get_tx_hints(&self, id: &MakerId) -> Result<MessageResponse> {
+        self.request(id, MessageRequest::TxHints).await
+    }
+
+    /// Derives the next receive address of the given type from a maker's wallet and
+    /// records it as issued.
+    pub async fn get_new_address(
+        &mut self,
+        id: &MakerId,
+        address_type: WalletAddressType,
+    ) -> Result<MessageResponse> {
+        let resp = self
+            .request(id, MessageRequest::NewAddress { address_type })
+            .await?;
+        if let MessageResponse::NewAddressResp(address) = &resp {
+            let addresses = self.issued_addresses.entry(id.clone()).or_default();
+            addresses.push(IssuedAddress {
+                address: address.clone(),
+                address_type,
+                issued_at: unix_now(),
+            });
+            if let Err(e) = self
+                .persistence
+                .save_maker_state(id, Self::ADDRESSES_FILE, addresses)
+            {
+                tracing::error!("Failed to persist issued addresses for '{}': {}", id, e);
+            }
+        }
+        Ok(resp)
+    }
+
+    /// Returns the receive addresses issued through the dashboard for a maker, newest first.
+    pub fn issued_addresses(&self, id: &MakerId) -> Vec<IssuedAddress> {
+        let mut addresses = self.issued_addresses.get(id).cloned().unwrap_or_default();
+        addresses.reverse();
+        addresses
+    }
+
+    /// Sends funds to an address from a maker's wallet, never spending frozen UTXOs.
+    /// The broadcast transaction is tracked so it can be fee-bumped later.
     pub async fn send_to_address(
-        &self,
+        &mut self,
         id: &MakerId,
         address: String,
         amount: u64,
         feerate: f64,
     ) -> Result<MessageResponse> {
-        self.pool
+        let resp = self
+            .pool
             .request(
                 id,
                 MessageRequest::SendToAddress {
                     address,
                     amount,
                     feerate,
+                    exclude: self
+                        .frozen_utxos
+                        .get(id)
+                        .map(|frozen| frozen.iter().copied().collect())
+                        .unwrap_or_default(),
                 },
             )
-            .await
+            .await?;
+        if let MessageResponse::SendToAddressResp(tx) = &resp {
+            self.track_outgoing_tx(id, tx.clone());
+        }
+        Ok(resp)
+    }
+
+    /// The latest rescan or descriptor re-import of a maker, if any was started.
+    pub fn rescan_job(&self, id: &MakerId) -> Option<RescanJob> {
+        self.rescans.get(id).cloned()
+    }
+
+    /// Records the start of a rescan or re-import. Only one may run per maker.
+    pub fn begin_rescan(
+        &mut self,
+        id: &MakerId,
+        kind: RescanKind,
+        from_height: u64,
+    ) -> Result<RescanJob, MakerManagerError> {
+        if !self.configs.contains_key(id) {
+            return Err(MakerManagerError::NotFound(id.clone()));
+        }
+        if self.is_locked(id) {
+            return Err(MakerManagerError::Locked(id.clone()));
+        }
+        if self
+            .rescans
+            .get(id)
+            .is_some_and(|job| job.finished_at.is_none())
+        {
+            return Err(MakerManagerError::RescanInProgress(id.clone()));
+        }
+        let job = RescanJob {
+            kind,
+            from_height,
+            started_at: unix_now(),
+            finished_at: None,
+            error: None,
+        };
+        self.rescans.insert(id.clone(), job.clone());
+        Ok(job)
+    }
+
+    /// Marks a maker's running rescan or re-import as finished.
+    pub fn finish_rescan(&mut self, id: &MakerId, error: Option<String>) {
+        if let Some(job) = self.rescans.get_mut(id) {
+            job.finished_at = Some(unix_now());
+            job.error = error;
+        }
+    }
+
+    /// Loads dashboard-side state (tracked transactions, labels) kept for a maker, unless
+    /// it is already in memory.
+    fn load_dashboard_state(&mut self, id: &MakerId) {
+        if !self.outgoing_txs.contains_key(id) {
+            let txs = self
+                .persistence
+                .load_maker_state(id, Self::OUTGOING_TXS_FILE)
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to load outgoing transactions for '{}': {}", id, e);
+                    Vec::new()
+                });
+            self.outgoing_txs.insert(id.clone(), txs);
+        }
+        if !self.issued_addresses.contains_key(id) {
+            let addresses = self
+                .persistence
+                .load_maker_state(id, Self::ADDRESSES_FILE)
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to load issued addresses for '{}': {}", id, e);
+                    Vec::new()
+                });
+            self.issued_addresses.insert(id.clone(), addresses);
+        }
+        if !self.frozen_utxos.contains_key(id) {
+            let frozen = self
+                .persistence
+                .load_maker_state(id, Self::FROZEN_UTXOS_FILE)
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to load frozen UTXOs for '{}': {}", id, e);
+                    BTreeSet::new()
+                });
+            self.frozen_utxos.insert(id.clone(), frozen);
+        }
+        if !self.renewal_policies.contains_key(id) {
+            let policy = self
+                .persistence
+                .load_maker_state(id, Self::RENEWAL_POLICY_FILE)
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to load renewal policy for '{}': {}", id, e);
+                    RenewalPolicy::default()
+                });
+            self.renewal_policies.insert(id.clone(), policy);
+        }
+        if !self.renewal_log.contains_key(id) {
+            let log = self
+                .persistence
+                .load_maker_state(id, Self::RENEWAL_LOG_FILE)
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to load renewal log for '{}': {}", id, e);
+                    Vec::new()
+                });
+            self.renewal_log.insert(id.clone(), log);
+        }
+        if !self.labels.contains_key(id) {
+            let labels = self
+                .persistence
+                .load_maker_file(id, Self::LABELS_FILE)
+                .map_err(|e| e.to_string())
+                .and_then(|raw| LabelStore::from_jsonl(&raw.unwrap_or_default()))
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to load labels for '{}': {}", id, e);
+                    LabelStore::default()
+                });
+            self.labels.insert(id.clone(), labels);
+        }
+    }
+
+    /// Returns a maker's labels, or `None` if the maker is not registered.
+    pub fn labels(&self, id: &MakerId) -> Option<LabelStore> {
+        if !self.configs.contains_key(id) {
+            return None;
+        }
+        Some(self.labels.get(id).cloned().unwrap_or_default())
+    }
+
+    /// Adds or replaces a label and persists the maker's label store.
+    pub fn set_label(&mut self, id: &MakerId, label: Label) -> Result<(), MakerManagerError> {
+        self.update_labels(id, |labels| labels.set(label))
+    }
+
+    /// Removes a label. Returns `false` if no such label existed.
+    pub fn remove_label(
+        &mut self,
+        id: &MakerId,
+        label_type: LabelType,
+        reference: &str,
+    ) -> Result<bool, MakerManagerError> {
+        self.update_labels(id, |labels| labels.remove(label_type, reference).is_some())
+    }
+
+    /// Merges imported labels into a maker's store, replacing existing ones with the same
+    /// type and reference. Returns the number of records imported.
+    pub fn import_labels(
+        &mut self,
+        id: &MakerId,
+        imported: Vec<Label>,
+    ) -> Result<usize, MakerManagerError> {
+        let count = imported.len();
+        self.update_labels(id, |labels| {
+            imported.into_iter().for_each(|l| labels.set(l))
+        })?;
+        Ok(count)
+    }
+
+    fn update_labels<T>(
+        &mut self,
+        id: &MakerId,
+        update: impl FnOnce(&mut LabelStore) -> T,
+    ) -> Result<T, MakerManagerError> {
+        if !self.configs.contains_key(id) {
+            return Err(MakerManagerError::NotFound(id.clone()));
+        }
+        let mut labels = self.labels.get(id).cloned().unwrap_or_default();
+        let result = update(&mut labels);
+        self.persistence
+            .save_maker_file(id, Self::LABELS_FILE, &labels.to_jsonl())
+            .map_err(MakerManagerError::Other)?;
+        self.labels.insert(id.clone(), labels);
+        Ok(result)
+    }
+
+    /// Returns a maker's frozen UTXOs, or `None` if the maker is not registered.
+    pub fn frozen_utxos(&self, id: &MakerId) -> Option<BTreeSet<OutPoint>> {
+        if !self.configs.contains_key(id) {
+            return None;
+        }
+        Some(self.frozen_utxos.get(id).cloned().unwrap_or_default())
+    }
+
+    /// Freezes or unfreezes a UTXO and persists the maker's frozen set.
+    /// Returns `false` if the UTXO already was in the requested state.
+    pub fn set_utxo_frozen(
+        &mut self,
+        id: &MakerId,
+        outpoint: OutPoint,
+        frozen: bool,
+    ) -> Result<bool, MakerManagerError> {
+        if !self.configs.contains_key(id) {
+            return Err(MakerManagerError::NotFound(id.clone()));
+        }
+        let mut set = self.frozen_utxos.get(id).cloned().unwrap_or_default();
+        let changed = if frozen {
+            set.insert(outpoint)
+        } else {
+            set.remove(&outpoint)
+        };
+        if changed {
+            self.persistence
+                .save_maker_state(id, Self::FROZEN_UTXOS_FILE, &set)
+                .map_err(MakerManagerError::Other)?;
+            self.frozen_utxos.insert(id.clone(), set);
+        }
+        Ok(changed)
+    }
+
+    /// Drops spent outpoints from a maker's frozen set and persists it.
+    pub fn forget_frozen_utxos(
+        &mut self,
+        id: &MakerId,
+        spent: &[OutPoint],
+    ) -> Result<(), MakerManagerError> {
+        let Some(current) = self.frozen_utxos.get(id) else {
+            return Ok(());
+        };
+        let mut set = current.clone();
+        set.retain(|outpoint| !spent.contains(outpoint));
+        if set.len() == current.len() {
+            return Ok(());
+        }
+        self.persistence
+            .save_maker_state(id, Self::FROZEN_UTXOS_FILE, &set)
+            .map_err(MakerManagerError::Other)?;
+        self.frozen_utxos.insert(id.clone(), set);
+        Ok(())
+    }
+
+    /// Returns a maker's fidelity bond renewal policy, or `None` if the maker is not registered.
+    pub fn renewal_policy(&self, id: &MakerId) -> Option<RenewalPolicy> {
+        if !self.configs.contains_key(id) {
+            return None;
+        }
+        Some(self.renewal_policies.get(id).cloned().unwrap_or_default())
+    }
+
+    /// Replaces and persists a maker's fidelity bond renewal policy.
+    pub fn set_renewal_policy(
+        &mut self,
+        id: &MakerId,
+        policy: RenewalPolicy,
+    ) -> Result<(), MakerManagerError> {
+        if !self.configs.contains_key(id) {
+            return Err(MakerManagerError::NotFound(id.clone()));
+        }
+        self.persistence
+            .save_maker_state(id, Self::RENEWAL_POLICY_FILE, &policy)
+            .map_err(MakerManagerError::Other)?;
+        self.renewal_policies.insert(id.clone(), policy);
+        Ok(())
+    }
+
+    /// Makers with renewal enabled that can be acted on, i.e. are not locked.
+    pub fn renewal_makers(&self) -> Vec<MakerId> {
+        self.renewal_policies
+            .iter()
+            .filter(|(id, policy)| policy.enabled && !self.is_locked(id))
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    /// Claims a maker's renewal, so the watcher and a manual evaluation can't both plan
+    /// and broadcast a bond. Release it with [`Self::finish_renewal`].
+    pub fn begin_renewal(&mut self, id: &MakerId) -> Result<(), MakerManagerError> {
+        if !self.configs.contains_key(id) {
+            return Err(MakerManagerError::NotFound(id.clone()));
+        }
+        if !self.renewals.insert(id.clone()) {
+            return Err(MakerManagerError::RenewalInProgress(id.clone()));
+        }
+        Ok(())
+    }
+
+    /// Releases a renewal claimed with [`Self::begin_renewal`].
+    pub fn finish_renewal(&mut self, id: &MakerId) {
+        self.renewals.remove(id);
+    }
+
+    /// Returns a maker's renewal audit log, newest first.
+    pub fn renewal_log(&self, id: &MakerId) -> Vec<RenewalRecord> {
+        let mut log = self.renewal_log.get(id).cloned().unwrap_or_default();
+        log.reverse();
+        log
+    }
+
+    /// Appends a renewal audit record unless it repeats the previous one.
+    /// Returns whether the record was kept.
+    pub fn record_renewal(&mut self, id: &MakerId, record: RenewalRecord) -> bool {
+        let log = self.renewal_log.entry(id.clone()).or_default();
+        if log.last().is_some_and(|last| record.repeats(last)) {
+            return false;
+        }
+        log.push(record);
+        if log.len() > RENEWAL_LOG_LIMIT {
+            log.drain(..log.len() - RENEWAL_LOG_LIMIT);
+        }
+        if let Err(e) = self
+            .persistence
+            .save_maker_state(id, Self::RENEWAL_LOG_FILE, log)
+        {
+            tracing::warn!("Failed to save renewal log for '{}': {}", id, e);
+        }
+        true
+    }
+
+    /// Appends a balance and status sample to a maker's history.
+    pub fn record_history(&mut self, id: &MakerId, point: HistoryPoint) {
+        if !self.configs.contains_key(id) {
+            return;
+        }
+        self.snapshot.makers.insert(id.clone(), point);
+        if let Err(e) = self.history.append(id, point) {
+            tracing::warn!("Failed to record history for '{}': {}", id, e);
+        }
+    }
+
+    /// Records the unlock heights of a maker's unspent fidelity bonds in the snapshot.
+    pub fn record_bond_locktimes(&mut self, id: &MakerId, locktimes: Vec<u32>) {
+        if self.configs.contains_key(id) {
+            self.snapshot.bond_locktimes.insert(id.clone(), locktimes);
+        }
+    }
+
+    /// Replaces the node readings of the snapshot with those of the latest sampling round.
+    pub fn record_nodes(&mut self, nodes: HashMap<String, NodeSample>) {
+        self.snapshot.nodes = nodes;
+    }
+
+    /// Latest maker and node readings of the history sampler.
+    pub fn snapshot(&self) -> &Snapshot {
+        &self.snapshot
+    }
+
+    /// Returns a maker's history of `from..to` in `step`-second buckets, or `None` if the
+    /// maker is not registered.
+    pub fn history(
+        &mut self,
+        id: &MakerId,
+        from: u64,
+        to: u64,
+        step: u64,
+    ) -> Option<Vec<HistoryPoint>> {
+        if !self.configs.contains_key(id) {
+            return None;
+        }
+        Some(self.history.range(id, from, to, step))
+    }
+
+    /// Returns a maker's recorded history points covering `from..to` at the finest
+    /// resolution still kept, including the last point before `from`.
+    pub fn history_points(&mut self, id: &MakerId, from: u64, to: u64) -> Vec<HistoryPoint> {
+        if !self.configs.contains_key(id) {
+            return Vec::new();
+        }
+        self.history.points(id, from, to)
+    }
+
+    /// Returns the transactions broadcast through the dashboard for a maker, newest first.
+    pub fn outgoing_txs(&self, id: &MakerId) -> Vec<OutgoingTx> {
+        let mut txs = self.outgoing_txs.get(id).cloned().unwrap_or_default();
+        txs.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        txs
+    }
+
+    /// Looks up a tracked outgoing transaction by txid.
+    pub fn get_outgoing_tx(&self, id: &MakerId, txid: &Txid) -> Option<&OutgoingTx> {
+        self.outgoing_txs
+            .get(id)
+            .and_then(|txs| txs.iter().find(|tx| &tx.txid == txid))
+    }
+
+    /// Fee-bumps a tracked outgoing transaction via RBF or CPFP.
+    /// On success the new transaction is tracked and, for RBF, the original is marked replaced.
+    /// The replacement check and the broadcast happen under the same borrow, so concurrent
+    /// bumps of one transaction can't both go out.
+    pub async fn bump_fee(
+        &mut self,
+        id: &MakerId,
+        txid: &Txid,
+        feerate: f64,
+        method: BumpMethod,
+    ) -> Result<MessageResponse, MakerManagerError> {
+        let tx = self
+            .get_outgoing_tx(id, txid)
+            .cloned()
+            .ok_or_else(|| anyhow!("Transaction {txid} is not tracked for maker '{id}'"))?;
+        if let Some(replacement) = tx.replaced_by {
+            return Err(MakerManagerError::Replaced(*txid, replacement));
+        }
+        let resp = self
+            .pool
+            .request(
+                id,
+                MessageRequest::BumpFee {
+                    tx,
+                    feerate,
+                    method,
+                },
+            )
+            .await?;
+        if let MessageResponse::BumpFeeResp(new_tx) = &resp {
+            if method == BumpMethod::Rbf {
+                if let Some(original) = self
+                    .outgoing_txs
+                    .get_mut(id)
+                    .and_then(|txs| txs.iter_mut().find(|tx| &tx.txid == txid))
+                {
+                    original.replaced_by = Some(new_tx.txid);
+                }
+            }
+            self.track_outgoing_tx(id, new_tx.clone());
+        }
+        Ok(resp)
+    }
+
+    fn track_outgoing_tx(&mut self, id: &MakerId, tx: OutgoingTx) {
+        let txs = self.outgoing_txs.entry(id.clone()).or_default();
+        txs.push(tx);
+        if let Err(e) = self
+            .persistence
+            .save_maker_state(id, Self::OUTGOING_TXS_FILE, txs)
+        {
+            tracing::error!(
+                "Failed to persist outgoing transactions for '{}': {}",
+                id,
+                e
+            );
+        }
     }
 
     /// Gets the Tor address of a maker
@@ -465,6 +1325,53 @@ impl MakerManager {
         self.request(id, MessageRequest::GetDataDir).await
     }
 
+    /// Creates a fidelity bond in a maker's wallet, or only prices it when `dry_run` is set.
+    /// The wallet picks the bond's inputs itself, so this fails while any spendable coin is
+    /// frozen.
+    pub async fn create_fidelity(
+        &self,
+        id: &MakerId,
+        amount: u64,
+        locktime: u32,
+        feerate: f64,
+        dry_run: bool,
+    ) -> Result<MessageResponse> {
+        self.request(
+            id,
+            MessageRequest::CreateFidelity {
+                amount,
+                locktime,
+                feerate,
+                exclude: self
+                    .frozen_utxos
+                    .get(id)
+                    .map(|frozen| frozen.iter().copied().collect())
+                    .unwrap_or_default(),
+                dry_run,
+            },
+        )
+        .await
+    }
+
+    /// Redeems an expired fidelity bond of a maker at the given feerate.
+    pub async fn redeem_fidelity(
+        &self,
+        id: &MakerId,
+        index: u32,
+        feerate: f64,
+        tip: u32,
+    ) -> Result<MessageResponse> {
+        self.request(
+            id,
+            MessageRequest::RedeemFidelity {
+                index,
+                feerate,
+                tip,
+            },
+        )
+        .await
+    }
+
     /// Lists fidelity bonds of a maker
     pub async fn list_fidelity(&self, id: &MakerId) -> Result<MessageResponse> {
         self.request(id, MessageRequest::ListFidelity).await
@@ -482,6 +1389,9 @@ impl MakerManager {
 
     /// Updates a maker's configuration, re-initialising the maker with the new settings.
     pub fn update_config(&mut self, id: &MakerId, config: MakerConfig) -> Result<()> {
+        if self.is_locked(id) {
+            return Err(MakerManagerError::Locked(id.clone()).into());
+        }
         let config = Self::normalize_config(id, config);
         let previous = self
             .configs
@@ -512,8 +1422,10 @@ impl MakerManager {
                             id,
                             e
                         );
+                        self.events.publish(Some(id), EventKind::MakerStopped);
                     }
                 }
+                self.events.publish(Some(id), EventKind::ConfigUpdated);
                 Ok(())
             }
             Err(e) => {
@@ -560,9 +1472,26 @@ impl MakerManager {
     pub fn remove_maker(&mut self, id: &MakerId) -> bool {
         self.pool.remove_maker(id);
         MakerLogWriter::unregister_maker(id);
+        self.outgoing_txs.remove(id);
+        self.labels.remove(id);
+        self.issued_addresses.remove(id);
+        self.frozen_utxos.remove(id);
+        self.rescans.remove(id);
+        self.renewal_policies.remove(id);
+        self.renewal_log.remove(id);
+        self.renewals.remove(id);
+        self.history.remove(id);
+        self.snapshot.makers.remove(id);
+        self.snapshot.bond_locktimes.remove(id);
         let removed = self.configs.remove(id).is_some();
+        // Subscribers only hear of deletions that survive a restart
+        if removed && self.persist() {
+            self.events.publish(Some(id), EventKind::MakerDeleted);
+        }
         if removed {
-            self.persist();
+            if let Err(e) = self.persistence.remove_maker_state(id) {
+                tracing::warn!("Failed to remove dashboard state for '{}': {}", id, e);
+            }
         }
         removed
     }
@@ -572,18 +1501,161 @@ impl MakerManager {
         if !self.configs.contains_key(id) {
             return Err(MakerManagerError::NotFound(id.clone()));
         }
+        if self.is_locked(id) {
+            return Err(MakerManagerError::Locked(id.clone()));
+        }
         if self.pool.is_server_running(id) {
             self.pool
                 .stop_server(id)
                 .map_err(MakerManagerError::Other)?;
+            self.events.publish(Some(id), EventKind::MakerStopped);
+        }
+        self.pool
+            .start_server(id)
+            .map_err(MakerManagerError::Other)?;
+        self.events.publish(Some(id), EventKind::MakerStarted);
+        Ok(())
+    }
+
+    /// Marks makers whose coinswap server exited on its own as stopped and publishes a
+    /// [`EventKind::MakerCrashed`] event for each. Returns their IDs.
+    pub fn reap_crashed(&mut self) -> Vec<MakerId> {
+        let crashed = self.pool.reap_crashed();
+        for id in &crashed {
+            tracing::warn!("Maker '{}' server exited unexpectedly", id);
+            self.events.publish(Some(id), EventKind::MakerCrashed);
+        }
+        crashed
+    }
+
+    /// The dashboard's event bus.
+    pub fn events(&self) -> Arc<EventBus> {
+        self.events.clone()
+    }
+
+    pub fn webhooks(&self) -> Vec<Webhook> {
+        self.webhooks.list().to_vec()
+    }
+
+    pub fn webhook(&self, id: u64) -> Option<Webhook> {
+        self.webhooks.get(id).cloned()
+    }
+
+    /// Adds and persists a webhook, assigning its ID.
+    pub fn add_webhook(&mut self, webhook: Webhook) -> Result<Webhook, MakerManagerError> {
+        self.webhooks
+            .add(&self.persistence, webhook)
+            .map_err(MakerManagerError::Other)
+    }
+
+    /// Replaces and persists the webhook with the same ID. Returns `false` if there is none.
+    pub fn update_webhook(&mut self, webhook: Webhook) -> Result<bool, MakerManagerError> {
+        self.webhooks
+            .update(&self.persistence, webhook)
+            .map_err(MakerManagerError::Other)
+    }
+
+    /// Removes a webhook and its delivery log. Returns `false` if there was no such webhook.
+    pub fn remove_webhook(&mut self, id: u64) -> Result<bool, MakerManagerError> {
+        self.webhooks
+            .remove(&self.persistence, id)
+            .map_err(MakerManagerError::Other)
+    }
+
+    /// Logs a webhook delivery attempt and returns it with its ID.
+    pub fn record_delivery(&mut self, delivery: Delivery) -> Delivery {
+        self.webhooks.record(delivery)
+    }
+
+    pub fn alert_rules(&self) -> Vec<AlertRule> {
+        self.alert_rules.rules().to_vec()
+    }
+
+    pub fn alert_rule(&self, id: u64) -> Option<AlertRule> {
+        self.alert_rules.get(id).cloned()
+    }
+
+    /// Adds and persists an alert rule, assigning its ID.
+    pub fn add_alert_rule(&mut self, rule: AlertRule) -> Result<AlertRule, MakerManagerError> {
+        let mut rules = self.alert_rules.clone();
+        let rule = rules.add(rule);
+        self.save_alert_rules(rules)?;
+        Ok(rule)
+    }
+
+    /// Replaces and persists the alert rule with the same ID. Returns `false` if there is none.
+    pub fn update_alert_rule(&mut self, rule: AlertRule) -> Result<bool, MakerManagerError> {
+        let mut rules = self.alert_rules.clone();
+        if !rules.update(rule) {
+            return Ok(false);
+        }
+        self.save_alert_rules(rules)?;
+        Ok(true)
+    }
+
+    /// Removes an alert rule. Returns `false` if there was no such rule.
+    pub fn remove_alert_rule(&mut self, id: u64) -> Result<bool, MakerManagerError> {
+        let mut rules = self.alert_rules.clone();
+        if !rules.remove(id) {
+            return Ok(false);
         }
-        self.pool.start_server(id).map_err(MakerManagerError::Other)
+        self.save_alert_rules(rules)?;
+        Ok(true)
+    }
+
+    /// Saves `rules` and makes them the current alert rules once they are on disk.
+    fn save_alert_rules(&mut self, rules: RuleSet) -> Result<(), MakerManagerError> {
+        self.persistence
+            .save_state(Self::ALERT_RULES_FILE, &rules)
+            .map_err(MakerManagerError::Other)?;
+        self.alert_rules = rules;
+        Ok(())
+    }
+
+    /// Records the outcome of checking `rule` for a maker. Returns the alert if it was
+    /// raised or resolved.
+    pub fn apply_alert(
+        &mut self,
+        rule: &AlertRule,
+        maker: &MakerId,
+        check: Check,
+        now: u64,
+    ) -> Option<Alert> {
+        self.alerts.apply(rule, maker, check, now)
+    }
+
+    /// Resolves firing alerts whose rule or maker was removed, or whose rule was disabled or
+    /// no longer applies to the maker. Returns them.
+    pub fn resolve_stale_alerts(&mut self, now: u64) -> Vec<Alert> {
+        let (rules, configs) = (&self.alert_rules, &self.configs);
+        self.alerts.resolve_where(now, |alert| {
+            configs.contains_key(&alert.maker)
+                && rules
+                    .get(alert.rule_id)
+                    .is_some_and(|rule| rule.applies_to(&alert.maker))
+        })
+    }
+
+    /// Firing alerts, newest first, followed by recently resolved ones.
+    pub fn alerts(&self) -> Vec<Alert> {
+        self.alerts.list()
+    }
+
+    /// A webhook's delivery attempts, newest first, or `None` if there is no such webhook.
+    pub fn webhook_deliveries(&self, id: u64) -> Option<Vec<Delivery>> {
+        self.webhooks.get(id)?;
+        Some(self.webhooks.deliveries(id))
     }
 
     pub fn is_server_running(&mut self, id: &MakerId) -> bool {
         self.pool.is_server_running(id)
     }
 
+    /// Whether a maker's server was started and not stopped since; true for crashed servers.
+    pub fn should_be_running(&self, id: &MakerId) -> bool {
+        self.pool.should_be_running(id)
+    }
+
     /// Returns the log file path for a given maker ID.
     pub fn log_file_path(&self, maker_id: &str) -> std::path::PathBuf {
         self.configs
@@ -656,9 +1728,12 @@ impl MakerManager {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeSet;
     use std::net::TcpListener;
 
-    use super::{MakerConfig, MakerManager};
+    use coinswap::bitcoin::OutPoint;
+
+    use super::{MakerConfig, MakerManager, MakerManagerError};
 
     #[test]
     fn normalize_wallet_name_defaults_to_maker_id() {
@@ -690,6 +1765,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn unpersisted_password_locks_once_forgotten() {
+        let config = MakerConfig {
+            password: Some("hunter2".to_string()),
+            persist_password: false,
+            ..MakerConfig::default()
+        };
+        assert!(!MakerManager::is_locked_config(&config));
+        let reloaded = MakerConfig {
+            password: None,
+            ..config
+        };
+        assert!(MakerManager::is_locked_config(&reloaded));
+        assert!(!MakerManager::is_locked_config(&MakerConfig::default()));
+    }
+
+    #[test]
+    fn constant_time_eq_compares_contents() {
+        assert!(super::constant_time_eq(b"hunter2", b"hunter2"));
+        assert!(!super::constant_time_eq(b"hunter2", b"hunter3"));
+        assert!(!super::constant_time_eq(b"hunter2", b"hunter"));
+    }
+
     #[test]
     fn normalize_config_updates_wallet_name() {
         let config = MakerConfig {
@@ -735,6 +1833,60 @@ mod tests {
         assert_ne!(rpc_port, 9050);
         assert_ne!(rpc_port, 9051);
     }
+
+    #[test]
+    fn spent_frozen_utxos_are_forgotten() {
+        let config_dir =
+            std::env::temp_dir().join(format!("maker-manager-frozen-test-{}", std::process::id()));
+        if config_dir.exists() {
+            std::fs::remove_dir_all(&config_dir).unwrap();
+        }
+        std::fs::create_dir_all(&config_dir).unwrap();
+
+        let mut manager = MakerManager::new(config_dir.clone()).unwrap();
+        let id = "m1".to_string();
+        manager.configs.insert(id.clone(), MakerConfig::default());
+        let txid = "a".repeat(64);
+        let kept: OutPoint = format!("{txid}:0").parse().unwrap();
+        let spent: OutPoint = format!("{txid}:1").parse().unwrap();
+        assert!(manager.set_utxo_frozen(&id, kept, true).unwrap());
+        assert!(manager.set_utxo_frozen(&id, spent, true).unwrap());
+
+        manager.forget_frozen_utxos(&id, &[spent]).unwrap();
+        assert_eq!(manager.frozen_utxos(&id).unwrap(), BTreeSet::from([kept]));
+        let stored: BTreeSet<OutPoint> = manager
+            .persistence
+            .load_maker_state(&id, MakerManager::FROZEN_UTXOS_FILE)
+            .unwrap();
+        assert_eq!(stored, BTreeSet::from([kept]));
+        std::fs::remove_dir_all(config_dir).unwrap();
+    }
+
+    #[test]
+    fn only_one_renewal_runs_per_maker() {
+        let config_dir =
+            std::env::temp_dir().join(format!("maker-manager-renewal-test-{}", std::process::id()));
+        if config_dir.exists() {
+            std::fs::remove_dir_all(&config_dir).unwrap();
+        }
+        std::fs::create_dir_all(&config_dir).unwrap();
+
+        let mut manager = MakerManager::new(config_dir.clone()).unwrap();
+        let id = "m1".to_string();
+        assert!(matches!(
+            manager.begin_renewal(&id),
+            Err(MakerManagerError::NotFound(_))
+        ));
+        manager.configs.insert(id.clone(), MakerConfig::default());
+        manager.begin_renewal(&id).unwrap();
+        assert!(matches!(
+            manager.begin_renewal(&id),
+            Err(MakerManagerError::RenewalInProgress(_))
+        ));
+        manager.finish_renewal(&id);
+        manager.begin_renewal(&id).unwrap();
+        std::fs::remove_dir_all(config_dir).unwrap();
+    }
 }
 
 impl Drop for MakerManager {
@@ -746,6 +1898,19 @@ impl Drop for MakerManager {
     }
 }
 
+/// Current Unix timestamp in seconds.
+pub(crate) fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Compares secrets without short-circuiting on the first differing byte.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
 /// Typed errors for MakerManager operations
 #[derive(Debug, thiserror::Error)]
 pub enum MakerManagerError {
@@ -755,6 +1920,18 @@ pub enum MakerManagerError {
     AlreadyRunning(String),
     #[error("Maker '{0}' is already stopped")]
     AlreadyStopped(String),
+    #[error("Maker '{0}' is locked; unlock it with its wallet password first")]
+    Locked(String),
+    #[error("Maker '{0}' is not locked")]
+    NotLocked(String),
+    #[error("Wallet password for maker '{0}' does not match")]
+    WrongPassword(String),
+    #[error("A rescan of maker '{0}' is already running")]
+    RescanInProgress(String),
+    #[error("A fidelity bond renewal of maker '{0}' is already running")]
+    RenewalInProgress(String),
+    #[error("Transaction {0} was already replaced by {1}")]
+    Replaced(Txid, Txid),
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
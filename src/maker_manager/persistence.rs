@@ -2,10 +2,10 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
-use anyhow::{Context, Result};
-use serde::{Deserialize, Serialize};
+use anyhow::{bail, Context, Result};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
-use super::maker_pool::MakerId;
+use super::maker_pool::{is_valid_maker_id, MakerId};
 use super::MakerConfig;
 
 fn default_network_port() -> u16 {
@@ -41,6 +41,9 @@ fn default_time_relative_fee_pct() -> f64 {
 fn default_required_confirms() -> u32 {
     1
 }
+fn default_persist_password() -> bool {
+    true
+}
 /// On-disk representation of a single maker's config.
 #[derive(Debug, Serialize, Deserialize)]
 struct StoredMakerConfig {
@@ -52,6 +55,8 @@ struct StoredMakerConfig {
     tor_auth: Option<String>,
     wallet_name: Option<String>,
     password: Option<String>,
+    #[serde(default = "default_persist_password")]
+    persist_password: bool,
     #[serde(default = "default_network_port")]
     network_port: u16,
     #[serde(default = "default_rpc_port")]
@@ -92,7 +97,12 @@ impl From<&MakerConfig> for StoredMakerConfig {
             rpc_password,
             tor_auth: c.tor_auth.clone(),
             wallet_name: c.wallet_name.clone(),
-            password: c.password.clone(),
+            password: if c.persist_password {
+                c.password.clone()
+            } else {
+                None
+            },
+            persist_password: c.persist_password,
             network_port: c.network_port,
             rpc_port: c.rpc_port,
             socks_port: c.socks_port,
@@ -122,6 +132,7 @@ impl From<StoredMakerConfig> for MakerConfig {
             tor_auth: s.tor_auth,
             wallet_name: s.wallet_name,
             password: s.password,
+            persist_password: s.persist_password,
             network_port: s.network_port,
             rpc_port: s.rpc_port,
             socks_port: s.socks_port,
@@ -189,6 +200,102 @@ impl PersistenceManager {
         Ok(())
     }
 
+    /// Saves a piece of dashboard-wide state as `<name>` in the config dir
+    pub fn save_state<T: Serialize>(&self, name: &str, value: &T) -> Result<()> {
+        let json = serde_json::to_string_pretty(value)
+            .with_context(|| format!("Failed to serialize {name}"))?;
+        let path = self.config_dir.join(name);
+        fs::write(&path, json)
+            .with_context(|| format!("Failed to write state file: {}", path.display()))
+    }
+
+    /// Loads a piece of dashboard-wide state. Returns the default value if it was never saved.
+    pub fn load_state<T: DeserializeOwned + Default>(&self, name: &str) -> Result<T> {
+        let path = self.config_dir.join(name);
+        if !path.exists() {
+            return Ok(T::default());
+        }
+        let json = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read state file: {}", path.display()))?;
+        serde_json::from_str(&json)
+            .with_context(|| format!("Failed to parse state file: {}", path.display()))
+    }
+
+    /// Returns the directory holding dashboard-side state for a single maker
+    fn maker_state_dir(&self, id: &str) -> Result<PathBuf> {
+        if !is_valid_maker_id(id) {
+            bail!("Invalid maker ID '{id}'");
+        }
+        Ok(self.config_dir.join("makers").join(id))
+    }
+
+    /// Saves a piece of per-maker dashboard state as `makers/<id>/<name>`
+    pub fn save_maker_state<T: Serialize>(&self, id: &str, name: &str, value: &T) -> Result<()> {
+        let json = serde_json::to_string_pretty(value)
+            .with_context(|| format!("Failed to serialize {name} for maker '{id}'"))?;
+        self.save_maker_file(id, name, &json)
+    }
+
+    /// Loads a piece of per-maker dashboard state. Returns the default value if it was never saved.
+    pub fn load_maker_state<T: DeserializeOwned + Default>(
+        &self,
+        id: &str,
+        name: &str,
+    ) -> Result<T> {
+        let Some(json) = self.load_maker_file(id, name)? else {
+            return Ok(T::default());
+        };
+        let path = self.maker_state_dir(id)?.join(name);
+        serde_json::from_str(&json)
+            .with_context(|| format!("Failed to parse state file: {}", path.display()))
+    }
+
+    /// Writes a raw per-maker state file as `makers/<id>/<name>`
+    pub fn save_maker_file(&self, id: &str, name: &str, contents: &str) -> Result<()> {
+        let dir = self.maker_state_dir(id)?;
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create maker state dir: {}", dir.display()))?;
+
+        let path = dir.join(name);
+        fs::write(&path, contents)
+            .with_context(|| format!("Failed to write state file: {}", path.display()))?;
+
+        Ok(())
+    }
+
+    /// Reads a raw per-maker state file. Returns `None` if it was never saved.
+    pub fn load_maker_file(&self, id: &str, name: &str) -> Result<Option<String>> {
+        let path = self.maker_state_dir(id)?.join(name);
+        if !path.exists() {
+            return Ok(None);
+        }
+        fs::read_to_string(&path)
+            .map(Some)
+            .with_context(|| format!("Failed to read state file: {}", path.display()))
+    }
+
+    /// Deletes all dashboard-side state stored for a maker
+    pub fn remove_maker_state(&self, id: &str) -> Result<()> {
+        let dir = self.maker_state_dir(id)?;
+        if !dir.exists() {
+            return Ok(());
+        }
+        // Never follow a link out of makers/
+        let root = self.config_dir.join("makers").canonicalize()?;
+        let resolved = dir
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve maker state dir: {}", dir.display()))?;
+        if resolved.parent() != Some(root.as_path()) {
+            bail!(
+                "Refusing to remove {}: it is not inside {}",
+                resolved.display(),
+                root.display()
+            );
+        }
+        fs::remove_dir_all(&resolved)
+            .with_context(|| format!("Failed to remove maker state dir: {}", dir.display()))
+    }
+
     /// Loads all maker configs from disk. Returns empty map if file doesn't exist.
     pub fn load(&self) -> Result<HashMap<MakerId, MakerConfig>> {
         let path = self.state_file();
@@ -209,3 +316,29 @@ impl PersistenceManager {
             .collect())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::PersistenceManager;
+
+    #[test]
+    fn maker_state_stays_inside_makers_dir() {
+        let dir = std::env::temp_dir().join(format!("maker-persistence-{}", std::process::id()));
+        let persistence = PersistenceManager::new(dir.clone()).unwrap();
+        std::fs::create_dir_all(dir.join("keep")).unwrap();
+
+        for id in ["..", "../keep", "a/b", ""] {
+            assert!(persistence.remove_maker_state(id).is_err(), "{id}");
+            assert!(
+                persistence.save_maker_file(id, "x.json", "{}").is_err(),
+                "{id}"
+            );
+        }
+        assert!(dir.join("keep").exists());
+
+        persistence.save_maker_file("m-1", "x.json", "{}").unwrap();
+        persistence.remove_maker_state("m-1").unwrap();
+        assert!(!dir.join("makers").join("m-1").exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,228 @@
+//! Automatic fidelity bond renewal.
+//!
+//! A maker loses its advertised weight once its fidelity bond expires. The renewal policy
+//! lets the dashboard lock a fresh bond shortly before that happens and, optionally, redeem
+//! expired bonds so their funds go back into the wallet for the next one.
+
+use coinswap::bitcoin::Txid;
+use serde::{Deserialize, Serialize};
+
+use super::message::FidelityBondSummary;
+
+/// Number of audit records kept per maker.
+pub const RENEWAL_LOG_LIMIT: usize = 500;
+
+/// Per-maker fidelity bond renewal settings.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RenewalPolicy {
+    pub enabled: bool,
+    /// Renew once every live bond is within this many blocks of expiry.
+    pub blocks_before_expiry: u32,
+    /// Redeem expired bonds so their funds are available for the renewal.
+    pub reuse_redeemed_funds: bool,
+    /// Only record the actions that would be taken.
+    pub dry_run: bool,
+    /// Feerate for renewal transactions in sat/vB; estimated when unset.
+    pub feerate: Option<f64>,
+}
+
+impl Default for RenewalPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            blocks_before_expiry: 144,
+            reuse_redeemed_funds: true,
+            dry_run: false,
+            feerate: None,
+        }
+    }
+}
+
+/// A single step of a renewal.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenewalStep {
+    /// Spend an expired bond back into the wallet.
+    Redeem { index: u32 },
+    /// Lock a new bond.
+    Create { amount: u64, locktime: u32 },
+}
+
+/// Audit record of an automatic renewal step.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RenewalRecord {
+    /// Unix timestamp of the evaluation
+    pub at: u64,
+    /// Chain tip the step was planned at
+    pub height: u32,
+    pub step: RenewalStep,
+    pub dry_run: bool,
+    /// Feerate in sat/vB the step was, or in a dry run would have been, broadcast at
+    #[serde(default)]
+    pub feerate: Option<f64>,
+    /// Index of the redeemed or created bond
+    pub bond_index: Option<u32>,
+    pub txid: Option<Txid>,
+    pub error: Option<String>,
+}
+
+impl RenewalRecord {
+    /// Whether `self` records the same outcome as `previous`, so re-evaluating an unchanged
+    /// situation on every block doesn't flood the log with dry runs and repeated failures.
+    pub fn repeats(&self, previous: &RenewalRecord) -> bool {
+        let same_step = match (self.step, previous.step) {
+            (RenewalStep::Redeem { index: a }, RenewalStep::Redeem { index: b }) => a == b,
+            // The planned locktime moves with the tip
+            (RenewalStep::Create { amount: a, .. }, RenewalStep::Create { amount: b, .. }) => {
+                a == b
+            }
+            _ => false,
+        };
+        same_step
+            && self.txid.is_none()
+            && self.dry_run == previous.dry_run
+            && self.error == previous.error
+    }
+}
+
+/// Plans the renewal steps for a maker's bonds at chain tip `tip`.
+///
+/// Only makers that already hold a bond are renewed. Expired bonds are redeemed first when
+/// `reuse_redeemed_funds` is set; a new bond of `amount` locked for `timelock` blocks is
+/// planned once no unspent bond outlives the renewal window.
+pub fn plan(
+    policy: &RenewalPolicy,
+    bonds: &[FidelityBondSummary],
+    tip: u32,
+    amount: u64,
+    timelock: u32,
+) -> Vec<RenewalStep> {
+    // A window as long as the timelock would put every new bond inside it straight away
+    if !policy.enabled || bonds.is_empty() || policy.blocks_before_expiry >= timelock {
+        return Vec::new();
+    }
+    let live: Vec<_> = bonds.iter().filter(|bond| !bond.spent).collect();
+    let mut steps = Vec::new();
+    if policy.reuse_redeemed_funds {
+        steps.extend(
+            live.iter()
+                .filter(|bond| bond.locktime <= tip)
+                .map(|bond| RenewalStep::Redeem { index: bond.index }),
+        );
+    }
+    let renewal_height = tip.saturating_add(policy.blocks_before_expiry);
+    if live.iter().all(|bond| bond.locktime <= renewal_height) {
+        steps.push(RenewalStep::Create {
+            amount,
+            locktime: tip.saturating_add(timelock),
+        });
+    }
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use coinswap::bitcoin::{Amount, OutPoint};
+
+    use super::{plan, RenewalPolicy, RenewalRecord, RenewalStep};
+    use crate::maker_manager::message::FidelityBondSummary;
+
+    fn bond(index: u32, locktime: u32, spent: bool) -> FidelityBondSummary {
+        FidelityBondSummary {
+            index,
+            outpoint: OutPoint::from_str(
+                "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:0",
+            )
+            .unwrap(),
+            amount: Amount::from_sat(50_000),
+            locktime,
+            conf_height: Some(100),
+            cert_expiry: None,
+            spent,
+            value: None,
+            advertised: !spent,
+        }
+    }
+
+    fn policy() -> RenewalPolicy {
+        RenewalPolicy {
+            enabled: true,
+            blocks_before_expiry: 100,
+            ..RenewalPolicy::default()
+        }
+    }
+
+    #[test]
+    fn renews_inside_the_window_only() {
+        let bonds = [bond(0, 20_000, false)];
+        assert!(plan(&policy(), &bonds, 19_000, 50_000, 15_000).is_empty());
+        assert_eq!(
+            plan(&policy(), &bonds, 19_950, 50_000, 15_000),
+            vec![RenewalStep::Create {
+                amount: 50_000,
+                locktime: 34_950
+            }]
+        );
+        // Nothing to do once a renewed bond outlives the window
+        let renewed = [bond(0, 20_000, false), bond(1, 34_950, false)];
+        assert!(plan(&policy(), &renewed, 19_960, 50_000, 15_000).is_empty());
+    }
+
+    #[test]
+    fn redeems_expired_bonds_before_renewing() {
+        let bonds = [bond(0, 10_000, true), bond(1, 20_000, false)];
+        assert_eq!(
+            plan(&policy(), &bonds, 20_000, 50_000, 15_000),
+            vec![
+                RenewalStep::Redeem { index: 1 },
+                RenewalStep::Create {
+                    amount: 50_000,
+                    locktime: 35_000
+                }
+            ]
+        );
+        let keep_locked = RenewalPolicy {
+            reuse_redeemed_funds: false,
+            ..policy()
+        };
+        assert_eq!(
+            plan(&keep_locked, &bonds, 20_000, 50_000, 15_000),
+            vec![RenewalStep::Create {
+                amount: 50_000,
+                locktime: 35_000
+            }]
+        );
+    }
+
+    #[test]
+    fn disabled_bondless_or_misconfigured_makers_are_left_alone() {
+        let bonds = [bond(0, 20_000, false)];
+        let disabled = RenewalPolicy {
+            enabled: false,
+            ..policy()
+        };
+        assert!(plan(&disabled, &bonds, 20_000, 50_000, 15_000).is_empty());
+        assert!(plan(&policy(), &[], 20_000, 50_000, 15_000).is_empty());
+        assert!(plan(&policy(), &bonds, 20_000, 50_000, 100).is_empty());
+    }
+
+    #[test]
+    fn repeated_outcomes_are_detected() {
+        let record = |height, locktime, error: Option<&str>| RenewalRecord {
+            at: 0,
+            height,
+            step: RenewalStep::Create {
+                amount: 50_000,
+                locktime,
+            },
+            dry_run: true,
+            feerate: Some(2.0),
+            bond_index: None,
+            txid: None,
+            error: error.map(str::to_string),
+        };
+        assert!(record(11, 15_011, None).repeats(&record(10, 15_010, None)));
+        assert!(!record(11, 15_011, Some("no funds")).repeats(&record(10, 15_010, None)));
+    }
+}
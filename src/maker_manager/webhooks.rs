@@ -0,0 +1,446 @@
+//! Outgoing webhooks.
+//!
+//! A webhook posts the events it subscribes to as JSON to its URL, signed with an
+//! HMAC-SHA256 of the timestamp and body when it has a secret. Failed deliveries are retried
+//! with exponential backoff, and every attempt is kept in a bounded delivery log. Retries are
+//! held in memory only: those still pending when the dashboard stops are dropped, and the
+//! log shows the last attempt that was made. Webhooks and the log are stored in the
+//! dashboard's config dir.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use coinswap::bitcoin::hashes::{hmac, sha256, Hash, HashEngine};
+use serde::{Deserialize, Serialize};
+
+use super::events::EventKind;
+use super::persistence::PersistenceManager;
+
+/// Number of delivery attempts kept in the log.
+pub const DELIVERY_LOG_LIMIT: usize = 500;
+
+/// Attempts per event before a delivery is given up.
+pub const MAX_ATTEMPTS: u32 = 5;
+
+/// Event types delivered by webhooks without an event filter.
+pub const DEFAULT_EVENTS: [&str; 8] = [
+    "maker_crashed",
+    "swap_completed",
+    "swap_failed",
+    "fidelity_expiring",
+    "low_balance",
+    "bitcoind_down",
+    "alert_firing",
+    "alert_resolved",
+];
+
+/// A configured webhook.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Webhook {
+    pub id: u64,
+    pub url: String,
+    /// Key the payloads are signed with; unsigned when unset
+    pub secret: Option<String>,
+    /// Event types to deliver; [`DEFAULT_EVENTS`] when empty
+    #[serde(default)]
+    pub events: Vec<String>,
+    pub enabled: bool,
+}
+
+impl Webhook {
+    /// Whether events of `event_type` are delivered to this webhook.
+    pub fn wants(&self, event_type: &str) -> bool {
+        self.enabled
+            && if self.events.is_empty() {
+                DEFAULT_EVENTS.contains(&event_type)
+            } else {
+                self.events.iter().any(|e| e == event_type)
+            }
+    }
+}
+
+/// One delivery attempt.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Delivery {
+    pub id: u64,
+    pub webhook_id: u64,
+    /// Event delivered; `None` for test deliveries
+    pub event_id: Option<u64>,
+    pub event_type: String,
+    /// 1 for the first attempt at an event
+    pub attempt: u32,
+    /// Unix timestamp of the attempt
+    pub at: u64,
+    /// HTTP status of the response, if one was received
+    pub status: Option<u16>,
+    /// Why the attempt failed; `None` if it succeeded
+    pub error: Option<String>,
+}
+
+/// Signature header value for `body` sent at `timestamp`: `sha256=` followed by the hex
+/// HMAC-SHA256 of `{timestamp}.{body}` keyed with `secret`. Covering the timestamp lets
+/// receivers reject replayed deliveries.
+pub fn signature(secret: &str, timestamp: u64, body: &[u8]) -> String {
+    let mut engine = hmac::HmacEngine::<sha256::Hash>::new(secret.as_bytes());
+    engine.input(format!("{timestamp}.").as_bytes());
+    engine.input(body);
+    format!("sha256={}", hmac::Hmac::<sha256::Hash>::from_engine(engine))
+}
+
+/// Delay before retrying after failed attempt number `attempt`: `base`, doubling with
+/// each further attempt.
+pub fn backoff(attempt: u32, base: Duration) -> Duration {
+    base.saturating_mul(1 << attempt.clamp(1, 16).saturating_sub(1))
+}
+
+/// Checks that every entry of an event filter is a known event type.
+pub fn validate_events(events: &[String]) -> Result<(), String> {
+    match events
+        .iter()
+        .find(|e| !EventKind::NAMES.contains(&e.as_str()))
+    {
+        Some(unknown) => Err(format!(
+            "Unknown event type '{unknown}'; expected one of: {}",
+            EventKind::NAMES.join(", ")
+        )),
+        None => Ok(()),
+    }
+}
+
+#[derive(Serialize, Deserialize, Default, Clone)]
+struct StoredWebhooks {
+    next_id: u64,
+    webhooks: Vec<Webhook>,
+}
+
+/// A change to the delivery log file.
+enum LogWrite {
+    Append(Delivery),
+    /// Drops a removed webhook's deliveries
+    Forget(u64),
+}
+
+/// Writes the delivery log on its own thread, in the order changes are sent, so attempts
+/// are logged without blocking the manager. The file is JSON Lines: attempts are appended,
+/// and it is rewritten with the newest [`DELIVERY_LOG_LIMIT`] once it has grown to twice
+/// that. Pending writes are flushed when the writer is dropped.
+struct LogWriter {
+    sender: Option<mpsc::Sender<LogWrite>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl LogWriter {
+    fn spawn(path: PathBuf, mut log: Vec<Delivery>, mut lines: usize) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let thread = std::thread::spawn(move || {
+            while let Ok(first) = receiver.recv() {
+                let mut appended = Vec::new();
+                let mut rewrite = false;
+                for write in std::iter::once(first).chain(receiver.try_iter()) {
+                    match write {
+                        LogWrite::Append(delivery) => {
+                            log.push(delivery.clone());
+                            appended.push(delivery);
+                        }
+                        LogWrite::Forget(webhook_id) => {
+                            log.retain(|d| d.webhook_id != webhook_id);
+                            rewrite = true;
+                        }
+                    }
+                }
+                if log.len() > DELIVERY_LOG_LIMIT {
+                    log.drain(..log.len() - DELIVERY_LOG_LIMIT);
+                }
+                lines += appended.len();
+                let written = if rewrite || lines >= 2 * DELIVERY_LOG_LIMIT {
+                    lines = log.len();
+                    Self::rewrite(&path, &log)
+                } else {
+                    Self::append(&path, &appended)
+                };
+                if let Err(e) = written {
+                    tracing::warn!("Failed to save webhook delivery log: {}", e);
+                }
+            }
+        });
+        Self {
+            sender: Some(sender),
+            thread: Some(thread),
+        }
+    }
+
+    fn send(&self, write: LogWrite) {
+        if let Some(sender) = &self.sender {
+            // Only fails if the writer thread panicked; the attempt stays in memory
+            let _ = sender.send(write);
+        }
+    }
+
+    fn to_jsonl(deliveries: &[Delivery]) -> Result<String> {
+        deliveries.iter().try_fold(String::new(), |mut out, d| {
+            out.push_str(&serde_json::to_string(d).context("Failed to serialize delivery")?);
+            out.push('\n');
+            Ok(out)
+        })
+    }
+
+    fn append(path: &Path, deliveries: &[Delivery]) -> Result<()> {
+        let jsonl = Self::to_jsonl(deliveries)?;
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(jsonl.as_bytes()))
+            .with_context(|| format!("Failed to write state file: {}", path.display()))
+    }
+
+    fn rewrite(path: &Path, deliveries: &[Delivery]) -> Result<()> {
+        let tmp = path.with_extension("jsonl.tmp");
+        fs::write(&tmp, Self::to_jsonl(deliveries)?)
+            .and_then(|()| fs::rename(&tmp, path))
+            .with_context(|| format!("Failed to write state file: {}", path.display()))
+    }
+}
+
+impl Drop for LogWriter {
+    fn drop(&mut self) {
+        self.sender.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// The dashboard's webhooks and their delivery log.
+pub struct WebhookStore {
+    stored: StoredWebhooks,
+    /// Oldest first
+    deliveries: Vec<Delivery>,
+    next_delivery: u64,
+    log: LogWriter,
+}
+
+impl WebhookStore {
+    const FILE: &'static str = "webhooks.json";
+    const LOG_FILE: &'static str = "webhook_deliveries.jsonl";
+
+    /// Loads the webhooks stored in the config dir, starting empty if there are none.
+    pub fn load(persistence: &PersistenceManager) -> Self {
+        let stored = persistence.load_state(Self::FILE).unwrap_or_else(|e| {
+            tracing::warn!("Failed to load webhooks: {}", e);
+            StoredWebhooks::default()
+        });
+        let log_path = persistence.config_dir.join(Self::LOG_FILE);
+        let (deliveries, lines) = Self::read_log(&log_path).unwrap_or_else(|e| {
+            tracing::warn!("Failed to load webhook delivery log: {}", e);
+            (Vec::new(), 0)
+        });
+        let next_delivery = deliveries.last().map_or(1, |last: &Delivery| last.id + 1);
+        let log = LogWriter::spawn(log_path, deliveries.clone(), lines);
+        Self {
+            stored,
+            deliveries,
+            next_delivery,
+            log,
+        }
+    }
+
+    /// Reads the newest [`DELIVERY_LOG_LIMIT`] attempts of the delivery log, and the number
+    /// of lines in the file.
+    fn read_log(path: &Path) -> Result<(Vec<Delivery>, usize)> {
+        if !path.exists() {
+            return Ok((Vec::new(), 0));
+        }
+        let jsonl = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read state file: {}", path.display()))?;
+        let lines: Vec<&str> = jsonl.lines().filter(|l| !l.trim().is_empty()).collect();
+        let deliveries = lines[lines.len().saturating_sub(DELIVERY_LOG_LIMIT)..]
+            .iter()
+            .map(|line| serde_json::from_str(line))
+            .collect::<Result<_, _>>()
+            .with_context(|| format!("Failed to parse state file: {}", path.display()))?;
+        Ok((deliveries, lines.len()))
+    }
+
+    pub fn list(&self) -> &[Webhook] {
+        &self.stored.webhooks
+    }
+
+    pub fn get(&self, id: u64) -> Option<&Webhook> {
+        self.stored.webhooks.iter().find(|w| w.id == id)
+    }
+
+    /// Adds and saves a webhook, assigning its ID.
+    pub fn add(
+        &mut self,
+        persistence: &PersistenceManager,
+        mut webhook: Webhook,
+    ) -> Result<Webhook> {
+        let mut stored = self.stored.clone();
+        webhook.id = stored.next_id.max(1);
+        stored.next_id = webhook.id + 1;
+        stored.webhooks.push(webhook.clone());
+        persistence.save_state(Self::FILE, &stored)?;
+        self.stored = stored;
+        Ok(webhook)
+    }
+
+    /// Replaces and saves the webhook with the same ID. Returns `false` if there is none.
+    pub fn update(&mut self, persistence: &PersistenceManager, webhook: Webhook) -> Result<bool> {
+        let mut stored = self.stored.clone();
+        let Some(existing) = stored.webhooks.iter_mut().find(|w| w.id == webhook.id) else {
+            return Ok(false);
+        };
+        *existing = webhook;
+        persistence.save_state(Self::FILE, &stored)?;
+        self.stored = stored;
+        Ok(true)
+    }
+
+    /// Removes a webhook and its deliveries. Returns `false` if there was no such webhook.
+    pub fn remove(&mut self, persistence: &PersistenceManager, id: u64) -> Result<bool> {
+        let mut stored = self.stored.clone();
+        stored.webhooks.retain(|w| w.id != id);
+        if stored.webhooks.len() == self.stored.webhooks.len() {
+            return Ok(false);
+        }
+        persistence.save_state(Self::FILE, &stored)?;
+        self.stored = stored;
+        self.deliveries.retain(|d| d.webhook_id != id);
+        self.log.send(LogWrite::Forget(id));
+        Ok(true)
+    }
+
+    /// Appends a delivery attempt to the log, assigning its ID, and returns it. The log
+    /// file is written in the background.
+    pub fn record(&mut self, mut delivery: Delivery) -> Delivery {
+        delivery.id = self.next_delivery;
+        self.next_delivery += 1;
+        self.deliveries.push(delivery.clone());
+        if self.deliveries.len() > DELIVERY_LOG_LIMIT {
+            self.deliveries
+                .drain(..self.deliveries.len() - DELIVERY_LOG_LIMIT);
+        }
+        self.log.send(LogWrite::Append(delivery.clone()));
+        delivery
+    }
+
+    /// A webhook's delivery attempts, newest first.
+    pub fn deliveries(&self, webhook_id: u64) -> Vec<Delivery> {
+        self.deliveries
+            .iter()
+            .rev()
+            .filter(|d| d.webhook_id == webhook_id)
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{backoff, signature, validate_events, Delivery, Webhook, WebhookStore};
+    use crate::maker_manager::persistence::PersistenceManager;
+
+    fn webhook(events: &[&str]) -> Webhook {
+        Webhook {
+            id: 0,
+            url: "http://127.0.0.1:9/hook".into(),
+            secret: None,
+            events: events.iter().map(|e| e.to_string()).collect(),
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn signature_is_hex_hmac_sha256_of_timestamp_and_body() {
+        assert_eq!(
+            signature("Jefe", 1_700_000_000, b"what do ya want for nothing?"),
+            "sha256=1cdd0650c8be1cb0974b1788d458b1e781206cfef59b85faafc582d2e182c57e"
+        );
+        assert_ne!(
+            signature("Jefe", 1_700_000_001, b"what do ya want for nothing?"),
+            signature("Jefe", 1_700_000_000, b"what do ya want for nothing?")
+        );
+    }
+
+    #[test]
+    fn backoff_doubles_per_attempt() {
+        let base = Duration::from_secs(10);
+        assert_eq!(backoff(1, base), base);
+        assert_eq!(backoff(2, base), base * 2);
+        assert_eq!(backoff(4, base), base * 8);
+    }
+
+    #[test]
+    fn event_filters() {
+        let all_defaults = webhook(&[]);
+        assert!(all_defaults.wants("maker_crashed"));
+        assert!(!all_defaults.wants("new_block"));
+        let blocks = webhook(&["new_block"]);
+        assert!(blocks.wants("new_block"));
+        assert!(!blocks.wants("maker_crashed"));
+        let disabled = Webhook {
+            enabled: false,
+            ..all_defaults
+        };
+        assert!(!disabled.wants("maker_crashed"));
+
+        assert!(validate_events(&["swap_failed".into()]).is_ok());
+        assert!(validate_events(&["swap_exploded".into()]).is_err());
+    }
+
+    #[test]
+    fn webhooks_and_deliveries_persist() {
+        let dir = std::env::temp_dir().join(format!("webhook-store-{}", std::process::id()));
+        let persistence = PersistenceManager::new(dir.clone()).unwrap();
+        let mut store = WebhookStore::load(&persistence);
+        let first = store.add(&persistence, webhook(&[])).unwrap();
+        let second = store.add(&persistence, webhook(&["new_block"])).unwrap();
+        assert_eq!((first.id, second.id), (1, 2));
+        let delivery = Delivery {
+            id: 0,
+            webhook_id: second.id,
+            event_id: Some(7),
+            event_type: "new_block".into(),
+            attempt: 1,
+            at: 1_700_000_000,
+            status: Some(500),
+            error: Some("HTTP 500".into()),
+        };
+        assert_eq!(store.record(delivery.clone()).id, 1);
+        assert_eq!(store.record(delivery.clone()).id, 2);
+        drop(store);
+
+        let mut store = WebhookStore::load(&persistence);
+        assert_eq!(store.list().len(), 2);
+        assert_eq!(store.deliveries(second.id).len(), 2);
+        assert_eq!(store.record(delivery).id, 3);
+        assert!(store.remove(&persistence, second.id).unwrap());
+        assert!(!store.remove(&persistence, second.id).unwrap());
+        assert_eq!(store.add(&persistence, webhook(&[])).unwrap().id, 3);
+        assert!(store.deliveries(second.id).is_empty());
+        drop(store);
+
+        let store = WebhookStore::load(&persistence);
+        assert!(store.deliveries(second.id).is_empty());
+        assert_eq!(store.list().len(), 2);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn failed_save_leaves_webhooks_unchanged() {
+        let dir = std::env::temp_dir().join(format!("webhook-readonly-{}", std::process::id()));
+        let persistence = PersistenceManager::new(dir.clone()).unwrap();
+        let mut store = WebhookStore::load(&persistence);
+        std::fs::remove_dir_all(dir).unwrap();
+        assert!(store.add(&persistence, webhook(&[])).is_err());
+        assert!(store.list().is_empty());
+    }
+}
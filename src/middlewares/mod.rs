@@ -1,5 +1,5 @@
 use axum::{
-    extract::{ConnectInfo, Request},
+    extract::{ConnectInfo, MatchedPath, Request},
     http::StatusCode,
     middleware::Next,
     response::{IntoResponse, Response},
@@ -7,6 +7,9 @@ use axum::{
 };
 use serde_json::json;
 use std::net::SocketAddr;
+use std::time::Instant;
+
+use crate::api::metrics;
 
 /// Middleware that checks if the client IP is a loopback address
 pub async fn restrict_to_localhost(
@@ -28,3 +31,25 @@ pub async fn restrict_to_localhost(
         (StatusCode::FORBIDDEN, body).into_response()
     }
 }
+
+/// Middleware that records request counts and latencies for `GET /metrics`.
+///
+/// Requests are labelled with the matched route template so path parameters such as maker
+/// IDs don't create a series per value.
+pub async fn track_http_metrics(request: Request, next: Next) -> Response {
+    let method = request.method().clone();
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let started = Instant::now();
+    let response = next.run(request).await;
+    metrics::record_http_request(
+        method.as_str(),
+        &path,
+        response.status().as_u16(),
+        started.elapsed(),
+    );
+    response
+}
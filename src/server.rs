@@ -1,6 +1,7 @@
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use axum::{middleware::from_fn, Router};
 use tokio::sync::Mutex;
@@ -10,8 +11,13 @@ use utoipa::OpenApi;
 use utoipa_axum::router::OpenApiRouter;
 use utoipa_swagger_ui::SwaggerUi;
 
-use crate::api::{api_router, ApiDoc, AppState};
-use crate::maker_manager::MakerManager;
+use crate::api::{
+    alerts, api_router, events, history, metrics, renewal, webhooks, ApiDoc, AppState,
+};
+use crate::maker_manager::{
+    alerts::{EventNotifier, LogNotifier},
+    MakerManager,
+};
 use crate::middlewares;
 use crate::utils::default_config_dir;
 
@@ -44,10 +50,14 @@ impl Default for ServerConfig {
     }
 }
 
+/// How often maker balances and status are recorded into the history store, by default
+pub const DEFAULT_HISTORY_INTERVAL: Duration = Duration::from_secs(60);
+
 /// The application server
 pub struct Server {
     config: ServerConfig,
     state: AppState,
+    history_interval: Duration,
 }
 
 impl Server {
@@ -56,7 +66,17 @@ impl Server {
     pub fn new(config: ServerConfig) -> anyhow::Result<Self> {
         let manager = MakerManager::new(config.config_dir.clone())?;
         let state: AppState = Arc::new(Mutex::new(manager));
-        Ok(Self { config, state })
+        Ok(Self {
+            config,
+            state,
+            history_interval: DEFAULT_HISTORY_INTERVAL,
+        })
+    }
+
+    /// Sets how often maker balances and status are recorded into the history store.
+    pub fn with_history_interval(mut self, interval: Duration) -> Self {
+        self.history_interval = interval;
+        self
     }
 
     /// Returns the socket address the server will bind to
@@ -75,6 +95,8 @@ impl Server {
 
         let mut app = router
             .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", api))
+            .merge(metrics::routes())
+            .route_layer(from_fn(middlewares::track_http_metrics))
             .with_state(self.state.clone())
             .layer(TraceLayer::new_for_http());
 
@@ -102,6 +124,16 @@ impl Server {
             anyhow::anyhow!("Failed to bind to {addr}. Is the port already in use? {e}")
         })?;
 
+        renewal::spawn_watcher(self.state.clone());
+        events::spawn_watcher(self.state.clone());
+        webhooks::spawn_dispatcher(self.state.clone());
+        let bus = self.state.lock().await.events();
+        alerts::spawn_evaluator(
+            self.state.clone(),
+            vec![Box::new(LogNotifier), Box::new(EventNotifier(bus))],
+        );
+        history::spawn_sampler(self.state.clone(), self.history_interval);
+
         axum::serve(
             listener,
             app.into_make_service_with_connect_info::<SocketAddr>(),
@@ -0,0 +1,122 @@
+//! Tests for the alert endpoints (`/alerts`, `/alerts/rules`).
+
+use axum::http::StatusCode;
+use serde_json::json;
+
+use super::{delete, get, post, put, test_app};
+
+// 200 / success-path
+
+#[tokio::test]
+async fn create_list_update_and_delete_rule() {
+    let app = test_app();
+    let (status, body) = post(
+        app.clone(),
+        "/alerts/rules",
+        json!({ "name": "Low balance", "condition": { "kind": "low_balance", "sats": 100000 } }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let id = body["data"]["id"].as_u64().unwrap();
+    assert_eq!(body["data"]["enabled"], true);
+    assert_eq!(body["data"]["maker"], json!(null));
+
+    let (status, body) = get(app.clone(), "/alerts/rules").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"][0]["condition"]["kind"], "low_balance");
+    assert_eq!(body["data"][0]["condition"]["sats"], 100000);
+
+    let (status, body) = put(
+        app.clone(),
+        &format!("/alerts/rules/{id}"),
+        json!({
+            "name": "Node behind",
+            "condition": { "kind": "node_sync_below", "progress": 0.999 },
+            "enabled": false
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["id"], id);
+    assert_eq!(body["data"]["condition"]["kind"], "node_sync_below");
+    assert_eq!(body["data"]["enabled"], false);
+
+    let (status, body) = delete(app.clone(), &format!("/alerts/rules/{id}")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["success"], true);
+    let (_, body) = get(app, "/alerts/rules").await;
+    assert_eq!(body["data"], json!([]));
+}
+
+#[tokio::test]
+async fn no_alerts_without_evaluation() {
+    let app = test_app();
+    let (status, body) = get(app.clone(), "/alerts").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"], json!([]));
+    let (status, body) = get(app, "/alerts?state=firing&maker=m1").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"], json!([]));
+}
+
+// 4xx
+
+#[tokio::test]
+async fn invalid_rules_are_rejected() {
+    let app = test_app();
+    for (name, condition) in [
+        ("", json!({ "kind": "maker_down", "minutes": 5 })),
+        ("Idle", json!({ "kind": "no_successful_swap", "hours": 0 })),
+        (
+            "Sync",
+            json!({ "kind": "node_sync_below", "progress": 99.9 }),
+        ),
+    ] {
+        let (status, body) = post(
+            app.clone(),
+            "/alerts/rules",
+            json!({ "name": name, "condition": condition }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{condition}");
+        assert_eq!(body["success"], false);
+    }
+    let (status, _) = post(
+        app,
+        "/alerts/rules",
+        json!({ "name": "Disk", "condition": { "kind": "disk_full" } }),
+    )
+    .await;
+    assert!(status.is_client_error());
+}
+
+#[tokio::test]
+async fn rule_for_unknown_maker_is_404() {
+    let (status, body) = post(
+        test_app(),
+        "/alerts/rules",
+        json!({
+            "name": "Down",
+            "condition": { "kind": "maker_down", "minutes": 5 },
+            "maker": "ghost"
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["error"], "Maker 'ghost' not found");
+}
+
+#[tokio::test]
+async fn unknown_rule_is_404() {
+    let app = test_app();
+    let (status, _) = put(
+        app.clone(),
+        "/alerts/rules/42",
+        json!({ "name": "Down", "condition": { "kind": "maker_down", "minutes": 5 } }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, body) = delete(app, "/alerts/rules/42").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["error"], "Alert rule 42 not found");
+}
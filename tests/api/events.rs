@@ -0,0 +1,30 @@
+//! Tests for the event stream endpoint (`/events`).
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use tower::ServiceExt;
+
+use super::{get, test_app};
+
+// 200 / success-path
+
+#[tokio::test]
+async fn events_stream_as_server_sent_events() {
+    let req = Request::get("/events?since=0").body(Body::empty()).unwrap();
+    let resp = test_app().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let content_type = resp.headers()[header::CONTENT_TYPE].to_str().unwrap();
+    assert!(content_type.starts_with("text/event-stream"));
+}
+
+// 4xx
+
+#[tokio::test]
+async fn events_for_unknown_maker_is_404() {
+    let (status, body) = get(test_app(), "/events?maker=ghost").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["success"], false);
+    assert!(body["error"].as_str().unwrap().contains("ghost"));
+}
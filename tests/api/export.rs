@@ -0,0 +1,72 @@
+//! Tests for the CSV export endpoints (`/export/swap-reports`, `/export/transactions`).
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+use super::{get, test_app};
+
+/// GET request → (status, content type, body text).
+async fn get_text(uri: &str) -> (StatusCode, String, String) {
+    let req = Request::get(uri).body(Body::empty()).unwrap();
+    let resp = test_app().oneshot(req).await.unwrap();
+    let status = resp.status();
+    let content_type = resp.headers()[header::CONTENT_TYPE]
+        .to_str()
+        .unwrap()
+        .to_string();
+    let bytes = resp.into_body().collect().await.unwrap().to_bytes();
+    (
+        status,
+        content_type,
+        String::from_utf8(bytes.to_vec()).unwrap(),
+    )
+}
+
+// 200 / success-path
+
+#[tokio::test]
+async fn swap_report_export_with_no_makers_is_header_only() {
+    let (status, content_type, body) = get_text("/export/swap-reports").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(content_type.starts_with("text/csv"));
+    assert!(body.starts_with("maker,swap_id,role,status,"));
+    assert_eq!(body.lines().count(), 1);
+}
+
+#[tokio::test]
+async fn accounting_exports_use_the_import_headers() {
+    let (_, _, koinly) = get_text("/export/transactions?format=koinly").await;
+    assert!(koinly.starts_with("Date,Sent Amount,Sent Currency,Received Amount,"));
+    let (_, _, cointracking) = get_text("/export/swap-reports?format=cointracking").await;
+    assert!(cointracking.starts_with("Type,Buy Amount,Buy Currency,Sell Amount,"));
+}
+
+// 4xx
+
+#[tokio::test]
+async fn export_unknown_maker_is_404() {
+    for uri in [
+        "/export/swap-reports?maker=unknown",
+        "/export/transactions?maker=unknown",
+    ] {
+        let (status, body) = get(test_app(), uri).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert!(!body["success"].as_bool().unwrap_or(true));
+    }
+}
+
+#[tokio::test]
+async fn export_rejects_inverted_period() {
+    let (status, _) = get(test_app(), "/export/swap-reports?from=200&to=100").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn export_rejects_unknown_format() {
+    let (status, _) = get(test_app(), "/export/transactions?format=xlsx").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
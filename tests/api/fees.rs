@@ -0,0 +1,19 @@
+//! Unit tests for fee estimation endpoints.
+
+use axum::http::StatusCode;
+
+use super::{get, test_app};
+
+#[tokio::test]
+async fn fees_without_makers_is_503() {
+    let (status, body) = get(test_app(), "/fees").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert!(!body["success"].as_bool().unwrap_or(true));
+}
+
+#[tokio::test]
+async fn fees_for_unknown_maker_is_404() {
+    let (status, body) = get(test_app(), "/fees?maker=unknown").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["error"], "Maker 'unknown' not found");
+}
@@ -1,8 +1,14 @@
 //! Unit tests for fidelity bond endpoints.
 
 use axum::http::StatusCode;
+use serde_json::json;
 
-use super::{get, test_app};
+use maker_dashboard::{
+    api::api_router,
+    maker_manager::renewal::{RenewalRecord, RenewalStep},
+};
+
+use super::{get, post, put, seeded_app, seeded_state, test_app};
 
 #[tokio::test]
 async fn list_fidelity_unknown_maker_is_404() {
@@ -10,3 +16,224 @@ async fn list_fidelity_unknown_maker_is_404() {
     assert_eq!(status, StatusCode::NOT_FOUND);
     assert!(!body["success"].as_bool().unwrap_or(true));
 }
+
+#[tokio::test]
+async fn create_fidelity_validates_before_asking_the_node() {
+    let (app, _) = seeded_app();
+    let (status, body) = post(
+        app.clone(),
+        "/makers/m1/fidelity",
+        json!({ "amount": 0, "timelock": 150, "feerate": 2.0 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], json!("amount must be greater than 0"));
+
+    let (status, body) = post(
+        app.clone(),
+        "/makers/m1/fidelity",
+        json!({ "amount": 50000, "timelock": 0, "feerate": 2.0 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], json!("timelock must be at least 1 block"));
+
+    // The seeded maker's node is unreachable
+    let (status, body) = post(
+        app,
+        "/makers/m1/fidelity",
+        json!({ "amount": 50000, "timelock": 150, "feerate": 2.0, "dry_run": true }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert!(body["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("Bitcoin Core unreachable"));
+}
+
+#[tokio::test]
+async fn redeem_fidelity_needs_the_chain_tip() {
+    let (app, _) = seeded_app();
+    let (status, body) = post(
+        app,
+        "/makers/m1/fidelity/0/redeem",
+        json!({ "feerate": 2.0 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert!(body["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("Bitcoin Core unreachable"));
+}
+
+#[tokio::test]
+async fn renewal_policy_is_saved_and_read_back() {
+    let (app, _) = seeded_app();
+    let (status, body) = get(app.clone(), "/makers/m1/fidelity/renewal").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["data"],
+        json!({
+            "enabled": false,
+            "blocks_before_expiry": 144,
+            "reuse_redeemed_funds": true,
+            "dry_run": false,
+            "feerate": null
+        })
+    );
+
+    let policy = json!({
+        "enabled": true,
+        "blocks_before_expiry": 288,
+        "reuse_redeemed_funds": false,
+        "dry_run": true,
+        "feerate": 3.5
+    });
+    let (status, body) = put(app.clone(), "/makers/m1/fidelity/renewal", policy.clone()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"], policy);
+
+    let (_, body) = get(app, "/makers/m1/fidelity/renewal").await;
+    assert_eq!(body["data"], policy);
+}
+
+#[tokio::test]
+async fn renewal_window_must_be_shorter_than_the_timelock() {
+    let (app, _) = seeded_app();
+    let (status, body) = put(
+        app.clone(),
+        "/makers/m1/fidelity/renewal",
+        json!({
+            "enabled": true,
+            "blocks_before_expiry": 15000,
+            "reuse_redeemed_funds": true,
+            "dry_run": false
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        body["error"],
+        json!("blocks_before_expiry must be between 1 and 14999 (the maker's fidelity_timelock)")
+    );
+
+    let (_, body) = get(app, "/makers/m1/fidelity/renewal").await;
+    assert_eq!(body["data"]["enabled"], json!(false));
+}
+
+#[tokio::test]
+async fn renewal_log_lists_records_newest_first() {
+    let (state, _) = seeded_state();
+    {
+        let mut mgr = state.lock().await;
+        let id = "m1".to_string();
+        mgr.record_renewal(
+            &id,
+            RenewalRecord {
+                at: 1_700_000_000,
+                height: 100,
+                step: RenewalStep::Redeem { index: 0 },
+                dry_run: false,
+                feerate: Some(2.0),
+                bond_index: Some(0),
+                txid: None,
+                error: Some("Bond 0 is already spent".to_string()),
+            },
+        );
+        mgr.record_renewal(
+            &id,
+            RenewalRecord {
+                at: 1_700_000_600,
+                height: 101,
+                step: RenewalStep::Create {
+                    amount: 50_000,
+                    locktime: 15_101,
+                },
+                dry_run: true,
+                feerate: Some(2.0),
+                bond_index: None,
+                txid: None,
+                error: None,
+            },
+        );
+    }
+
+    let (status, body) = get(
+        api_router().with_state(state),
+        "/makers/m1/fidelity/renewal/log",
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["data"],
+        json!([
+            {
+                "at": 1_700_000_600,
+                "height": 101,
+                "action": "create",
+                "dry_run": true,
+                "feerate": 2.0,
+                "bond_index": null,
+                "amount": 50_000,
+                "locktime": 15_101,
+                "txid": null,
+                "error": null
+            },
+            {
+                "at": 1_700_000_000,
+                "height": 100,
+                "action": "redeem",
+                "dry_run": false,
+                "feerate": 2.0,
+                "bond_index": 0,
+                "amount": null,
+                "locktime": null,
+                "txid": null,
+                "error": "Bond 0 is already spent"
+            }
+        ])
+    );
+}
+
+#[tokio::test]
+async fn renewal_evaluation_needs_the_chain_tip() {
+    let (app, _) = seeded_app();
+    let (status, body) = post(
+        app.clone(),
+        "/makers/m1/fidelity/renewal/evaluate",
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert!(body["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("Bitcoin Core unreachable"));
+
+    let (_, body) = get(app, "/makers/m1/fidelity/renewal/log").await;
+    assert_eq!(body["data"], json!([]));
+}
+
+#[tokio::test]
+async fn bond_value_estimate_without_other_makers() {
+    let (status, body) = get(
+        test_app(),
+        "/fidelity/value?amount=100000000&timelock=13000",
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let estimate = &body["data"];
+    assert!(estimate["bond_value"].as_u64().unwrap() > 0);
+    assert_eq!(estimate["timelock_allowed"], json!(true));
+    assert_eq!(estimate["advertised"], json!([]));
+    assert!(estimate["rank"].is_null());
+}
+
+#[tokio::test]
+async fn bond_value_rejects_zero_amount() {
+    let (status, body) = get(test_app(), "/fidelity/value?amount=0&timelock=13000").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(!body["success"].as_bool().unwrap_or(true));
+}
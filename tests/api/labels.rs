@@ -0,0 +1,70 @@
+//! Unit tests for label (address book) endpoints.
+
+use axum::http::StatusCode;
+use serde_json::json;
+
+use super::{delete, get, post, put, test_app};
+
+const TXID: &str = "f91d0a8a78462bc59398f2c5d7a84fcff491c26ba54c4833478b202796c8aafd";
+
+#[tokio::test]
+async fn list_labels_unknown_maker_is_404() {
+    let (status, body) = get(test_app(), "/makers/unknown/labels").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["error"], "Maker 'unknown' not found");
+}
+
+#[tokio::test]
+async fn set_label_unknown_maker_is_404() {
+    let (status, body) = put(
+        test_app(),
+        "/makers/unknown/labels",
+        json!({ "type": "tx", "ref": TXID, "label": "deposit" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(!body["success"].as_bool().unwrap_or(true));
+}
+
+#[tokio::test]
+async fn set_label_with_malformed_reference_is_400() {
+    let (status, body) = put(
+        test_app(),
+        "/makers/unknown/labels",
+        json!({ "type": "output", "ref": "not-an-outpoint", "label": "x" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "Invalid output reference 'not-an-outpoint'");
+}
+
+#[tokio::test]
+async fn delete_label_with_unknown_type_is_400() {
+    let (status, _) = delete(test_app(), &format!("/makers/unknown/labels/blob/{TXID}")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn delete_label_unknown_maker_is_404() {
+    let (status, _) = delete(test_app(), &format!("/makers/unknown/labels/tx/{TXID}")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn export_labels_unknown_maker_is_404() {
+    let (status, _) = get(test_app(), "/makers/unknown/labels/export").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn import_malformed_labels_is_400() {
+    // The JSON helper sends a single JSON value, which is a valid single-line import body
+    let (status, body) = post(
+        test_app(),
+        "/makers/unknown/labels/import",
+        json!({ "type": "tx", "ref": "nope" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().starts_with("Line 1:"));
+}
@@ -105,6 +105,23 @@ async fn create_without_credentials_is_400() {
     );
 }
 
+#[tokio::test]
+async fn create_with_path_like_id_is_400() {
+    for id in ["..", "a/b", "", "my maker"] {
+        let (status, body) = post(
+            test_app(),
+            "/makers",
+            json!({ "id": id, "rpc_user": "alice", "rpc_password": "pw" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{id}");
+        assert_eq!(
+            body["error"],
+            "Maker ID may only contain letters, digits, '-' and '_'"
+        );
+    }
+}
+
 #[tokio::test]
 async fn create_with_only_rpc_user_is_400() {
     let (status, body) = post(
@@ -117,6 +134,75 @@ async fn create_with_only_rpc_user_is_400() {
     assert!(!body["success"].as_bool().unwrap());
 }
 
+#[tokio::test]
+async fn create_restore_without_passphrase_is_400() {
+    let (status, body) = post(
+        test_app(),
+        "/makers",
+        json!({
+            "id": "test",
+            "rpc_user": "alice",
+            "rpc_password": "pass",
+            "restore": { "backup": "{}", "passphrase": "" }
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        body["error"],
+        "Restoring requires both the backup contents and its passphrase"
+    );
+}
+
+#[tokio::test]
+async fn create_unpersisted_password_without_password_is_400() {
+    let (status, body) = post(
+        test_app(),
+        "/makers",
+        json!({ "id": "test", "rpc_user": "alice", "rpc_password": "pass", "persist_password": false }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        body["error"],
+        "persist_password can only be disabled for password-protected wallets"
+    );
+}
+
+#[tokio::test]
+async fn unlock_unknown_maker_is_404() {
+    let (status, body) = post(
+        test_app(),
+        "/makers/unknown/unlock",
+        json!({ "password": "hunter2" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["error"], "Maker 'unknown' not found");
+}
+
+#[tokio::test]
+async fn change_password_unknown_maker_is_404() {
+    let (status, _) = post(
+        test_app(),
+        "/makers/unknown/password",
+        json!({ "new_password": "hunter2" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn change_password_to_empty_is_400() {
+    let (status, _) = post(
+        test_app(),
+        "/makers/unknown/password",
+        json!({ "new_password": "" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
 // 500 when Bitcoin is unavailable
 
 #[tokio::test]
@@ -0,0 +1,119 @@
+//! Tests for the Prometheus endpoint (`/metrics`).
+
+use std::collections::HashMap;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use http_body_util::BodyExt;
+use serde_json::json;
+use tower::ServiceExt;
+
+use maker_dashboard::{
+    api::{metrics, AppState},
+    maker_manager::history::{HistoryPoint, NodeSample, WalletPoint},
+};
+
+use super::seeded_state;
+
+/// GET /metrics → (status, content type, body text).
+async fn scrape(state: AppState) -> (StatusCode, String, String) {
+    let app = metrics::routes().with_state(state);
+    let req = Request::get("/metrics").body(Body::empty()).unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    let status = resp.status();
+    let content_type = resp.headers()[header::CONTENT_TYPE]
+        .to_str()
+        .unwrap()
+        .to_string();
+    let bytes = resp.into_body().collect().await.unwrap().to_bytes();
+    (
+        status,
+        content_type,
+        String::from_utf8(bytes.to_vec()).unwrap(),
+    )
+}
+
+fn write_report(data_dir: &std::path::Path, swap_id: &str, status: &str, fee: i64) {
+    let dir = data_dir.join("swap_reports");
+    std::fs::create_dir_all(&dir).unwrap();
+    let report = json!({
+        "swap_id": swap_id,
+        "role": "Maker",
+        "status": status,
+        "swap_duration_seconds": 60.0,
+        "start_timestamp": 940,
+        "end_timestamp": 1_000,
+        "network": "regtest",
+        "incoming_amount": 50_000,
+        "outgoing_amount": 49_000,
+        "fee_paid_or_earned": fee,
+        "timelock": 20,
+        "total_maker_fees": 0,
+        "mining_fee": 300,
+        "fee_percentage": 0.0,
+        "input_utxos": []
+    });
+    std::fs::write(dir.join(format!("{swap_id}.json")), report.to_string()).unwrap();
+}
+
+// 200 / success-path
+
+#[tokio::test]
+async fn metrics_are_served_from_the_sampler_snapshot() {
+    let (state, data_dir) = seeded_state();
+    write_report(&data_dir, "s1", "Success", 1_000);
+    write_report(&data_dir, "s2", "Failed", -400);
+    {
+        let mut mgr = state.lock().await;
+        let mut point = HistoryPoint::sample(1_700_000_000, true, false, Some(800));
+        point.wallet = Some(WalletPoint {
+            samples: 1,
+            regular: 70_000,
+            spendable: 60_000,
+            ..WalletPoint::default()
+        });
+        mgr.record_history(&"m1".to_string(), point);
+        mgr.record_nodes(HashMap::from([(
+            "127.0.0.1:1".to_string(),
+            NodeSample {
+                up: true,
+                height: Some(800),
+                progress: Some(1.0),
+            },
+        )]));
+    }
+
+    let (status, content_type, body) = scrape(state).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(content_type.starts_with("text/plain; version=0.0.4"));
+    for line in [
+        "maker_count 1",
+        "maker_sample_timestamp_seconds{maker=\"m1\"} 1700000000",
+        "maker_up{maker=\"m1\"} 1",
+        "maker_locked{maker=\"m1\"} 1",
+        "maker_balance_sats{maker=\"m1\",category=\"spendable\"} 60000",
+        "maker_swaps_total{maker=\"m1\",status=\"Success\"} 1",
+        "maker_swap_fees_earned_sats_total{maker=\"m1\"} 1000",
+        "maker_swap_fees_paid_sats_total{maker=\"m1\"} 400",
+        "maker_swap_mining_fees_sats_total{maker=\"m1\"} 600",
+        "bitcoind_block_height{rpc=\"127.0.0.1:1\"} 800",
+        "# TYPE maker_swap_fees_paid_sats_total counter",
+    ] {
+        assert!(
+            body.lines().any(|l| l == line),
+            "missing {line:?} in\n{body}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn unsampled_makers_have_no_liveness_or_wallet_figures() {
+    let (state, _) = seeded_state();
+    let (status, _, body) = scrape(state).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.lines().any(|l| l == "maker_locked{maker=\"m1\"} 1"));
+    assert!(!body.contains("maker_up{"));
+    assert!(!body.contains("maker_balance_sats{"));
+}
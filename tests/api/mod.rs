@@ -2,8 +2,10 @@
 //!
 //! Each sub-module tests one category of API endpoints.
 //! All tests use `test_app()` which builds a fresh router backed by an empty
-//! `MakerManager` — no Bitcoin RPC or real coinswap infrastructure required.
+//! `MakerManager`, or `seeded_app()` for one with a locked maker — no Bitcoin RPC or
+//! real coinswap infrastructure required.
 
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
@@ -17,12 +19,23 @@ use serde_json::Value;
 use tokio::sync::Mutex;
 use tower::ServiceExt;
 
-use maker_dashboard::{api::api_router, maker_manager::MakerManager};
+use maker_dashboard::{
+    api::{api_router, AppState},
+    maker_manager::MakerManager,
+};
 
+mod alerts;
+mod events;
+mod export;
+mod fees;
 mod fidelity;
+mod labels;
 mod makers;
+mod metrics;
 mod monitoring;
+mod transactions;
 mod wallet;
+mod webhooks;
 
 static COUNTER: AtomicU64 = AtomicU64::new(0);
 
@@ -40,6 +53,52 @@ pub fn test_app() -> Router {
     api_router().with_state(state)
 }
 
+/// Builds a fresh Router whose manager starts with one maker, `m1`. Its password isn't
+/// persisted, so it stays locked and no wallet or node is touched. Returns the router and
+/// the maker's (empty) data directory.
+pub fn seeded_app() -> (Router, PathBuf) {
+    let (state, data_dir) = seeded_state();
+    (api_router().with_state(state), data_dir)
+}
+
+/// The state behind [`seeded_app`], for tests that record into the manager first.
+pub fn seeded_state() -> (AppState, PathBuf) {
+    let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+    let config_dir =
+        std::env::temp_dir().join(format!("maker-api-seeded-{}-{}", std::process::id(), n));
+    if config_dir.exists() {
+        std::fs::remove_dir_all(&config_dir).unwrap();
+    }
+    let data_dir = config_dir.join("m1-data");
+    std::fs::create_dir_all(&data_dir).unwrap();
+    let makers = serde_json::json!({
+        "makers": {
+            "m1": {
+                "data_directory": data_dir,
+                "rpc": "127.0.0.1:1",
+                "zmq": "tcp://127.0.0.1:2",
+                "rpc_user": null,
+                "rpc_password": null,
+                "tor_auth": null,
+                "wallet_name": "m1",
+                "password": null,
+                "persist_password": false
+            }
+        }
+    });
+    std::fs::write(config_dir.join("makers.json"), makers.to_string()).unwrap();
+    let manager = MakerManager::new(config_dir).expect("MakerManager::new");
+    (Arc::new(Mutex::new(manager)), data_dir)
+}
+
+/// A router over a new manager for the config dir behind [`seeded_state`]'s `data_dir`, as
+/// after a dashboard restart.
+pub fn restarted_app(data_dir: &Path) -> Router {
+    let config_dir = data_dir.parent().unwrap().to_path_buf();
+    let manager = MakerManager::new(config_dir).expect("MakerManager::new");
+    api_router().with_state(Arc::new(Mutex::new(manager)))
+}
+
 /// GET request → (status, response JSON).
 pub async fn get(app: Router, uri: &str) -> (StatusCode, Value) {
     send(app, Request::get(uri).body(Body::empty()).unwrap()).await
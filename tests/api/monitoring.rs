@@ -3,7 +3,7 @@
 use axum::http::StatusCode;
 use serde_json::json;
 
-use super::{get, test_app};
+use super::{get, seeded_app, test_app};
 
 // 200 / success-path
 
@@ -17,8 +17,200 @@ async fn health_with_no_makers_returns_ok_status() {
     );
 }
 
+fn report(swap_id: &str, end: u64, status: &str) -> serde_json::Value {
+    json!({
+        "swap_id": swap_id,
+        "role": "Maker",
+        "status": status,
+        "swap_duration_seconds": 60.0,
+        "start_timestamp": end - 60,
+        "end_timestamp": end,
+        "network": "regtest",
+        "incoming_amount": 50_000,
+        "outgoing_amount": 49_000,
+        "fee_paid_or_earned": 1_000,
+        "timelock": 20,
+        "total_maker_fees": 0,
+        "mining_fee": 0,
+        "fee_percentage": 0.0,
+        "input_utxos": []
+    })
+}
+
+fn save_report(data_dir: &std::path::Path, report: &serde_json::Value) {
+    let dir = data_dir.join("swap_reports");
+    std::fs::create_dir_all(&dir).unwrap();
+    let swap_id = report["swap_id"].as_str().unwrap();
+    std::fs::write(dir.join(format!("{swap_id}.json")), report.to_string()).unwrap();
+}
+
+fn write_report(data_dir: &std::path::Path, swap_id: &str, end: u64, status: &str) {
+    save_report(data_dir, &report(swap_id, end, status));
+}
+
+#[tokio::test]
+async fn swap_reports_list_every_report_newest_first() {
+    let (app, data_dir) = seeded_app();
+    write_report(&data_dir, "s1", 1_000, "Success");
+    write_report(&data_dir, "s2", 2_000, "Failed");
+
+    let (status, body) = get(app.clone(), "/makers/m1/swap-reports").await;
+    assert_eq!(status, StatusCode::OK);
+    let ids: Vec<_> = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["swap_id"].clone())
+        .collect();
+    assert_eq!(ids, [json!("s2"), json!("s1")]);
+
+    let (status, body) = get(app.clone(), "/makers/m1/swap-reports/by-id/s1").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["end_timestamp"], json!(1_000));
+}
+
+#[tokio::test]
+async fn swap_report_pages_follow_the_cursor() {
+    let (app, data_dir) = seeded_app();
+    write_report(&data_dir, "s1", 1_000, "Success");
+    write_report(&data_dir, "s2", 2_000, "Failed");
+    write_report(&data_dir, "s3", 3_000, "Success");
+
+    let (status, body) = get(app.clone(), "/makers/m1/swap-reports/page?limit=2").await;
+    assert_eq!(status, StatusCode::OK);
+    let page = &body["data"];
+    assert_eq!(page["reports"].as_array().unwrap().len(), 2);
+    assert_eq!(page["reports"][0]["swap_id"], json!("s3"));
+    assert_eq!(page["next_cursor"], json!("2000:s2"));
+
+    let (_, body) = get(
+        app.clone(),
+        "/makers/m1/swap-reports/page?limit=2&cursor=2000:s2",
+    )
+    .await;
+    assert_eq!(body["data"]["reports"][0]["swap_id"], json!("s1"));
+    assert_eq!(body["data"]["next_cursor"], json!(null));
+
+    let (_, body) = get(app, "/makers/m1/swap-reports/page?status=success").await;
+    let ids: Vec<_> = body["data"]["reports"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["swap_id"].clone())
+        .collect();
+    assert_eq!(ids, [json!("s3"), json!("s1")]);
+}
+
+#[tokio::test]
+async fn recovery_groups_failures_and_links_recoveries() {
+    let (app, data_dir) = seeded_app();
+    write_report(&data_dir, "ok", 1_000, "Success");
+    let mut timed_out = report("timeout", 2_000, "Failed");
+    timed_out["error_message"] = json!("Connection timed out");
+    save_report(&data_dir, &timed_out);
+    let mut recovered = report("recovered", 3_000, "Failed");
+    recovered["error_message"] = json!("Invalid contract signature");
+    recovered["incoming_contract_txid"] = json!("aa".repeat(32));
+    recovered["recovery_txids"] = json!(["bb".repeat(32)]);
+    recovered["recovery_duration_seconds"] = json!(120.0);
+    save_report(&data_dir, &recovered);
+
+    let (status, body) = get(app.clone(), "/makers/m1/recovery").await;
+    assert_eq!(status, StatusCode::OK);
+    let data = &body["data"];
+    assert_eq!(data["failed"], json!(1));
+    assert_eq!(data["recovered"], json!(1));
+    assert_eq!(
+        data["categories"],
+        json!([
+            {
+                "category": "timeout",
+                "failed": 1,
+                "recovered": 0,
+                "last_seen": 2_000,
+                "last_error": "Connection timed out"
+            },
+            {
+                "category": "protocol",
+                "failed": 0,
+                "recovered": 1,
+                "last_seen": 3_000,
+                "last_error": "Invalid contract signature"
+            }
+        ])
+    );
+    let recoveries = data["recoveries"].as_array().unwrap();
+    assert_eq!(recoveries.len(), 1);
+    assert_eq!(recoveries[0]["swap_id"], json!("recovered"));
+    assert_eq!(recoveries[0]["category"], json!("protocol"));
+    assert_eq!(recoveries[0]["contract_txids"], json!(["aa".repeat(32)]));
+    assert_eq!(recoveries[0]["recovery_txids"], json!(["bb".repeat(32)]));
+    // The locked maker can't list its contract outputs, and its node is unreachable
+    assert_eq!(data["pending"], json!([]));
+    assert!(data["pending_error"].is_string());
+    assert_eq!(data["tip_height"], json!(null));
+
+    let (_, body) = get(app, "/makers/m1/recovery?to=2500").await;
+    assert_eq!(body["data"]["failed"], json!(1));
+    assert_eq!(body["data"]["recovered"], json!(0));
+    assert_eq!(body["data"]["recoveries"], json!([]));
+}
+
+#[tokio::test]
+async fn fleet_analytics_with_no_makers_is_empty() {
+    let (status, body) = get(test_app(), "/analytics?window=day&to=864000").await;
+    assert_eq!(status, StatusCode::OK);
+    let data = &body["data"];
+    assert_eq!(data["from"], json!(777600));
+    assert_eq!(data["bucket_secs"], json!(3600));
+    assert_eq!(data["buckets"].as_array().unwrap().len(), 24);
+    assert_eq!(data["totals"]["swaps"], json!(0));
+    assert_eq!(data["makers"], json!([]));
+}
+
+// 400 for invalid input
+
+#[tokio::test]
+async fn analytics_custom_window_requires_from() {
+    let (status, body) = get(test_app(), "/analytics?window=custom").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(!body["success"].as_bool().unwrap_or(true));
+}
+
 // 404 for unknown maker
 
+#[tokio::test]
+async fn balance_history_rejects_inverted_period() {
+    let (status, body) = get(
+        test_app(),
+        "/makers/unknown/history/balances?from=200&to=100",
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(!body["success"].as_bool().unwrap_or(true));
+}
+
+#[tokio::test]
+async fn balance_history_unknown_maker_is_404() {
+    let (status, body) = get(test_app(), "/makers/unknown/history/balances").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(!body["success"].as_bool().unwrap_or(true));
+}
+
+#[tokio::test]
+async fn returns_unknown_maker_is_404() {
+    let (status, body) = get(test_app(), "/makers/unknown/returns").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(!body["success"].as_bool().unwrap_or(true));
+}
+
+#[tokio::test]
+async fn analytics_unknown_maker_is_404() {
+    let (status, body) = get(test_app(), "/makers/unknown/analytics").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(!body["success"].as_bool().unwrap_or(true));
+}
+
 #[tokio::test]
 async fn status_unknown_maker_is_404() {
     let (status, body) = get(test_app(), "/makers/unknown/status").await;
@@ -33,6 +225,36 @@ async fn swaps_unknown_maker_is_404() {
     assert!(!body["success"].as_bool().unwrap_or(true));
 }
 
+#[tokio::test]
+async fn swap_report_pages_are_bounded() {
+    let (app, data_dir) = seeded_app();
+    for n in 0..51 {
+        write_report(&data_dir, &format!("s{n:02}"), 1_000 + n, "Success");
+    }
+
+    let (status, body) = get(app.clone(), "/makers/m1/swap-reports/page").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["reports"].as_array().unwrap().len(), 50);
+    assert_eq!(body["data"]["next_cursor"], json!("1001:s01"));
+
+    for limit in [0, 501] {
+        let uri = format!("/makers/m1/swap-reports/page?limit={limit}");
+        let (status, body) = get(app.clone(), &uri).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], json!("limit must be between 1 and 500"));
+    }
+}
+
+#[tokio::test]
+async fn swap_named_page_can_be_fetched() {
+    let (app, data_dir) = seeded_app();
+    write_report(&data_dir, "page", 1_000, "Success");
+
+    let (status, body) = get(app, "/makers/m1/swap-reports/by-id/page").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["swap_id"], json!("page"));
+}
+
 #[tokio::test]
 async fn logs_unknown_maker_is_404() {
     let (status, body) = get(test_app(), "/makers/unknown/logs").await;
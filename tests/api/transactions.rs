@@ -0,0 +1,116 @@
+//! Unit tests for transaction history, outgoing transaction tracking and fee bumping endpoints.
+
+use axum::http::StatusCode;
+use serde_json::json;
+
+use super::{get, post, restarted_app, seeded_app, test_app};
+
+const TXID: &str = "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
+const REPLACED: &str = "f91d0a8a78462bc59398f2c5d7a84fcff491c26ba54c4833478b202796c8aafd";
+
+#[tokio::test]
+async fn history_unknown_maker_is_404() {
+    let (status, body) = get(test_app(), "/makers/unknown/transactions?offset=0&limit=10").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["error"], "Maker 'unknown' not found");
+}
+
+#[tokio::test]
+async fn outgoing_unknown_maker_is_404() {
+    let (status, body) = get(test_app(), "/makers/unknown/transactions/outgoing").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["error"], "Maker 'unknown' not found");
+}
+
+#[tokio::test]
+async fn bump_checks_the_tracked_transaction() {
+    let (_, data_dir) = seeded_app();
+    let state_dir = data_dir.parent().unwrap().join("makers").join("m1");
+    std::fs::create_dir_all(&state_dir).unwrap();
+    let change = format!("{TXID}:1");
+    let tx = |txid: &str, created_at: u64, replaced_by: Option<&str>| {
+        json!({
+            "txid": txid,
+            "address": "bcrt1qtest",
+            "amount": 10_000,
+            "feerate": 5.0,
+            "fee": 705,
+            "vsize": 141,
+            "inputs": [],
+            "change": format!("{txid}:1"),
+            "created_at": created_at,
+            "replaced_by": replaced_by,
+        })
+    };
+    let outgoing = json!([tx(TXID, 100, None), tx(REPLACED, 50, Some(TXID))]);
+    std::fs::write(state_dir.join("outgoing_txs.json"), outgoing.to_string()).unwrap();
+    std::fs::write(
+        state_dir.join("frozen_utxos.json"),
+        json!([change]).to_string(),
+    )
+    .unwrap();
+    let app = restarted_app(&data_dir);
+
+    let (status, body) = get(app.clone(), "/makers/m1/transactions/outgoing").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"][0]["txid"], TXID);
+    assert_eq!(body["data"][1]["replaced_by"], TXID);
+
+    let bump = |txid: &str| format!("/makers/m1/transactions/{txid}/bump");
+    let (status, body) = post(
+        app.clone(),
+        &bump(TXID),
+        json!({ "method": "rbf", "feerate": 5.5 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        body["error"],
+        "Replacement feerate must be at least 6 sat/vB (original 5 sat/vB)"
+    );
+
+    let (status, body) = post(
+        app.clone(),
+        &bump(TXID),
+        json!({ "method": "cpfp", "feerate": 10.0 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(
+        body["error"],
+        format!("Change output {change} is frozen; unfreeze it to bump via CPFP")
+    );
+
+    let (status, body) = post(
+        app.clone(),
+        &bump(REPLACED),
+        json!({ "method": "rbf", "feerate": 10.0 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(
+        body["error"],
+        format!("Transaction {REPLACED} was already replaced by {TXID}")
+    );
+
+    let untracked = "0".repeat(64);
+    let (status, _) = post(
+        app.clone(),
+        &bump(&untracked),
+        json!({ "method": "rbf", "feerate": 10.0 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn bump_invalid_txid_is_400() {
+    let (status, body) = post(
+        test_app(),
+        "/makers/unknown/transactions/not-a-txid/bump",
+        json!({ "feerate": 10.0 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "Invalid txid 'not-a-txid'");
+}
@@ -3,7 +3,9 @@
 use axum::http::StatusCode;
 use serde_json::json;
 
-use super::{get, post, test_app};
+use super::{get, post, restarted_app, seeded_app, test_app};
+
+const OUTPOINT: &str = "f91d0a8a78462bc59398f2c5d7a84fcff491c26ba54c4833478b202796c8aafd:0";
 
 #[tokio::test]
 async fn balance_unknown_maker_is_404() {
@@ -65,3 +67,135 @@ async fn send_to_address_unknown_maker_is_404() {
     assert_eq!(status, StatusCode::NOT_FOUND);
     assert!(!body["success"].as_bool().unwrap_or(true));
 }
+
+#[tokio::test]
+async fn send_takes_exactly_one_fee_option() {
+    let (app, _) = seeded_app();
+    let (status, body) = post(
+        app.clone(),
+        "/makers/m1/send",
+        json!({ "address": "bcrt1qtest", "amount": 1000, "feerate": 2.0, "fee_target": "fast" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        body["error"],
+        "Specify either feerate or fee_target, not both"
+    );
+
+    let (status, body) = post(
+        app.clone(),
+        "/makers/m1/send",
+        json!({ "address": "bcrt1qtest", "amount": 1000 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        body["error"],
+        "Either feerate or fee_target must be provided"
+    );
+
+    // A preset needs the node's fee estimate, and m1's node is unreachable
+    let (status, _) = post(
+        app,
+        "/makers/m1/send",
+        json!({ "address": "bcrt1qtest", "amount": 1000, "fee_target": "fast" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test]
+async fn addresses_need_the_wallet_node() {
+    let (app, _) = seeded_app();
+    // Usage comes from the node, which is unreachable for m1
+    let (status, body) = get(app.clone(), "/makers/m1/addresses").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert!(body["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("listreceivedbyaddress failed"));
+
+    let (status, _) = get(app, "/makers/m1/address?type=p2pkh").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn freeze_malformed_outpoint_is_400() {
+    let (status, body) = post(test_app(), "/makers/unknown/utxos/nope/unfreeze", json!({})).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(!body["success"].as_bool().unwrap_or(true));
+}
+
+#[tokio::test]
+async fn frozen_utxo_survives_restart() {
+    let (app, data_dir) = seeded_app();
+    let (status, body) = post(
+        app.clone(),
+        &format!("/makers/m1/utxos/{OUTPOINT}/freeze"),
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["frozen"], true);
+    // The node is unreachable, so only the dashboard remembers the freeze
+    assert_eq!(body["data"]["core_locked"], false);
+
+    let (_, body) = get(app, "/makers/m1/utxos/frozen").await;
+    assert_eq!(body["data"], json!([OUTPOINT]));
+
+    // A new manager over the same config dir still lists the locked maker's frozen coin
+    let (status, body) = get(restarted_app(&data_dir), "/makers/m1/utxos/frozen").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"], json!([OUTPOINT]));
+}
+
+#[tokio::test]
+async fn backup_requires_confirmation_and_an_unlocked_wallet() {
+    let (app, _) = seeded_app();
+    let backup = |body| post(app.clone(), "/makers/m1/wallet/backup", body);
+    let (status, body) = backup(json!({ "confirm": "m2", "passphrase": "correct horse" })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        body["error"],
+        "Set `confirm` to 'm1' to export the wallet backup"
+    );
+
+    let (status, body) = backup(json!({ "confirm": "m1", "passphrase": "" })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "A backup passphrase is required");
+
+    let (status, body) = backup(json!({ "confirm": "m1", "passphrase": "correct horse" })).await;
+    assert_eq!(status, StatusCode::LOCKED);
+    assert_eq!(
+        body["error"],
+        "Maker 'm1' is locked; unlock it with its wallet password first"
+    );
+}
+
+#[tokio::test]
+async fn rescan_is_refused_without_the_node() {
+    let (app, _) = seeded_app();
+    let (status, body) = post(
+        app.clone(),
+        "/makers/m1/rescan",
+        json!({ "from_height": 100, "stop_height": 50 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "stop_height must not be below from_height");
+
+    for path in ["/makers/m1/rescan", "/makers/m1/descriptors/reimport"] {
+        let (status, body) = post(app.clone(), path, json!({ "from_height": 100 })).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert!(body["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("Bitcoin Core unreachable"));
+    }
+
+    // Refused scans leave no job behind in the maker's status
+    let (status, body) = get(app, "/makers/m1/status").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["data"]["rescan"].is_null());
+}
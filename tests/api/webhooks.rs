@@ -0,0 +1,284 @@
+//! Tests for the webhook endpoints (`/webhooks`), delivering to a local HTTP receiver.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+use tokio::sync::Mutex;
+
+use maker_dashboard::{
+    api::webhooks::deliver,
+    maker_manager::{
+        webhooks::{signature, Webhook},
+        MakerManager,
+    },
+};
+
+use super::{delete, get, post, put, test_app};
+
+/// A request received by [`receiver`]: lower-cased header lines and the body.
+struct Received {
+    headers: Vec<String>,
+    body: String,
+}
+
+impl Received {
+    fn header(&self, name: &str) -> Option<&str> {
+        let prefix = format!("{name}:");
+        self.headers
+            .iter()
+            .find_map(|line| line.strip_prefix(&prefix))
+            .map(str::trim)
+    }
+}
+
+/// Listens on a free local port and answers one request per status in `statuses`.
+fn receiver(statuses: Vec<u16>) -> (String, JoinHandle<Vec<Received>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    let handle = std::thread::spawn(move || {
+        let mut received = Vec::new();
+        for status in statuses {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut headers = Vec::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                headers.push(line.trim_end().to_ascii_lowercase());
+            }
+            let length = headers
+                .iter()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .map_or(0, |value| value.trim().parse().unwrap());
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 {status} X\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+            )
+            .unwrap();
+            received.push(Received {
+                headers,
+                body: String::from_utf8(body).unwrap(),
+            });
+        }
+        received
+    });
+    (url, handle)
+}
+
+// 200 / success-path
+
+#[tokio::test]
+async fn create_list_and_delete_webhook() {
+    let app = test_app();
+    let (status, body) = post(
+        app.clone(),
+        "/webhooks",
+        json!({ "url": "https://example.com/hook", "secret": "s3cret", "events": ["swap_failed"] }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let id = body["data"]["id"].as_u64().unwrap();
+    assert_eq!(body["data"]["has_secret"], true);
+    assert_eq!(body["data"]["enabled"], true);
+
+    let (status, body) = get(app.clone(), "/webhooks").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"][0]["events"], json!(["swap_failed"]));
+    assert!(!body.to_string().contains("s3cret"));
+
+    let (status, body) = delete(app.clone(), &format!("/webhooks/{id}")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["success"], true);
+    let (_, body) = get(app, "/webhooks").await;
+    assert_eq!(body["data"], json!([]));
+}
+
+#[tokio::test]
+async fn update_keeps_secret_unless_cleared() {
+    let app = test_app();
+    let (_, body) = post(
+        app.clone(),
+        "/webhooks",
+        json!({ "url": "https://example.com/hook", "secret": "s3cret" }),
+    )
+    .await;
+    let uri = format!("/webhooks/{}", body["data"]["id"]);
+
+    let (status, body) = put(
+        app.clone(),
+        &uri,
+        json!({ "url": "https://example.com/other", "enabled": false }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["url"], "https://example.com/other");
+    assert_eq!(body["data"]["has_secret"], true);
+    assert_eq!(body["data"]["enabled"], false);
+
+    let (_, body) = put(
+        app,
+        &uri,
+        json!({ "url": "https://example.com/other", "secret": "" }),
+    )
+    .await;
+    assert_eq!(body["data"]["has_secret"], false);
+}
+
+#[tokio::test]
+async fn test_delivery_is_signed_and_logged() {
+    let app = test_app();
+    let (url, received) = receiver(vec![200]);
+    let (_, body) = post(
+        app.clone(),
+        "/webhooks",
+        json!({ "url": url, "secret": "s3cret" }),
+    )
+    .await;
+    let id = body["data"]["id"].as_u64().unwrap();
+
+    let (status, body) = post(app.clone(), &format!("/webhooks/{id}/test"), json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["success"], true);
+    assert_eq!(body["data"]["status"], 200);
+    assert_eq!(body["data"]["event_type"], "ping");
+
+    let received = received.join().unwrap().remove(0);
+    let payload: Value = serde_json::from_str(&received.body).unwrap();
+    assert_eq!(payload["type"], "ping");
+    assert_eq!(payload["webhook_id"], id);
+    assert_eq!(received.header("x-webhook-event"), Some("ping"));
+    let timestamp: u64 = received
+        .header("x-webhook-timestamp")
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(timestamp.abs_diff(payload["at"].as_u64().unwrap()) <= 1);
+    assert_eq!(
+        received.header("x-webhook-signature"),
+        Some(signature("s3cret", timestamp, received.body.as_bytes()).as_str())
+    );
+
+    let (status, body) = get(app, &format!("/webhooks/{id}/deliveries")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn failed_test_delivery_is_logged() {
+    let app = test_app();
+    let (url, received) = receiver(vec![500]);
+    let (_, body) = post(app.clone(), "/webhooks", json!({ "url": url })).await;
+    let id = body["data"]["id"].as_u64().unwrap();
+
+    let (status, body) = post(app.clone(), &format!("/webhooks/{id}/test"), json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["success"], false);
+    assert_eq!(body["data"]["status"], 500);
+    let received = received.join().unwrap().remove(0);
+    assert!(received.header("x-webhook-signature").is_none());
+    assert!(received.header("x-webhook-timestamp").is_some());
+
+    let (_, body) = get(app, &format!("/webhooks/{id}/deliveries")).await;
+    assert_eq!(body["data"][0]["error"], "HTTP 500");
+}
+
+#[tokio::test]
+async fn failed_deliveries_are_retried_and_logged() {
+    let dir = std::env::temp_dir().join(format!("webhook-deliver-{}", std::process::id()));
+    let state = Arc::new(Mutex::new(MakerManager::new(dir.clone()).unwrap()));
+    let (url, received) = receiver(vec![503, 200]);
+    let webhook = state
+        .lock()
+        .await
+        .add_webhook(Webhook {
+            id: 0,
+            url,
+            secret: None,
+            events: Vec::new(),
+            enabled: true,
+        })
+        .unwrap();
+
+    let body = r#"{"type":"maker_crashed"}"#.to_string();
+    let delivery = deliver(
+        &state,
+        &webhook,
+        Some(1),
+        "maker_crashed",
+        body.clone(),
+        3,
+        Duration::from_millis(1),
+    )
+    .await;
+    assert_eq!(delivery.attempt, 2);
+    assert_eq!(delivery.status, Some(200));
+    assert!(delivery.error.is_none());
+    let bodies: Vec<String> = received
+        .join()
+        .unwrap()
+        .into_iter()
+        .map(|r| r.body)
+        .collect();
+    assert_eq!(bodies, [body.clone(), body]);
+
+    let log = state.lock().await.webhook_deliveries(webhook.id).unwrap();
+    assert_eq!(log.len(), 2);
+    assert_eq!(log[1].status, Some(503));
+    assert_eq!(log[1].error.as_deref(), Some("HTTP 503"));
+    drop(state);
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+// 4xx
+
+#[tokio::test]
+async fn create_with_invalid_url_is_400() {
+    let (status, body) = post(
+        test_app(),
+        "/webhooks",
+        json!({ "url": "ftp://example.com" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().contains("http"));
+}
+
+#[tokio::test]
+async fn create_with_unknown_event_is_400() {
+    let (status, body) = post(
+        test_app(),
+        "/webhooks",
+        json!({ "url": "https://example.com/hook", "events": ["swap_exploded"] }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().contains("swap_exploded"));
+}
+
+#[tokio::test]
+async fn unknown_webhook_is_404() {
+    let app = test_app();
+    let (status, _) = get(app.clone(), "/webhooks/42/deliveries").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = post(app.clone(), "/webhooks/42/test", json!({})).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = put(
+        app.clone(),
+        "/webhooks/42",
+        json!({ "url": "https://example.com" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = delete(app, "/webhooks/42").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -8,6 +8,7 @@
 //   - Verify files in each maker's data directory are present post-swap.
 //   - Restart both makers via POST /api/makers/{id}/restart.
 //   - Re-sync wallets and assert all balances / UTXO counts are still consistent.
+//   - Create, redeem and renew (dry run) a fidelity bond via /api/makers/{id}/fidelity.
 
 #![cfg(feature = "integration-test")]
 
@@ -235,6 +236,24 @@ impl ApiClient {
             .unwrap_or_else(|e| panic!("JSON decode PUT {path}: {e}"))
     }
 
+    /// POST that may be answered with an error status → (status, response JSON).
+    fn post_status(&self, path: &str, body: &Value) -> (u16, Value) {
+        let resp = match self
+            .agent
+            .post(&format!("{}{path}", self.base))
+            .send_json(body)
+        {
+            Ok(resp) => resp,
+            Err(ureq::Error::Status(_, resp)) => resp,
+            Err(e) => panic!("POST {path} failed: {e}"),
+        };
+        let status = resp.status();
+        let json = resp
+            .into_json()
+            .unwrap_or_else(|e| panic!("JSON decode POST {path}: {e}"));
+        (status, json)
+    }
+
     // - domain helpers
 
     fn create_maker(
@@ -290,7 +309,7 @@ impl ApiClient {
             resp["success"].as_bool().unwrap_or(false),
             "get_address '{id}': {resp}"
         );
-        resp["data"]
+        resp["data"]["address"]
             .as_str()
             .unwrap_or_else(|| panic!("no address in response"))
             .to_owned()
@@ -356,13 +375,13 @@ impl ApiClient {
         );
     }
 
-    fn list_fidelity(&self, id: &str) -> String {
+    fn list_fidelity(&self, id: &str) -> Vec<Value> {
         let resp: Value = self.get(&format!("/makers/{id}/fidelity"));
         assert!(
             resp["success"].as_bool().unwrap_or(false),
             "fidelity '{id}': {resp}"
         );
-        resp["data"].as_str().unwrap_or("").to_owned()
+        resp["data"].as_array().cloned().unwrap_or_default()
     }
 
     fn get_status(&self, id: &str) -> (bool, bool) {
@@ -839,8 +858,15 @@ fn test_maker_manager_integration() {
         );
     }
 
-    let _ = client.list_fidelity(MAKER_ALPHA_ID);
-    let _ = client.list_fidelity(MAKER_BETA_ID);
+    for id in [MAKER_ALPHA_ID, MAKER_BETA_ID] {
+        let bonds = client.list_fidelity(id);
+        assert!(
+            bonds
+                .iter()
+                .any(|b| b["advertised"].as_bool() == Some(true)),
+            "'{id}' advertises no fidelity bond: {bonds:?}"
+        );
+    }
 
     // Config update is preserved after restart
     let detail_post = client.get_maker_detail(MAKER_ALPHA_ID);
@@ -850,6 +876,105 @@ fn test_maker_manager_integration() {
         "wallet_name not preserved after restart"
     );
 
+    // Fidelity bond lifecycle: preview, create, refuse early redemption, redeem once expired
+    println!("[INFO] Creating and redeeming a short fidelity bond");
+    let bonds_path = format!("/makers/{MAKER_BETA_ID}/fidelity");
+    let preview: Value = client.post_json(
+        &bonds_path,
+        &serde_json::json!({ "amount": 1_000_000, "timelock": 10, "feerate": 2.0, "dry_run": true }),
+    );
+    assert!(
+        preview["success"].as_bool().unwrap_or(false),
+        "bond preview failed: {preview}"
+    );
+    let funding = &preview["data"];
+    assert!(
+        funding["index"].is_null(),
+        "dry run created a bond: {funding}"
+    );
+    assert_eq!(funding["amount"].as_u64(), Some(1_000_000));
+    assert_eq!(funding["timelock"].as_u64(), Some(10));
+    assert_eq!(funding["sufficient"].as_bool(), Some(true));
+    let fee = funding["fee"].as_u64().expect("preview fee");
+    assert!(fee > 0, "preview without a fee: {funding}");
+    assert_eq!(funding["required"].as_u64(), Some(1_000_000 + fee));
+
+    let (status, created) = client.post_status(
+        &bonds_path,
+        &serde_json::json!({ "amount": 1_000_000, "timelock": 10, "feerate": 2.0 }),
+    );
+    assert_eq!(status, 201, "bond creation failed: {created}");
+    let index = created["data"]["index"]
+        .as_u64()
+        .expect("created bond index");
+    assert!(
+        created["data"]["txid"].is_string(),
+        "no funding txid: {created}"
+    );
+    generate_blocks(&bitcoind, 1);
+    client.sync_wallet(MAKER_BETA_ID);
+
+    let find_bond = |client: &ApiClient| {
+        client
+            .list_fidelity(MAKER_BETA_ID)
+            .into_iter()
+            .find(|b| b["index"].as_u64() == Some(index))
+            .unwrap_or_else(|| panic!("bond {index} not listed"))
+    };
+    let bond = find_bond(&client);
+    assert_eq!(bond["amount"].as_u64(), Some(1_000_000));
+    assert_eq!(bond["spent"].as_bool(), Some(false));
+    assert_eq!(bond["expired"].as_bool(), Some(false));
+    assert_eq!(bond["blocks_to_expiry"].as_u64(), Some(9));
+
+    let redeem_path = format!("{bonds_path}/{index}/redeem");
+    let redeem_body = serde_json::json!({ "feerate": 2.0 });
+    let (status, early) = client.post_status(&redeem_path, &redeem_body);
+    assert_eq!(status, 409, "unexpired bond redeemed: {early}");
+
+    generate_blocks(&bitcoind, 10);
+    client.sync_wallet(MAKER_BETA_ID);
+    let (status, redeemed) = client.post_status(&redeem_path, &redeem_body);
+    assert_eq!(status, 200, "bond redemption failed: {redeemed}");
+    assert!(
+        redeemed["data"].is_string(),
+        "no redemption txid: {redeemed}"
+    );
+    generate_blocks(&bitcoind, 1);
+    client.sync_wallet(MAKER_BETA_ID);
+    assert_eq!(find_bond(&client)["spent"].as_bool(), Some(true));
+
+    // A renewal window as long as the bonds' remaining time plans a new bond (dry run)
+    println!("[INFO] Evaluating a dry-run fidelity renewal");
+    let policy: Value = client.put_json(
+        &format!("{bonds_path}/renewal"),
+        &serde_json::json!({
+            "enabled": true,
+            "blocks_before_expiry": 14_999,
+            "reuse_redeemed_funds": false,
+            "dry_run": true,
+            "feerate": 2.0
+        }),
+    );
+    assert!(
+        policy["success"].as_bool().unwrap_or(false),
+        "renewal policy rejected: {policy}"
+    );
+    let (status, evaluated) =
+        client.post_status(&format!("{bonds_path}/renewal/evaluate"), &Value::Null);
+    assert_eq!(status, 200, "renewal evaluation failed: {evaluated}");
+    let records = evaluated["data"].as_array().expect("renewal records");
+    assert_eq!(records.len(), 1, "unexpected renewal plan: {evaluated}");
+    assert_eq!(records[0]["action"].as_str(), Some("create"));
+    assert_eq!(records[0]["dry_run"].as_bool(), Some(true));
+    assert_eq!(records[0]["amount"].as_u64(), Some(TEST_FIDELITY_AMOUNT));
+    assert!(
+        records[0]["txid"].is_null(),
+        "dry run broadcast: {evaluated}"
+    );
+    let log: Value = client.get(&format!("{bonds_path}/renewal/log"));
+    assert_eq!(log["data"][0]["action"].as_str(), Some("create"));
+
     println!(
         "[INFO] All checks passed — alpha: {post_alpha_spendable} sats, beta: {post_beta_spendable} sats"
     );